@@ -1,58 +1,220 @@
+use std::collections::HashMap;
 use std::env;
+use std::sync::{Mutex, OnceLock, RwLock};
+use std::time::SystemTime;
 
 /// Runtime configuration for GitHub API clients.
 /// Values are sourced from environment variables with sensible defaults.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Config {
     pub token: String,
+    /// When set, the token is re-read from this file whenever its mtime changes.
+    pub token_file: Option<String>,
     pub api_url: String,
     pub graphql_url: String,
     pub api_version: String,
     pub user_agent: String,
     pub timeout_secs: u64,
+    pub graphql_timeout_secs: u64,
+    /// Add `meta.warning` once remaining rate budget falls below this percent of the limit (0 = off).
+    pub rate_warn_percent: u32,
+    /// Cap on a tools/call result; larger outputs are cut down and marked `truncated` (0 = off).
+    pub max_response_bytes: usize,
+    /// Wall-clock budget for one tools/call across all its requests and retries (None = off).
+    pub call_deadline_secs: Option<u64>,
+    /// Log a warning for any single request slower than this many milliseconds (0 = off).
+    pub slow_ms: u64,
 }
 
+pub const DEFAULT_RATE_WARN_PERCENT: u32 = 10;
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 8 * 1024 * 1024;
+
+// Process-wide config cache; populated on first use and swapped by `Config::reload`.
+static CURRENT: OnceLock<RwLock<Option<Config>>> = OnceLock::new();
+
+// Last token read from a token file, keyed by path and mtime.
+struct TokenFileCache {
+    path: String,
+    mtime: Option<SystemTime>,
+    token: String,
+}
+
+static TOKEN_FILE: Mutex<Option<TokenFileCache>> = Mutex::new(None);
+
 impl Config {
     /// Load configuration from environment.
     ///
     /// Env vars:
-    /// - GITHUB_TOKEN (or GH_TOKEN) [required]
+    /// - GITHUB_TOKEN (or GH_TOKEN) [required unless GITHUB_MCP_TOKEN_FILE is set]
+    /// - GITHUB_MCP_TOKEN_FILE (token file; re-read when its mtime changes)
     /// - GITHUB_API_URL (default: https://api.github.com)
     /// - GITHUB_GRAPHQL_URL (default: <GITHUB_API_URL>/graphql)
     /// - GITHUB_API_VERSION (default: 2022-11-28)
     /// - GITHUB_HTTP_TIMEOUT_SECS (default: 30)
+    /// - GITHUB_GRAPHQL_TIMEOUT_SECS (default: GITHUB_HTTP_TIMEOUT_SECS)
     /// - GITHUB_USER_AGENT (default: github-mcp/<version>)
+    /// - GITHUB_MCP_RATE_WARN_PERCENT (default: 10; 0 disables the warning)
+    /// - GITHUB_MCP_MAX_RESPONSE_BYTES (default: 8 MiB; 0 disables the cap)
+    /// - GITHUB_MCP_CALL_DEADLINE_SECS (default: unset; 0 also means no deadline)
+    /// - GITHUB_MCP_SLOW_MS (default: 0, off; e.g. 5000 warns about requests over 5s)
+    ///
+    /// When GITHUB_MCP_CONFIG_FILE points to a `KEY=VALUE` file, its entries override the
+    /// variables above; this is what makes a reload observe new values. The same file's
+    /// tool gating keys (GITHUB_MCP_READ_ONLY etc.) are read by the server's gating policy.
     pub fn from_env() -> Result<Self, String> {
-        let token = env::var("GITHUB_TOKEN")
-            .or_else(|_| env::var("GH_TOKEN"))
-            .map_err(|_| "Missing GITHUB_TOKEN or GH_TOKEN".to_string())?;
+        let overrides = env::var("GITHUB_MCP_CONFIG_FILE")
+            .ok()
+            .map(|p| read_env_file(&p))
+            .transpose()?
+            .unwrap_or_default();
+        let var = |k: &str| {
+            overrides
+                .get(k)
+                .cloned()
+                .ok_or(())
+                .or_else(|_| env::var(k).map_err(|_| ()))
+        };
+
+        let token_file = var("GITHUB_MCP_TOKEN_FILE").ok().filter(|p| !p.is_empty());
+        let token = match &token_file {
+            Some(path) => read_token_file(path)?,
+            None => var("GITHUB_TOKEN")
+                .or_else(|_| var("GH_TOKEN"))
+                .map_err(|_| "Missing GITHUB_TOKEN or GH_TOKEN".to_string())?,
+        };
 
         let api_url =
-            env::var("GITHUB_API_URL").unwrap_or_else(|_| "https://api.github.com".to_string());
-        let graphql_url = env::var("GITHUB_GRAPHQL_URL").unwrap_or_else(|_| {
+            var("GITHUB_API_URL").unwrap_or_else(|_| "https://api.github.com".to_string());
+        let graphql_url = var("GITHUB_GRAPHQL_URL").unwrap_or_else(|_| {
             let mut base = api_url.trim_end_matches('/').to_string();
             base.push_str("/graphql");
             base
         });
-        let api_version =
-            env::var("GITHUB_API_VERSION").unwrap_or_else(|_| "2022-11-28".to_string());
-        let timeout_secs = env::var("GITHUB_HTTP_TIMEOUT_SECS")
+        let api_version = var("GITHUB_API_VERSION").unwrap_or_else(|_| "2022-11-28".to_string());
+        let timeout_secs = var("GITHUB_HTTP_TIMEOUT_SECS")
             .ok()
             .and_then(|s| s.parse::<u64>().ok())
             .unwrap_or(30);
+        let graphql_timeout_secs = var("GITHUB_GRAPHQL_TIMEOUT_SECS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(timeout_secs);
         let default_ua = format!(
             "github-mcp/{} (+https://github.com/HautechAI/github-mcp)",
             env::var("CARGO_PKG_VERSION").unwrap_or_else(|_| "0.0.0".into())
         );
-        let user_agent = env::var("GITHUB_USER_AGENT").unwrap_or(default_ua);
+        let user_agent = var("GITHUB_USER_AGENT").unwrap_or(default_ua);
+        let rate_warn_percent = var("GITHUB_MCP_RATE_WARN_PERCENT")
+            .ok()
+            .and_then(|s| s.parse::<u32>().ok())
+            .map(|p| p.min(100))
+            .unwrap_or(DEFAULT_RATE_WARN_PERCENT);
+        let max_response_bytes = var("GITHUB_MCP_MAX_RESPONSE_BYTES")
+            .ok()
+            .and_then(|s| s.parse::<usize>().ok())
+            .unwrap_or(DEFAULT_MAX_RESPONSE_BYTES);
+        let call_deadline_secs = var("GITHUB_MCP_CALL_DEADLINE_SECS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .filter(|s| *s > 0);
+        let slow_ms = var("GITHUB_MCP_SLOW_MS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(0);
 
         Ok(Self {
             token,
+            token_file,
             api_url,
             graphql_url,
             api_version,
             user_agent,
             timeout_secs,
+            graphql_timeout_secs,
+            rate_warn_percent,
+            max_response_bytes,
+            call_deadline_secs,
+            slow_ms,
         })
     }
+
+    /// Cached configuration, loaded from env on first use.
+    /// All fields are hot-reloadable: handlers fetch their HTTP client per request from this
+    /// (see `http::shared_client`).
+    pub fn current() -> Result<Self, String> {
+        let lock = CURRENT.get_or_init(|| RwLock::new(None));
+        if let Some(cfg) = lock.read().ok().and_then(|g| g.clone()) {
+            return Ok(cfg);
+        }
+        let cfg = Self::from_env()?;
+        if let Ok(mut g) = lock.write() {
+            *g = Some(cfg.clone());
+        }
+        Ok(cfg)
+    }
+
+    /// Token to send with the next request. With a token file, the file is re-read when its
+    /// mtime changes so external rotation is picked up; if it becomes unreadable or empty the
+    /// last good token is kept.
+    pub fn current_token(&self) -> String {
+        let Some(path) = &self.token_file else {
+            return self.token.clone();
+        };
+        let mtime = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        let mut cache = TOKEN_FILE.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(c) = cache.as_ref() {
+            if &c.path == path && mtime.is_some() && c.mtime == mtime {
+                return c.token.clone();
+            }
+        }
+        let token = match read_token_file(path) {
+            Ok(t) => t,
+            Err(e) => {
+                log::warn!("token file: {}; keeping previous token", e);
+                return cache
+                    .as_ref()
+                    .filter(|c| &c.path == path)
+                    .map(|c| c.token.clone())
+                    .unwrap_or_else(|| self.token.clone());
+            }
+        };
+        *cache = Some(TokenFileCache {
+            path: path.clone(),
+            mtime,
+            token: token.clone(),
+        });
+        token
+    }
+
+    /// Re-read configuration and swap the cached copy. On error the previous config is kept.
+    /// Returns whether the effective configuration changed.
+    pub fn reload() -> Result<bool, String> {
+        let next = Self::from_env()?;
+        let lock = CURRENT.get_or_init(|| RwLock::new(None));
+        let mut g = lock.write().map_err(|e| e.to_string())?;
+        let changed = g.as_ref() != Some(&next);
+        *g = Some(next);
+        Ok(changed)
+    }
+}
+
+fn read_token_file(path: &str) -> Result<String, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    let token = text.trim();
+    if token.is_empty() {
+        return Err(format!("{}: empty token file", path));
+    }
+    Ok(token.to_string())
+}
+
+/// Parse a `KEY=VALUE` file (blank lines and `#` comments ignored).
+pub fn read_env_file(path: &str) -> Result<HashMap<String, String>, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    Ok(text
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .filter_map(|l| l.split_once('='))
+        .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+        .collect())
 }
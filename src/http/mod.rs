@@ -1,16 +1,23 @@
 use crate::config::Config;
 // Re-export canonical RateMeta from shared types to avoid duplication and unify JSON shape.
-pub use crate::types::RateMeta;
+pub use crate::types::{ErrorCode, RateMeta};
 use base64::Engine; // for URL_SAFE_NO_PAD.encode/decode
 use log::warn;
-use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, RETRY_AFTER, USER_AGENT};
+use reqwest::header::{
+    HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE, RETRY_AFTER, USER_AGENT,
+};
 use reqwest::{Client, StatusCode};
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+pub mod node_ids;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ErrorInfo {
-    pub code: String,
+    pub code: ErrorCode,
     pub message: String,
     pub retriable: bool,
 }
@@ -29,10 +36,43 @@ pub struct RestResponse<T> {
     pub headers: Option<HeaderMap>,
 }
 
+const DEFAULT_ACCEPT: &str = "application/vnd.github+json";
+
+// Per-call header overrides for endpoints that need a preview media type or a pinned API
+// version. Unset fields fall back to `application/vnd.github+json` and `Config.api_version`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestOptions<'a> {
+    pub accept: Option<&'a str>,
+    pub api_version: Option<&'a str>,
+    // Statuses treated as success. None means any 2xx, which already covers 202 Accepted
+    // for endpoints that queue work asynchronously.
+    pub accept_statuses: Option<&'a [StatusCode]>,
+}
+
+impl<'a> RequestOptions<'a> {
+    fn accept(&self) -> &'a str {
+        self.accept.unwrap_or(DEFAULT_ACCEPT)
+    }
+
+    fn api_version<'c>(&self, cfg: &'c Config) -> &'c str
+    where
+        'a: 'c,
+    {
+        self.api_version.unwrap_or(&cfg.api_version)
+    }
+
+    fn accepts(&self, status: StatusCode) -> bool {
+        match self.accept_statuses {
+            Some(list) => list.contains(&status),
+            None => status.is_success(),
+        }
+    }
+}
+
 pub fn build_client(cfg: &Config) -> reqwest::Result<Client> {
     let mut default_headers = HeaderMap::new();
     default_headers.insert(USER_AGENT, HeaderValue::from_str(&cfg.user_agent).unwrap());
-    // Authorization header is injected per request to allow token rotation later.
+    // Authorization header is injected per request so token-file rotation is picked up.
     let builder = Client::builder()
         .default_headers(default_headers)
         .timeout(Duration::from_secs(cfg.timeout_secs))
@@ -42,28 +82,117 @@ pub fn build_client(cfg: &Config) -> reqwest::Result<Client> {
     builder.build()
 }
 
-fn auth_header(token: &str) -> HeaderValue {
-    HeaderValue::from_str(&format!("Bearer {}", token)).expect("valid header")
+// Handlers share one client so keep-alive connections and TLS sessions survive across
+// tools/call. Only the user agent and timeout are baked into a client (the token is injected
+// per request), so a config reload that changes either replaces it.
+type ClientKey = (String, u64);
+static SHARED_CLIENT: Mutex<Option<(ClientKey, Client)>> = Mutex::new(None);
+static CLIENT_BUILDS: AtomicUsize = AtomicUsize::new(0);
+
+pub fn shared_client(cfg: &Config) -> reqwest::Result<Client> {
+    let key = (cfg.user_agent.clone(), cfg.timeout_secs);
+    let mut slot = SHARED_CLIENT.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((k, client)) = slot.as_ref() {
+        if *k == key {
+            return Ok(client.clone());
+        }
+    }
+    let client = build_client(cfg)?;
+    CLIENT_BUILDS.fetch_add(1, Ordering::Relaxed);
+    *slot = Some((key, client.clone()));
+    Ok(client)
+}
+
+fn auth_header(cfg: &Config) -> HeaderValue {
+    HeaderValue::from_str(&format!("Bearer {}", cfg.current_token())).expect("valid header")
 }
 
 pub fn map_status_to_error(status: StatusCode, message: String) -> ErrorInfo {
     let (code, retriable) = match status {
-        StatusCode::BAD_REQUEST => ("bad_request", false),
-        StatusCode::UNAUTHORIZED => ("unauthorized", false),
-        StatusCode::FORBIDDEN => ("forbidden", false),
-        StatusCode::NOT_FOUND => ("not_found", false),
-        StatusCode::CONFLICT => ("conflict", false),
-        StatusCode::TOO_MANY_REQUESTS => ("rate_limited", true),
-        s if s.is_server_error() => ("upstream_error", true),
-        _ => ("server_error", false),
+        StatusCode::BAD_REQUEST => (ErrorCode::BadRequest, false),
+        StatusCode::UNAUTHORIZED => (ErrorCode::Unauthorized, false),
+        StatusCode::FORBIDDEN => (ErrorCode::Forbidden, false),
+        StatusCode::NOT_FOUND => (ErrorCode::NotFound, false),
+        StatusCode::CONFLICT => (ErrorCode::Conflict, false),
+        StatusCode::TOO_MANY_REQUESTS => (ErrorCode::RateLimited, true),
+        s if s.is_server_error() => (ErrorCode::UpstreamError, true),
+        _ => (ErrorCode::ServerError, false),
     };
     ErrorInfo {
-        code: code.to_string(),
+        code,
         message,
         retriable,
     }
 }
 
+// Like map_status_to_error, but inspects response headers first. A 403 carrying
+// `X-GitHub-SSO: required; url=...` means the token is not authorized for the org's SAML SSO;
+// surface it as `sso_required` with the authorization URL so the user can fix it. A 404 keeps
+// `not_found` but its message notes that it may be a permissions issue.
+pub fn map_rest_error(status: StatusCode, headers: &HeaderMap, message: String) -> ErrorInfo {
+    if status == StatusCode::FORBIDDEN {
+        if let Some(sso) = headers.get("x-github-sso").and_then(|v| v.to_str().ok()) {
+            if sso.trim_start().starts_with("required") {
+                let url = sso
+                    .split(';')
+                    .filter_map(|p| p.trim().strip_prefix("url="))
+                    .next();
+                let message = match url {
+                    Some(u) => format!(
+                        "SAML SSO authorization required for this token; authorize it at {}",
+                        u
+                    ),
+                    None => format!(
+                        "SAML SSO authorization required for this token: {}",
+                        message
+                    ),
+                };
+                return ErrorInfo {
+                    code: ErrorCode::SsoRequired,
+                    message,
+                    retriable: false,
+                };
+            }
+        }
+    }
+    if status == StatusCode::NOT_FOUND {
+        return map_status_to_error(status, not_found_hint(headers, message));
+    }
+    map_status_to_error(status, message)
+}
+
+// GitHub answers 404 (not 403) for private resources the token cannot see, so a `not_found`
+// may really be an access problem. Say so in the message; when a classic token's
+// `X-OAuth-Scopes` shows it lacks `repo`, name the scopes it does have.
+fn not_found_hint(headers: &HeaderMap, message: String) -> String {
+    let scopes = headers
+        .get("x-oauth-scopes")
+        .and_then(|v| v.to_str().ok())
+        .map(|s| {
+            s.split(',')
+                .map(|p| p.trim())
+                .filter(|p| !p.is_empty())
+                .collect::<Vec<_>>()
+        });
+    match scopes {
+        Some(scopes) if !scopes.contains(&"repo") => format!(
+            "{} (the token's scopes [{}] do not include `repo`; private resources are reported as not found)",
+            message,
+            scopes.join(", ")
+        ),
+        _ => format!(
+            "{} (GitHub also returns 404 for private resources the token cannot access)",
+            message
+        ),
+    }
+}
+
+// Rate-limit resets arrive as Unix epoch seconds; expose them like every other timestamp.
+pub fn epoch_to_rfc3339(epoch: i64) -> Option<String> {
+    chrono::DateTime::<chrono::Utc>::from_timestamp(epoch, 0)
+        .map(|t| t.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+}
+
 pub fn extract_rate_from_rest(headers: &HeaderMap) -> RateMeta {
     let remaining = headers
         .get("x-ratelimit-remaining")
@@ -77,18 +206,32 @@ pub fn extract_rate_from_rest(headers: &HeaderMap) -> RateMeta {
         .get("x-ratelimit-reset")
         .and_then(|v| v.to_str().ok())
         .and_then(|s| s.parse::<i64>().ok())
-        .map(|epoch| {
-            chrono::DateTime::<chrono::Utc>::from_timestamp(epoch, 0)
-                .unwrap()
-                .to_rfc3339()
-        });
+        .and_then(epoch_to_rfc3339);
     RateMeta {
         remaining,
         used,
         reset_at,
+        cost: None,
+        node_count: None,
+    }
+}
+
+// Normalize an upstream timestamp to RFC3339 UTC with second precision ("2025-01-01T00:00:00Z").
+// GraphQL already uses this form; REST payloads occasionally carry offsets or fractional seconds.
+// Values that don't parse are passed through unchanged.
+pub fn normalize_ts(s: String) -> String {
+    match chrono::DateTime::parse_from_rfc3339(&s) {
+        Ok(dt) => dt
+            .with_timezone(&chrono::Utc)
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        Err(_) => s,
     }
 }
 
+pub fn normalize_ts_opt(s: Option<String>) -> Option<String> {
+    s.map(normalize_ts)
+}
+
 // Percent-encode a single URL path segment per RFC3986 (encode all but unreserved)
 // Unreserved: ALPHA / DIGIT / "-" / "." / "_" / "~"
 pub fn encode_path_segment(s: &str) -> String {
@@ -112,7 +255,57 @@ pub fn encode_path_segment(s: &str) -> String {
     out
 }
 
-fn compute_backoff(attempt: u32, retry_after: Option<Duration>) -> Duration {
+// Encode each '/'-separated segment but keep the separators, for multi-segment values
+// such as branch names ("feature/x") or repository file paths.
+pub fn encode_path(s: &str) -> String {
+    s.split('/')
+        .map(encode_path_segment)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// GITHUB_MCP_DEBUG=1 traces every HTTP attempt to stderr; the env is read once.
+pub fn debug_enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| std::env::var("GITHUB_MCP_DEBUG").ok().as_deref() == Some("1"))
+}
+
+// One line per attempt; request bodies are never printed. A missing status means the
+// request failed before a response arrived.
+fn trace_attempt(
+    method: &str,
+    url: &str,
+    status: Option<StatusCode>,
+    attempt: u32,
+    has_body: bool,
+) {
+    if !debug_enabled() {
+        return;
+    }
+    let status = status.map_or_else(|| "transport-error".to_string(), |s| s.as_u16().to_string());
+    let body = if has_body { " body=<redacted>" } else { "" };
+    eprintln!(
+        "[debug] {} {} status={} attempt={}{}",
+        method, url, status, attempt, body
+    );
+}
+
+// GITHUB_MCP_SLOW_MS: warn about any single request slower than this, retries counted
+// separately, so upstream latency shows up without turning on debug tracing.
+fn warn_if_slow(cfg: &Config, method: &str, url: &str, sent: Instant) {
+    let elapsed = sent.elapsed();
+    if cfg.slow_ms > 0 && elapsed >= Duration::from_millis(cfg.slow_ms) {
+        warn!(
+            "slow request: {} {} took {} ms (GITHUB_MCP_SLOW_MS={})",
+            method,
+            url,
+            elapsed.as_millis(),
+            cfg.slow_ms
+        );
+    }
+}
+
+pub fn compute_backoff(attempt: u32, retry_after: Option<Duration>) -> Duration {
     if let Some(d) = retry_after {
         return d;
     }
@@ -123,35 +316,116 @@ fn compute_backoff(attempt: u32, retry_after: Option<Duration>) -> Duration {
     Duration::from_millis(max / 2 + jitter)
 }
 
+thread_local! {
+    static CALL_DEADLINE: Cell<Option<(Instant, bool)>> = const { Cell::new(None) };
+}
+
+// Scopes the GITHUB_MCP_CALL_DEADLINE_SECS deadline to one tools/call. Every helper checks it
+// before each attempt, so the retries of all the requests a handler makes share one budget.
+// Thread-local like the output flags: handlers block_on their future on the calling thread.
+pub struct CallDeadlineGuard(Option<(Instant, bool)>);
+
+impl CallDeadlineGuard {
+    pub fn set(deadline: Option<Instant>) -> Self {
+        Self(CALL_DEADLINE.with(|c| c.replace(deadline.map(|d| (d, false)))))
+    }
+
+    // Installs the earliest of the deadline already in force (an enclosing batch call), the
+    // operator's (GITHUB_MCP_CALL_DEADLINE_SECS) and the client's `_timeout_secs`, so neither a
+    // client nor a nested call can extend the budget. Running out of a client deadline reports
+    // `timeout`.
+    pub fn set_earliest(operator: Option<Instant>, client: Option<Instant>) -> Self {
+        let outer = CALL_DEADLINE.with(|c| c.get());
+        let deadline = [
+            outer,
+            operator.map(|d| (d, false)),
+            client.map(|d| (d, true)),
+        ]
+        .into_iter()
+        .flatten()
+        .min_by_key(|(d, _)| *d);
+        Self(CALL_DEADLINE.with(|c| c.replace(deadline)))
+    }
+}
+
+impl Drop for CallDeadlineGuard {
+    fn drop(&mut self) {
+        CALL_DEADLINE.with(|c| c.set(self.0));
+    }
+}
+
+fn check_deadline() -> Result<(), ErrorInfo> {
+    match CALL_DEADLINE.with(|c| c.get()) {
+        Some((d, false)) if Instant::now() >= d => Err(ErrorInfo {
+            code: ErrorCode::DeadlineExceeded,
+            message: "call deadline exceeded (GITHUB_MCP_CALL_DEADLINE_SECS)".into(),
+            retriable: true,
+        }),
+        Some((d, true)) if Instant::now() >= d => Err(ErrorInfo {
+            code: ErrorCode::Timeout,
+            message: "call exceeded its _timeout_secs".into(),
+            retriable: true,
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn deadline_response<T>(err: ErrorInfo) -> RestResponse<T> {
+    RestResponse {
+        value: None,
+        meta: Meta { rate: None },
+        error: Some(err),
+        status: StatusCode::REQUEST_TIMEOUT,
+        headers: None,
+    }
+}
+
+// Caps a backoff sleep or a request timeout at what is left of the call deadline.
+pub fn within_deadline(d: Duration) -> Duration {
+    match CALL_DEADLINE.with(|c| c.get()) {
+        Some((deadline, _)) => d.min(deadline.saturating_duration_since(Instant::now())),
+        None => d,
+    }
+}
+
 pub async fn rest_get_json<T: for<'de> Deserialize<'de>>(
     client: &Client,
     cfg: &Config,
     path: &str,
+) -> RestResponse<T> {
+    rest_get_json_opts(client, cfg, path, RequestOptions::default()).await
+}
+
+pub async fn rest_get_json_opts<T: for<'de> Deserialize<'de>>(
+    client: &Client,
+    cfg: &Config,
+    path: &str,
+    opts: RequestOptions<'_>,
 ) -> RestResponse<T> {
     let url = format!("{}{}", cfg.api_url, path);
-    let debug = std::env::var("GITHUB_MCP_DEBUG").ok().as_deref() == Some("1");
-    if debug {
-        eprintln!("[debug] REST GET {}", url);
-    }
     let mut attempt: u32 = 0;
     loop {
+        if let Err(e) = check_deadline() {
+            return deadline_response(e);
+        }
+        let sent = Instant::now();
         let res = client
             .get(&url)
-            .header(AUTHORIZATION, auth_header(&cfg.token))
-            .header("X-GitHub-Api-Version", &cfg.api_version)
-            .header(
-                ACCEPT,
-                HeaderValue::from_static("application/vnd.github+json"),
-            )
+            .header(AUTHORIZATION, auth_header(cfg))
+            .timeout(within_deadline(Duration::from_secs(cfg.timeout_secs)))
+            .header("X-GitHub-Api-Version", opts.api_version(cfg))
+            .header(ACCEPT, opts.accept())
             .send()
             .await;
+        warn_if_slow(cfg, "GET", &url, sent);
 
         let res = match res {
             Ok(r) => r,
             Err(e) => {
+                trace_attempt("GET", &url, None, attempt, false);
                 warn!("REST GET error sending request: {}", e);
                 if attempt < 5 {
-                    tokio::time::sleep(compute_backoff(attempt, None)).await;
+                    tokio::time::sleep(within_deadline(compute_backoff(attempt, None))).await;
                     attempt += 1;
                     continue;
                 }
@@ -159,7 +433,7 @@ pub async fn rest_get_json<T: for<'de> Deserialize<'de>>(
                     value: None,
                     meta: Meta { rate: None },
                     error: Some(ErrorInfo {
-                        code: "upstream_error".into(),
+                        code: ErrorCode::UpstreamError,
                         message: e.to_string(),
                         retriable: true,
                     }),
@@ -170,8 +444,9 @@ pub async fn rest_get_json<T: for<'de> Deserialize<'de>>(
         };
 
         let status = res.status();
+        trace_attempt("GET", &url, Some(status), attempt, false);
         let headers = res.headers().clone();
-        if debug {
+        if debug_enabled() {
             if let Some(link) = headers.get("link").and_then(|v| v.to_str().ok()) {
                 eprintln!("[debug] Link: {}", link);
             }
@@ -183,11 +458,11 @@ pub async fn rest_get_json<T: for<'de> Deserialize<'de>>(
             .and_then(|s| s.parse::<u64>().ok())
             .map(Duration::from_secs);
 
-        if status.is_success() {
-            match res.json::<T>().await {
+        if opts.accepts(status) {
+            match read_json_body::<T>(res, status, opts).await {
                 Ok(val) => {
                     return RestResponse {
-                        value: Some(val),
+                        value: val,
                         meta: Meta { rate: Some(rate) },
                         error: None,
                         status,
@@ -199,8 +474,8 @@ pub async fn rest_get_json<T: for<'de> Deserialize<'de>>(
                         value: None,
                         meta: Meta { rate: Some(rate) },
                         error: Some(ErrorInfo {
-                            code: "server_error".into(),
-                            message: e.to_string(),
+                            code: ErrorCode::ServerError,
+                            message: e,
                             retriable: false,
                         }),
                         status,
@@ -217,12 +492,12 @@ pub async fn rest_get_json<T: for<'de> Deserialize<'de>>(
                 "REST GET {} retrying (status {}), backoff {:?}",
                 url, status, backoff
             );
-            tokio::time::sleep(backoff).await;
+            tokio::time::sleep(within_deadline(backoff)).await;
             attempt += 1;
             continue;
         }
         let text = res.text().await.unwrap_or_default();
-        let err = map_status_to_error(status, text);
+        let err = map_rest_error(status, &headers, text);
         return RestResponse {
             value: None,
             meta: Meta { rate: Some(rate) },
@@ -240,22 +515,53 @@ pub async fn rest_get_json_with_accept<T: for<'de> Deserialize<'de>>(
     path: &str,
     accept: &str,
 ) -> RestResponse<T> {
+    let opts = RequestOptions {
+        accept: Some(accept),
+        ..Default::default()
+    };
+    rest_get_json_opts(client, cfg, path, opts).await
+}
+
+// Generic JSON PUT helper
+pub async fn rest_put_json<TReq: Serialize, TResp: for<'de> Deserialize<'de>>(
+    client: &Client,
+    cfg: &Config,
+    path: &str,
+    body: &TReq,
+) -> RestResponse<TResp> {
+    rest_put_json_opts(client, cfg, path, body, RequestOptions::default()).await
+}
+
+pub async fn rest_put_json_opts<TReq: Serialize, TResp: for<'de> Deserialize<'de>>(
+    client: &Client,
+    cfg: &Config,
+    path: &str,
+    body: &TReq,
+    opts: RequestOptions<'_>,
+) -> RestResponse<TResp> {
     let url = format!("{}{}", cfg.api_url, path);
     let mut attempt: u32 = 0;
     loop {
+        if let Err(e) = check_deadline() {
+            return deadline_response(e);
+        }
+        let sent = Instant::now();
         let res = client
-            .get(&url)
-            .header(AUTHORIZATION, auth_header(&cfg.token))
-            .header("X-GitHub-Api-Version", &cfg.api_version)
-            .header(ACCEPT, HeaderValue::from_str(accept).unwrap())
+            .put(&url)
+            .header(AUTHORIZATION, auth_header(cfg))
+            .timeout(within_deadline(Duration::from_secs(cfg.timeout_secs)))
+            .header("X-GitHub-Api-Version", opts.api_version(cfg))
+            .header(ACCEPT, opts.accept())
+            .json(body)
             .send()
             .await;
-
+        warn_if_slow(cfg, "PUT", &url, sent);
         let res = match res {
             Ok(r) => r,
             Err(e) => {
+                trace_attempt("PUT", &url, None, attempt, true);
                 if attempt < 5 {
-                    tokio::time::sleep(compute_backoff(attempt, None)).await;
+                    tokio::time::sleep(within_deadline(compute_backoff(attempt, None))).await;
                     attempt += 1;
                     continue;
                 }
@@ -263,7 +569,7 @@ pub async fn rest_get_json_with_accept<T: for<'de> Deserialize<'de>>(
                     value: None,
                     meta: Meta { rate: None },
                     error: Some(ErrorInfo {
-                        code: "upstream_error".into(),
+                        code: ErrorCode::UpstreamError,
                         message: e.to_string(),
                         retriable: true,
                     }),
@@ -272,21 +578,15 @@ pub async fn rest_get_json_with_accept<T: for<'de> Deserialize<'de>>(
                 };
             }
         };
-
         let status = res.status();
+        trace_attempt("PUT", &url, Some(status), attempt, true);
         let headers = res.headers().clone();
         let rate = extract_rate_from_rest(&headers);
-        let retry_after = headers
-            .get(RETRY_AFTER)
-            .and_then(|v| v.to_str().ok())
-            .and_then(|s| s.parse::<u64>().ok())
-            .map(Duration::from_secs);
-
-        if status.is_success() {
-            match res.json::<T>().await {
+        if opts.accepts(status) {
+            match read_json_body::<TResp>(res, status, opts).await {
                 Ok(val) => {
                     return RestResponse {
-                        value: Some(val),
+                        value: val,
                         meta: Meta { rate: Some(rate) },
                         error: None,
                         status,
@@ -298,8 +598,8 @@ pub async fn rest_get_json_with_accept<T: for<'de> Deserialize<'de>>(
                         value: None,
                         meta: Meta { rate: Some(rate) },
                         error: Some(ErrorInfo {
-                            code: "server_error".into(),
-                            message: e.to_string(),
+                            code: ErrorCode::ServerError,
+                            message: e,
                             retriable: false,
                         }),
                         status,
@@ -308,15 +608,13 @@ pub async fn rest_get_json_with_accept<T: for<'de> Deserialize<'de>>(
                 }
             }
         }
-
         if (status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()) && attempt < 5 {
-            let backoff = compute_backoff(attempt, retry_after);
-            tokio::time::sleep(backoff).await;
+            tokio::time::sleep(within_deadline(compute_backoff(attempt, None))).await;
             attempt += 1;
             continue;
         }
         let text = res.text().await.unwrap_or_default();
-        let err = map_status_to_error(status, text);
+        let err = map_rest_error(status, &headers, text);
         return RestResponse {
             value: None,
             meta: Meta { rate: Some(rate) },
@@ -327,32 +625,46 @@ pub async fn rest_get_json_with_accept<T: for<'de> Deserialize<'de>>(
     }
 }
 
-// Generic JSON PUT helper
-pub async fn rest_put_json<TReq: Serialize, TResp: for<'de> Deserialize<'de>>(
+// Generic JSON PATCH helper
+pub async fn rest_patch_json<TReq: Serialize, TResp: for<'de> Deserialize<'de>>(
     client: &Client,
     cfg: &Config,
     path: &str,
     body: &TReq,
+) -> RestResponse<TResp> {
+    rest_patch_json_opts(client, cfg, path, body, RequestOptions::default()).await
+}
+
+pub async fn rest_patch_json_opts<TReq: Serialize, TResp: for<'de> Deserialize<'de>>(
+    client: &Client,
+    cfg: &Config,
+    path: &str,
+    body: &TReq,
+    opts: RequestOptions<'_>,
 ) -> RestResponse<TResp> {
     let url = format!("{}{}", cfg.api_url, path);
     let mut attempt: u32 = 0;
     loop {
+        if let Err(e) = check_deadline() {
+            return deadline_response(e);
+        }
+        let sent = Instant::now();
         let res = client
-            .put(&url)
-            .header(AUTHORIZATION, auth_header(&cfg.token))
-            .header("X-GitHub-Api-Version", &cfg.api_version)
-            .header(
-                ACCEPT,
-                HeaderValue::from_static("application/vnd.github+json"),
-            )
+            .patch(&url)
+            .header(AUTHORIZATION, auth_header(cfg))
+            .timeout(within_deadline(Duration::from_secs(cfg.timeout_secs)))
+            .header("X-GitHub-Api-Version", opts.api_version(cfg))
+            .header(ACCEPT, opts.accept())
             .json(body)
             .send()
             .await;
+        warn_if_slow(cfg, "PATCH", &url, sent);
         let res = match res {
             Ok(r) => r,
             Err(e) => {
+                trace_attempt("PATCH", &url, None, attempt, true);
                 if attempt < 5 {
-                    tokio::time::sleep(compute_backoff(attempt, None)).await;
+                    tokio::time::sleep(within_deadline(compute_backoff(attempt, None))).await;
                     attempt += 1;
                     continue;
                 }
@@ -360,7 +672,7 @@ pub async fn rest_put_json<TReq: Serialize, TResp: for<'de> Deserialize<'de>>(
                     value: None,
                     meta: Meta { rate: None },
                     error: Some(ErrorInfo {
-                        code: "upstream_error".into(),
+                        code: ErrorCode::UpstreamError,
                         message: e.to_string(),
                         retriable: true,
                     }),
@@ -370,41 +682,42 @@ pub async fn rest_put_json<TReq: Serialize, TResp: for<'de> Deserialize<'de>>(
             }
         };
         let status = res.status();
+        trace_attempt("PATCH", &url, Some(status), attempt, true);
         let headers = res.headers().clone();
         let rate = extract_rate_from_rest(&headers);
-        if status.is_success() {
-            match res.json::<TResp>().await {
+        if opts.accepts(status) {
+            match read_json_body::<TResp>(res, status, opts).await {
                 Ok(val) => {
                     return RestResponse {
-                        value: Some(val),
+                        value: val,
                         meta: Meta { rate: Some(rate) },
                         error: None,
                         status,
                         headers: Some(headers),
-                    };
+                    }
                 }
                 Err(e) => {
                     return RestResponse {
                         value: None,
                         meta: Meta { rate: Some(rate) },
                         error: Some(ErrorInfo {
-                            code: "server_error".into(),
-                            message: e.to_string(),
+                            code: ErrorCode::ServerError,
+                            message: e,
                             retriable: false,
                         }),
                         status,
                         headers: Some(headers),
-                    };
+                    }
                 }
             }
         }
         if (status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()) && attempt < 5 {
-            tokio::time::sleep(compute_backoff(attempt, None)).await;
+            tokio::time::sleep(within_deadline(compute_backoff(attempt, None))).await;
             attempt += 1;
             continue;
         }
         let text = res.text().await.unwrap_or_default();
-        let err = map_status_to_error(status, text);
+        let err = map_rest_error(status, &headers, text);
         return RestResponse {
             value: None,
             meta: Meta { rate: Some(rate) },
@@ -415,40 +728,294 @@ pub async fn rest_put_json<TReq: Serialize, TResp: for<'de> Deserialize<'de>>(
     }
 }
 
-// Generic JSON PATCH helper
-pub async fn rest_patch_json<TReq: Serialize, TResp: for<'de> Deserialize<'de>>(
+// Parse a success body. 202 Accepted, 204 No Content and 205 Reset Content (enable/disable,
+// star, queued work) come without one, so an empty body there yields None; so does a status
+// the caller listed in `accept_statuses`. An empty 200/201 is still a decode error, since
+// callers that expect a payload rely on getting one.
+async fn read_json_body<T: for<'de> Deserialize<'de>>(
+    res: reqwest::Response,
+    status: StatusCode,
+    opts: RequestOptions<'_>,
+) -> Result<Option<T>, String> {
+    let bytes = res.bytes().await.map_err(|e| e.to_string())?;
+    let bodyless = matches!(
+        status,
+        StatusCode::ACCEPTED | StatusCode::NO_CONTENT | StatusCode::RESET_CONTENT
+    ) || opts
+        .accept_statuses
+        .is_some_and(|list| list.contains(&status));
+    if bodyless && bytes.iter().all(u8::is_ascii_whitespace) {
+        return Ok(None);
+    }
+    serde_json::from_slice(&bytes)
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+// Binary GET that follows a single redirect (e.g. release assets and job logs, which 302 to a
+// pre-signed storage URL). The API hop retries on 429/5xx (honouring Retry-After); the redirect
+// target is fetched once, without auth.
+pub async fn rest_get_bytes_follow_redirect(
     client: &Client,
     cfg: &Config,
     path: &str,
-    body: &TReq,
-) -> RestResponse<TResp> {
+    accept: &'static str,
+) -> RestResponse<Vec<u8>> {
+    let mut buf = Vec::new();
+    let resp = rest_download_follow_redirect(client, cfg, path, accept, &mut buf, None).await;
+    RestResponse {
+        value: resp.value.map(|_| buf),
+        meta: resp.meta,
+        error: resp.error,
+        status: resp.status,
+        headers: resp.headers,
+    }
+}
+
+// Result of rest_download_follow_redirect: bytes written to the sink, and whether the body was
+// cut off at max_bytes.
+#[derive(Debug, Clone, Copy)]
+pub struct Download {
+    pub bytes: u64,
+    pub truncated: bool,
+}
+
+// Same request flow as rest_get_bytes_follow_redirect, but the body is streamed chunk by chunk
+// into `sink` instead of being buffered. With `max_bytes`, the download stops once the cap is
+// reached and reports `truncated`.
+pub async fn rest_download_follow_redirect<W: std::io::Write>(
+    client: &Client,
+    cfg: &Config,
+    path: &str,
+    accept: &'static str,
+    sink: &mut W,
+    max_bytes: Option<u64>,
+) -> RestResponse<Download> {
     let url = format!("{}{}", cfg.api_url, path);
+    let upstream = |message: String, status: StatusCode, rate: Option<RateMeta>| RestResponse {
+        value: None,
+        meta: Meta { rate },
+        error: Some(ErrorInfo {
+            code: ErrorCode::UpstreamError,
+            message,
+            retriable: true,
+        }),
+        status,
+        headers: None,
+    };
     let mut attempt: u32 = 0;
-    loop {
+    let (res, status, rate) = loop {
+        if let Err(e) = check_deadline() {
+            return deadline_response(e);
+        }
+        let sent = Instant::now();
         let res = client
-            .patch(&url)
-            .header(AUTHORIZATION, auth_header(&cfg.token))
+            .get(&url)
+            .header(AUTHORIZATION, auth_header(cfg))
+            .timeout(within_deadline(Duration::from_secs(cfg.timeout_secs)))
             .header("X-GitHub-Api-Version", &cfg.api_version)
-            .header(
-                ACCEPT,
-                HeaderValue::from_static("application/vnd.github+json"),
-            )
-            .json(body)
+            .header(ACCEPT, HeaderValue::from_static(accept))
             .send()
             .await;
+        warn_if_slow(cfg, "GET", &url, sent);
         let res = match res {
             Ok(r) => r,
             Err(e) => {
+                trace_attempt("GET", &url, None, attempt, false);
                 if attempt < 5 {
-                    tokio::time::sleep(compute_backoff(attempt, None)).await;
+                    tokio::time::sleep(within_deadline(compute_backoff(attempt, None))).await;
                     attempt += 1;
                     continue;
                 }
+                return upstream(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR, None);
+            }
+        };
+        let status = res.status();
+        trace_attempt("GET", &url, Some(status), attempt, false);
+        if (status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()) && attempt < 5 {
+            let retry_after = res
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|s| s.parse::<u64>().ok())
+                .map(Duration::from_secs);
+            tokio::time::sleep(within_deadline(compute_backoff(attempt, retry_after))).await;
+            attempt += 1;
+            continue;
+        }
+        let rate = extract_rate_from_rest(res.headers());
+        break (res, status, rate);
+    };
+    let res = if status.is_redirection() {
+        let Some(loc) = res
+            .headers()
+            .get("location")
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string())
+        else {
+            return upstream("Missing Location for redirect".into(), status, Some(rate));
+        };
+        let sent = Instant::now();
+        let redirect = client
+            .get(&loc)
+            .timeout(within_deadline(Duration::from_secs(cfg.timeout_secs)))
+            .send()
+            .await;
+        warn_if_slow(cfg, "GET", &loc, sent);
+        match redirect {
+            Ok(r) if r.status().is_success() => r,
+            Ok(r) => {
+                return upstream(
+                    format!("Redirect target returned {}", r.status()),
+                    r.status(),
+                    Some(rate),
+                )
+            }
+            Err(e) => return upstream(e.to_string(), status, Some(rate)),
+        }
+    } else if status.is_success() {
+        res
+    } else {
+        let headers = res.headers().clone();
+        let text = res.text().await.unwrap_or_default();
+        return RestResponse {
+            value: None,
+            meta: Meta { rate: Some(rate) },
+            error: Some(map_rest_error(status, &headers, text)),
+            status,
+            headers: Some(headers),
+        };
+    };
+    let final_status = res.status();
+    let headers = res.headers().clone();
+    let mut res = res;
+    let mut written: u64 = 0;
+    let mut truncated = false;
+    loop {
+        let chunk = match res.chunk().await {
+            Ok(Some(c)) => c,
+            Ok(None) => break,
+            Err(e) => return upstream(e.to_string(), final_status, Some(rate)),
+        };
+        let take = match max_bytes {
+            Some(cap) if written + chunk.len() as u64 > cap => {
+                truncated = true;
+                (cap - written) as usize
+            }
+            _ => chunk.len(),
+        };
+        if let Err(e) = sink.write_all(&chunk[..take]) {
+            return RestResponse {
+                value: None,
+                meta: Meta { rate: Some(rate) },
+                error: Some(ErrorInfo {
+                    code: ErrorCode::ServerError,
+                    message: format!("Failed to write download: {e}"),
+                    retriable: false,
+                }),
+                status: final_status,
+                headers: Some(headers),
+            };
+        }
+        written += take as u64;
+        if truncated {
+            // Dropping the response aborts the rest of the transfer.
+            break;
+        }
+    }
+    RestResponse {
+        value: Some(Download {
+            bytes: written,
+            truncated,
+        }),
+        meta: Meta { rate: Some(rate) },
+        error: None,
+        status: final_status,
+        headers: Some(headers),
+    }
+}
+
+// DELETE helper for endpoints that answer 204 No Content; the body is ignored on success.
+// A 404 after a retried 5xx or transport error counts as success: the earlier attempt may
+// already have deleted the resource.
+pub async fn rest_delete(client: &Client, cfg: &Config, path: &str) -> RestResponse<()> {
+    rest_delete_opts(client, cfg, path, RequestOptions::default()).await
+}
+
+pub async fn rest_delete_opts(
+    client: &Client,
+    cfg: &Config,
+    path: &str,
+    opts: RequestOptions<'_>,
+) -> RestResponse<()> {
+    rest_send_empty(client, cfg, reqwest::Method::DELETE, path, opts).await
+}
+
+// Bodyless POST for action endpoints (rerun, cancel, enable/disable) that answer
+// 200/202/204 with nothing worth parsing.
+pub async fn rest_post_empty(client: &Client, cfg: &Config, path: &str) -> RestResponse<()> {
+    rest_send_empty(
+        client,
+        cfg,
+        reqwest::Method::POST,
+        path,
+        RequestOptions::default(),
+    )
+    .await
+}
+
+// Bodyless PUT; same conventions as rest_post_empty.
+pub async fn rest_put_empty(client: &Client, cfg: &Config, path: &str) -> RestResponse<()> {
+    rest_send_empty(
+        client,
+        cfg,
+        reqwest::Method::PUT,
+        path,
+        RequestOptions::default(),
+    )
+    .await
+}
+
+async fn rest_send_empty(
+    client: &Client,
+    cfg: &Config,
+    method: reqwest::Method,
+    path: &str,
+    opts: RequestOptions<'_>,
+) -> RestResponse<()> {
+    let url = format!("{}{}", cfg.api_url, path);
+    let mut attempt: u32 = 0;
+    // Set once an attempt failed in a way that may still have reached GitHub.
+    let mut maybe_applied = false;
+    loop {
+        if let Err(e) = check_deadline() {
+            return deadline_response(e);
+        }
+        let sent = Instant::now();
+        let res = client
+            .request(method.clone(), &url)
+            .header(AUTHORIZATION, auth_header(cfg))
+            .timeout(within_deadline(Duration::from_secs(cfg.timeout_secs)))
+            .header("X-GitHub-Api-Version", opts.api_version(cfg))
+            .header(ACCEPT, opts.accept())
+            .send()
+            .await;
+        warn_if_slow(cfg, method.as_str(), &url, sent);
+        let res = match res {
+            Ok(r) => r,
+            Err(e) => {
+                trace_attempt(method.as_str(), &url, None, attempt, false);
+                if attempt < 5 {
+                    tokio::time::sleep(within_deadline(compute_backoff(attempt, None))).await;
+                    attempt += 1;
+                    maybe_applied = true;
+                    continue;
+                }
                 return RestResponse {
                     value: None,
                     meta: Meta { rate: None },
                     error: Some(ErrorInfo {
-                        code: "upstream_error".into(),
+                        code: ErrorCode::UpstreamError,
                         message: e.to_string(),
                         retriable: true,
                     }),
@@ -458,13 +1025,163 @@ pub async fn rest_patch_json<TReq: Serialize, TResp: for<'de> Deserialize<'de>>(
             }
         };
         let status = res.status();
+        trace_attempt(method.as_str(), &url, Some(status), attempt, false);
+        let headers = res.headers().clone();
+        let rate = extract_rate_from_rest(&headers);
+        let already_deleted =
+            method == reqwest::Method::DELETE && maybe_applied && status == StatusCode::NOT_FOUND;
+        if opts.accepts(status) || already_deleted {
+            return RestResponse {
+                value: Some(()),
+                meta: Meta { rate: Some(rate) },
+                error: None,
+                status,
+                headers: Some(headers),
+            };
+        }
+        if (status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()) && attempt < 5 {
+            tokio::time::sleep(within_deadline(compute_backoff(attempt, None))).await;
+            attempt += 1;
+            maybe_applied |= status.is_server_error();
+            continue;
+        }
+        let text = res.text().await.unwrap_or_default();
+        let err = map_rest_error(status, &headers, text);
+        return RestResponse {
+            value: None,
+            meta: Meta { rate: Some(rate) },
+            error: Some(err),
+            status,
+            headers: Some(headers),
+        };
+    }
+}
+
+// Raw-body POST (e.g. release asset uploads). `target` may be an absolute URL such as a
+// release's upload_url, or a path relative to the API base. Not retried on 5xx or mid-request
+// failures: a partially applied upload leaves a placeholder asset behind and a blind retry
+// would fail with 422. Only a 429 or a failed connect (nothing reached GitHub) is retried.
+pub async fn rest_post_bytes<TResp: for<'de> Deserialize<'de>>(
+    client: &Client,
+    cfg: &Config,
+    target: &str,
+    content_type: &str,
+    body: Vec<u8>,
+) -> RestResponse<TResp> {
+    rest_send_bytes(
+        client,
+        cfg,
+        reqwest::Method::POST,
+        target,
+        content_type,
+        body,
+    )
+    .await
+}
+
+// Raw-body PUT; same conventions as rest_post_bytes.
+pub async fn rest_put_bytes<TResp: for<'de> Deserialize<'de>>(
+    client: &Client,
+    cfg: &Config,
+    target: &str,
+    content_type: &str,
+    body: Vec<u8>,
+) -> RestResponse<TResp> {
+    rest_send_bytes(
+        client,
+        cfg,
+        reqwest::Method::PUT,
+        target,
+        content_type,
+        body,
+    )
+    .await
+}
+
+async fn rest_send_bytes<TResp: for<'de> Deserialize<'de>>(
+    client: &Client,
+    cfg: &Config,
+    method: reqwest::Method,
+    target: &str,
+    content_type: &str,
+    body: Vec<u8>,
+) -> RestResponse<TResp> {
+    let url = if target.starts_with("http://") || target.starts_with("https://") {
+        target.to_string()
+    } else {
+        format!("{}{}", cfg.api_url, target)
+    };
+    let content_type = match HeaderValue::from_str(content_type) {
+        Ok(v) => v,
+        Err(e) => {
+            return RestResponse {
+                value: None,
+                meta: Meta { rate: None },
+                error: Some(ErrorInfo {
+                    code: ErrorCode::BadRequest,
+                    message: format!("invalid content type: {}", e),
+                    retriable: false,
+                }),
+                status: StatusCode::BAD_REQUEST,
+                headers: None,
+            }
+        }
+    };
+    // Built once: a Vec body is held as shared bytes, so try_clone per attempt is cheap.
+    let request = client
+        .request(method.clone(), &url)
+        .header(AUTHORIZATION, auth_header(cfg))
+        .header("X-GitHub-Api-Version", &cfg.api_version)
+        .header(
+            ACCEPT,
+            HeaderValue::from_static("application/vnd.github+json"),
+        )
+        .header(CONTENT_TYPE, content_type)
+        .body(body);
+    let mut attempt: u32 = 0;
+    loop {
+        if let Err(e) = check_deadline() {
+            return deadline_response(e);
+        }
+        let sent = Instant::now();
+        let res = request
+            .try_clone()
+            .expect("in-memory body is cloneable")
+            .timeout(within_deadline(Duration::from_secs(cfg.timeout_secs)))
+            .send()
+            .await;
+        warn_if_slow(cfg, method.as_str(), &url, sent);
+        let res = match res {
+            Ok(r) => r,
+            Err(e) => {
+                trace_attempt(method.as_str(), &url, None, attempt, true);
+                if e.is_connect() && attempt < 5 {
+                    tokio::time::sleep(within_deadline(compute_backoff(attempt, None))).await;
+                    attempt += 1;
+                    continue;
+                }
+                return RestResponse {
+                    value: None,
+                    meta: Meta { rate: None },
+                    error: Some(ErrorInfo {
+                        code: ErrorCode::UpstreamError,
+                        message: e.to_string(),
+                        retriable: true,
+                    }),
+                    status: StatusCode::INTERNAL_SERVER_ERROR,
+                    headers: None,
+                };
+            }
+        };
+        let status = res.status();
+        trace_attempt(method.as_str(), &url, Some(status), attempt, true);
         let headers = res.headers().clone();
         let rate = extract_rate_from_rest(&headers);
         if status.is_success() {
-            match res.json::<TResp>().await {
+            match read_json_body::<TResp>(res, status, RequestOptions::default()).await {
                 Ok(val) => {
                     return RestResponse {
-                        value: Some(val),
+                        value: val,
                         meta: Meta { rate: Some(rate) },
                         error: None,
                         status,
@@ -476,8 +1193,8 @@ pub async fn rest_patch_json<TReq: Serialize, TResp: for<'de> Deserialize<'de>>(
                         value: None,
                         meta: Meta { rate: Some(rate) },
                         error: Some(ErrorInfo {
-                            code: "server_error".into(),
-                            message: e.to_string(),
+                            code: ErrorCode::ServerError,
+                            message: e,
                             retriable: false,
                         }),
                         status,
@@ -486,13 +1203,18 @@ pub async fn rest_patch_json<TReq: Serialize, TResp: for<'de> Deserialize<'de>>(
                 }
             }
         }
-        if (status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()) && attempt < 5 {
-            tokio::time::sleep(compute_backoff(attempt, None)).await;
+        if status == StatusCode::TOO_MANY_REQUESTS && attempt < 5 {
+            let retry_after = headers
+                .get(RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|s| s.parse::<u64>().ok())
+                .map(Duration::from_secs);
+            tokio::time::sleep(within_deadline(compute_backoff(attempt, retry_after))).await;
             attempt += 1;
             continue;
         }
         let text = res.text().await.unwrap_or_default();
-        let err = map_status_to_error(status, text);
+        let err = map_rest_error(status, &headers, text);
         return RestResponse {
             value: None,
             meta: Meta { rate: Some(rate) },
@@ -509,26 +1231,40 @@ pub async fn rest_post_json<TReq: Serialize, TResp: for<'de> Deserialize<'de>>(
     cfg: &Config,
     path: &str,
     body: &TReq,
+) -> RestResponse<TResp> {
+    rest_post_json_opts(client, cfg, path, body, RequestOptions::default()).await
+}
+
+pub async fn rest_post_json_opts<TReq: Serialize, TResp: for<'de> Deserialize<'de>>(
+    client: &Client,
+    cfg: &Config,
+    path: &str,
+    body: &TReq,
+    opts: RequestOptions<'_>,
 ) -> RestResponse<TResp> {
     let url = format!("{}{}", cfg.api_url, path);
     let mut attempt: u32 = 0;
     loop {
+        if let Err(e) = check_deadline() {
+            return deadline_response(e);
+        }
+        let sent = Instant::now();
         let res = client
             .post(&url)
-            .header(AUTHORIZATION, auth_header(&cfg.token))
-            .header("X-GitHub-Api-Version", &cfg.api_version)
-            .header(
-                ACCEPT,
-                HeaderValue::from_static("application/vnd.github+json"),
-            )
+            .header(AUTHORIZATION, auth_header(cfg))
+            .timeout(within_deadline(Duration::from_secs(cfg.timeout_secs)))
+            .header("X-GitHub-Api-Version", opts.api_version(cfg))
+            .header(ACCEPT, opts.accept())
             .json(body)
             .send()
             .await;
+        warn_if_slow(cfg, "POST", &url, sent);
         let res = match res {
             Ok(r) => r,
             Err(e) => {
+                trace_attempt("POST", &url, None, attempt, true);
                 if attempt < 5 {
-                    tokio::time::sleep(compute_backoff(attempt, None)).await;
+                    tokio::time::sleep(within_deadline(compute_backoff(attempt, None))).await;
                     attempt += 1;
                     continue;
                 }
@@ -536,7 +1272,7 @@ pub async fn rest_post_json<TReq: Serialize, TResp: for<'de> Deserialize<'de>>(
                     value: None,
                     meta: Meta { rate: None },
                     error: Some(ErrorInfo {
-                        code: "upstream_error".into(),
+                        code: ErrorCode::UpstreamError,
                         message: e.to_string(),
                         retriable: true,
                     }),
@@ -546,13 +1282,14 @@ pub async fn rest_post_json<TReq: Serialize, TResp: for<'de> Deserialize<'de>>(
             }
         };
         let status = res.status();
+        trace_attempt("POST", &url, Some(status), attempt, true);
         let headers = res.headers().clone();
         let rate = extract_rate_from_rest(&headers);
-        if status.is_success() || status == StatusCode::ACCEPTED {
-            match res.json::<TResp>().await {
+        if opts.accepts(status) {
+            match read_json_body::<TResp>(res, status, opts).await {
                 Ok(val) => {
                     return RestResponse {
-                        value: Some(val),
+                        value: val,
                         meta: Meta { rate: Some(rate) },
                         error: None,
                         status,
@@ -564,8 +1301,8 @@ pub async fn rest_post_json<TReq: Serialize, TResp: for<'de> Deserialize<'de>>(
                         value: None,
                         meta: Meta { rate: Some(rate) },
                         error: Some(ErrorInfo {
-                            code: "server_error".into(),
-                            message: e.to_string(),
+                            code: ErrorCode::ServerError,
+                            message: e,
                             retriable: false,
                         }),
                         status,
@@ -575,12 +1312,12 @@ pub async fn rest_post_json<TReq: Serialize, TResp: for<'de> Deserialize<'de>>(
             }
         }
         if (status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()) && attempt < 5 {
-            tokio::time::sleep(compute_backoff(attempt, None)).await;
+            tokio::time::sleep(within_deadline(compute_backoff(attempt, None))).await;
             attempt += 1;
             continue;
         }
         let text = res.text().await.unwrap_or_default();
-        let err = map_status_to_error(status, text);
+        let err = map_rest_error(status, &headers, text);
         return RestResponse {
             value: None,
             meta: Meta { rate: Some(rate) },
@@ -600,19 +1337,26 @@ pub async fn rest_get_text_with_accept(
     let url = format!("{}{}", cfg.api_url, path);
     let mut attempt: u32 = 0;
     loop {
+        if let Err(e) = check_deadline() {
+            return deadline_response(e);
+        }
+        let sent = Instant::now();
         let res = client
             .get(&url)
-            .header(AUTHORIZATION, auth_header(&cfg.token))
+            .header(AUTHORIZATION, auth_header(cfg))
+            .timeout(within_deadline(Duration::from_secs(cfg.timeout_secs)))
             .header("X-GitHub-Api-Version", &cfg.api_version)
             .header(ACCEPT, HeaderValue::from_str(accept).unwrap())
             .send()
             .await;
+        warn_if_slow(cfg, "GET", &url, sent);
 
         let res = match res {
             Ok(r) => r,
             Err(e) => {
+                trace_attempt("GET", &url, None, attempt, false);
                 if attempt < 5 {
-                    tokio::time::sleep(compute_backoff(attempt, None)).await;
+                    tokio::time::sleep(within_deadline(compute_backoff(attempt, None))).await;
                     attempt += 1;
                     continue;
                 }
@@ -620,7 +1364,7 @@ pub async fn rest_get_text_with_accept(
                     value: None,
                     meta: Meta { rate: None },
                     error: Some(ErrorInfo {
-                        code: "upstream_error".into(),
+                        code: ErrorCode::UpstreamError,
                         message: e.to_string(),
                         retriable: true,
                     }),
@@ -631,6 +1375,7 @@ pub async fn rest_get_text_with_accept(
         };
 
         let status = res.status();
+        trace_attempt("GET", &url, Some(status), attempt, false);
         let headers = res.headers().clone();
         let rate = extract_rate_from_rest(&headers);
         let retry_after = headers
@@ -650,11 +1395,11 @@ pub async fn rest_get_text_with_accept(
         }
         if (status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()) && attempt < 5 {
             let backoff = compute_backoff(attempt, retry_after);
-            tokio::time::sleep(backoff).await;
+            tokio::time::sleep(within_deadline(backoff)).await;
             attempt += 1;
             continue;
         }
-        let err = map_status_to_error(status, text);
+        let err = map_rest_error(status, &headers, text);
         return RestResponse {
             value: None,
             meta: Meta { rate: Some(rate) },
@@ -682,34 +1427,73 @@ pub struct GraphQlResponse<T> {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GraphQlError {
     pub message: String,
+    // GitHub sets `type` on some errors (e.g. MAX_NODE_LIMIT_EXCEEDED, NOT_FOUND).
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub error_type: Option<String>,
+    // Response path of the field that failed, e.g. ["repository", "n12"] for an aliased lookup.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub path: Vec<serde_json::Value>,
 }
 
-pub async fn graphql_post<
-    TReq: Serialize,
-    TResp: for<'de> Deserialize<'de>,
-    TRate: for<'de> Deserialize<'de>,
->(
+// Map GraphQL error entries to an ErrorInfo. Node-limit violations are caused by the
+// request shape (first/limit too large), so they are surfaced as non-retriable bad_request.
+pub fn map_graphql_errors(errors: &[GraphQlError]) -> ErrorInfo {
+    let msg = errors
+        .iter()
+        .map(|e| e.message.clone())
+        .collect::<Vec<_>>()
+        .join("; ");
+    let node_limit = errors
+        .iter()
+        .any(|e| e.error_type.as_deref() == Some("MAX_NODE_LIMIT_EXCEEDED"));
+    if node_limit {
+        return ErrorInfo {
+            code: ErrorCode::BadRequest,
+            message: msg,
+            retriable: false,
+        };
+    }
+    ErrorInfo {
+        code: ErrorCode::UpstreamError,
+        message: msg,
+        retriable: true,
+    }
+}
+
+// POST a GraphQL document, retrying transport errors, 429 and 5xx. Returns the parsed response
+// body with `data`/`errors` untouched; the error is set only when no body could be obtained.
+// Meta carries `data.rateLimit` when the query selected it.
+pub async fn graphql_post_raw<TReq: Serialize>(
     client: &Client,
     cfg: &Config,
     query: &str,
     variables: &TReq,
-) -> (Option<TResp>, Meta, Option<ErrorInfo>) {
+) -> (Option<serde_json::Value>, Meta, Option<ErrorInfo>) {
     let mut attempt: u32 = 0;
     let body = serde_json::json!({ "query": query, "variables": variables });
     loop {
+        if let Err(e) = check_deadline() {
+            return (None, Meta { rate: None }, Some(e));
+        }
+        let sent = Instant::now();
         let res = client
             .post(&cfg.graphql_url)
-            .header(AUTHORIZATION, auth_header(&cfg.token))
+            .header(AUTHORIZATION, auth_header(cfg))
             .header(ACCEPT, HeaderValue::from_static("application/json"))
+            .timeout(within_deadline(Duration::from_secs(
+                cfg.graphql_timeout_secs,
+            )))
             .json(&body)
             .send()
             .await;
+        warn_if_slow(cfg, "POST", &cfg.graphql_url, sent);
 
         let res = match res {
             Ok(r) => r,
             Err(e) => {
+                trace_attempt("POST", &cfg.graphql_url, None, attempt, true);
                 if attempt < 5 {
-                    tokio::time::sleep(compute_backoff(attempt, None)).await;
+                    tokio::time::sleep(within_deadline(compute_backoff(attempt, None))).await;
                     attempt += 1;
                     continue;
                 }
@@ -717,7 +1501,7 @@ pub async fn graphql_post<
                     None,
                     Meta { rate: None },
                     Some(ErrorInfo {
-                        code: "upstream_error".into(),
+                        code: ErrorCode::UpstreamError,
                         message: e.to_string(),
                         retriable: true,
                     }),
@@ -726,10 +1510,11 @@ pub async fn graphql_post<
         };
 
         let status = res.status();
+        trace_attempt("POST", &cfg.graphql_url, Some(status), attempt, true);
+        let headers = res.headers().clone();
         let text = res.text().await.unwrap_or_default();
 
         if status.is_success() {
-            // Parse both typed and value to extract rateLimit if present
             let v: serde_json::Value = match serde_json::from_str(&text) {
                 Ok(v) => v,
                 Err(e) => {
@@ -737,77 +1522,89 @@ pub async fn graphql_post<
                         None,
                         Meta { rate: None },
                         Some(ErrorInfo {
-                            code: "server_error".into(),
+                            code: ErrorCode::ServerError,
                             message: e.to_string(),
                             retriable: false,
                         }),
                     );
                 }
             };
-            let parsed: Result<GraphQlResponse<TResp>, _> = serde_json::from_value(v.clone());
-            match parsed {
-                Ok(resp) => {
-                    if let Some(errors) = resp.errors {
-                        let msg = errors
-                            .iter()
-                            .map(|e| e.message.clone())
-                            .collect::<Vec<_>>()
-                            .join("; ");
-                        return (
-                            None,
-                            Meta { rate: None },
-                            Some(ErrorInfo {
-                                code: "upstream_error".into(),
-                                message: msg,
-                                retriable: true,
-                            }),
-                        );
-                    }
-                    let rate = v.get("data").and_then(|d| d.get("rateLimit")).map(|rl| {
-                        let remaining = rl
-                            .get("remaining")
-                            .and_then(|x| x.as_i64())
-                            .map(|x| x as i32);
-                        let used = rl.get("used").and_then(|x| x.as_i64()).map(|x| x as i32);
-                        let reset_at = rl
-                            .get("resetAt")
-                            .and_then(|x| x.as_str())
-                            .map(|s| s.to_string());
-                        RateMeta {
-                            remaining,
-                            used,
-                            reset_at,
-                        }
-                    });
-                    return (resp.data, Meta { rate }, None);
+            let rate = v.get("data").and_then(|d| d.get("rateLimit")).map(|rl| {
+                let remaining = rl
+                    .get("remaining")
+                    .and_then(|x| x.as_i64())
+                    .map(|x| x as i32);
+                let used = rl.get("used").and_then(|x| x.as_i64()).map(|x| x as i32);
+                let reset_at = rl
+                    .get("resetAt")
+                    .and_then(|x| x.as_str())
+                    .map(|s| s.to_string());
+                let int = |k: &str| rl.get(k).and_then(|x| x.as_i64()).map(|x| x as i32);
+                RateMeta {
+                    remaining,
+                    used,
+                    reset_at,
+                    cost: int("cost"),
+                    node_count: int("nodeCount"),
                 }
-                Err(e) => {
-                    return (
-                        None,
-                        Meta { rate: None },
-                        Some(ErrorInfo {
-                            code: "server_error".into(),
-                            message: e.to_string(),
-                            retriable: false,
-                        }),
-                    );
-                }
-            }
+            });
+            return (Some(v), Meta { rate }, None);
         }
 
         // Retry on 429/5xx
         if (status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()) && attempt < 5 {
             let retry_after = None;
-            tokio::time::sleep(compute_backoff(attempt, retry_after)).await;
+            tokio::time::sleep(within_deadline(compute_backoff(attempt, retry_after))).await;
             attempt += 1;
             continue;
         }
-        let err = map_status_to_error(status, text);
+        let err = map_rest_error(status, &headers, text);
         return (None, Meta { rate: None }, Some(err));
     }
 }
 
-// REST opaque cursor codec: base64(JSON { page, per_page })
+// Returns (data, meta, error). Both data and error are set on a partial success: handlers
+// that check the error first keep treating it as a failure.
+pub async fn graphql_post<
+    TReq: Serialize,
+    TResp: for<'de> Deserialize<'de>,
+    TRate: for<'de> Deserialize<'de>,
+>(
+    client: &Client,
+    cfg: &Config,
+    query: &str,
+    variables: &TReq,
+) -> (Option<TResp>, Meta, Option<ErrorInfo>) {
+    let (v, meta, err) = graphql_post_raw(client, cfg, query, variables).await;
+    let Some(v) = v else {
+        return (None, meta, err);
+    };
+    match serde_json::from_value::<GraphQlResponse<TResp>>(v) {
+        Ok(resp) => {
+            // GraphQL may resolve most of a query and still report errors for some fields
+            // (e.g. a blocked author). When data is present it is returned alongside the
+            // error so handlers can choose to surface a partial result.
+            if let Some(errors) = resp.errors {
+                return match resp.data {
+                    Some(d) => (Some(d), meta, Some(map_graphql_errors(&errors))),
+                    None => (None, Meta { rate: None }, Some(map_graphql_errors(&errors))),
+                };
+            }
+            (resp.data, meta, None)
+        }
+        Err(e) => (
+            None,
+            Meta { rate: None },
+            Some(ErrorInfo {
+                code: ErrorCode::ServerError,
+                message: e.to_string(),
+                retriable: false,
+            }),
+        ),
+    }
+}
+
+// REST opaque cursor codec: base64(JSON { page, per_page, path?, after? })
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct RestCursor {
     pub page: u32,
@@ -815,6 +1612,10 @@ pub struct RestCursor {
     // Optional absolute or relative path to use for the next request; if present, prefer it.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
+    // Opaque `after` token for endpoints that don't page by number (audit log, notifications).
+    // `page` then only counts pages fetched. Absent from page-based cursors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
 }
 
 pub fn encode_rest_cursor(c: RestCursor) -> String {
@@ -828,45 +1629,121 @@ pub fn decode_rest_cursor(s: &str) -> Option<RestCursor> {
     serde_json::from_slice(&bytes).ok()
 }
 
+// URL for the given rel (e.g. "next", "last") from a Link header, if present.
+// Example: <https://api.github.com/repos/o/r/pulls/1/comments?page=2>; rel="next", <...>; rel="last"
+fn link_url_for_rel(headers: &HeaderMap, rel: &str) -> Option<String> {
+    let link = headers.get("link")?.to_str().ok()?;
+    let needle = format!("rel=\"{}\"", rel);
+    let part = link
+        .split(',')
+        .map(str::trim)
+        .find(|p| p.contains(&needle))?;
+    let start = part.find('<')? + 1;
+    let end = part.find('>')?;
+    Some(part[start..end].to_string())
+}
+
 // Extract the relative path (including query) for rel="next" from a Link header if present.
 // Returns values like "/repos/o/r/pulls/1/comments?page=2".
 pub fn extract_next_path_from_link(headers: &HeaderMap) -> Option<String> {
-    let link = headers.get("link")?.to_str().ok()?;
-    // Find the segment with rel="next"
-    // Example: <https://api.github.com/repos/o/r/pulls/1/comments?page=2>; rel="next", <...>; rel="last"
-    for part in link.split(',') {
-        let trimmed = part.trim();
-        if !trimmed.contains("rel=\"next\"") {
-            continue;
-        }
-        let start = trimmed.find('<')? + 1;
-        let end = trimmed.find('>')?;
-        let url_str = &trimmed[start..end];
-        // Try to parse as absolute URL first; fall back to assuming it's already a path
-        if let Ok(u) = url::Url::parse(url_str) {
-            let mut p = u.path().to_string();
-            if let Some(q) = u.query() {
-                p.push('?');
-                p.push_str(q);
-            }
-            return Some(p);
-        } else {
-            return Some(url_str.to_string());
+    let url_str = link_url_for_rel(headers, "next")?;
+    // Try to parse as absolute URL first; fall back to assuming it's already a path
+    if let Ok(u) = url::Url::parse(&url_str) {
+        let mut p = u.path().to_string();
+        if let Some(q) = u.query() {
+            p.push('?');
+            p.push_str(q);
         }
+        Some(p)
+    } else {
+        Some(url_str)
     }
-    None
+}
+
+// Page number of rel="last" from a Link header. GitHub omits rel="last" on the last page
+// and on endpoints that cannot count (e.g. some search results).
+pub fn last_page_from_link(headers: &HeaderMap) -> Option<u32> {
+    let url_str = link_url_for_rel(headers, "last")?;
+    let query = url_str.split_once('?')?.1;
+    query
+        .split('&')
+        .filter_map(|kv| kv.split_once('='))
+        .find(|(k, _)| *k == "page")
+        .and_then(|(_, v)| v.parse().ok())
+}
+
+// Cursor for the page after `page` on an `after`-token endpoint, taken from the Link
+// header's rel="next"; None on the last page. `path` is the request path the token belongs
+// to (without `after`), so a handler can reject a cursor replayed with different arguments.
+pub fn next_after_cursor(
+    headers: &HeaderMap,
+    page: u32,
+    per_page: u32,
+    path: String,
+) -> Option<String> {
+    let raw = next_link_query_param(headers, "after")?;
+    let after = urlencoding::decode(&raw).ok()?.into_owned();
+    Some(encode_rest_cursor(RestCursor {
+        page: page + 1,
+        per_page,
+        path: Some(path),
+        after: Some(after),
+    }))
+}
+
+// Raw (still percent-encoded) value of a query parameter in the rel="next" Link URL. For
+// endpoints that page with opaque `after`/`before` tokens instead of page numbers.
+pub fn next_link_query_param(headers: &HeaderMap, name: &str) -> Option<String> {
+    let url_str = link_url_for_rel(headers, "next")?;
+    let query = url_str.split_once('?')?.1;
+    query
+        .split('&')
+        .filter_map(|kv| kv.split_once('='))
+        .find(|(k, _)| *k == name)
+        .map(|(_, v)| v.to_string())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn shared_client_is_reused_until_its_settings_change() {
+        let mut cfg = Config {
+            token: "t".into(),
+            token_file: None,
+            api_url: "http://127.0.0.1:1".into(),
+            graphql_url: "http://127.0.0.1:1/graphql".into(),
+            api_version: "2022-11-28".into(),
+            user_agent: "shared-client-test".into(),
+            timeout_secs: 5,
+            graphql_timeout_secs: 5,
+            rate_warn_percent: 10,
+            max_response_bytes: 0,
+            call_deadline_secs: None,
+            slow_ms: 0,
+        };
+        let before = CLIENT_BUILDS.load(Ordering::Relaxed);
+        for _ in 0..3 {
+            shared_client(&cfg).unwrap();
+        }
+        assert_eq!(CLIENT_BUILDS.load(Ordering::Relaxed), before + 1);
+        // A token change doesn't need a new client; a timeout change does.
+        cfg.token = "rotated".into();
+        shared_client(&cfg).unwrap();
+        assert_eq!(CLIENT_BUILDS.load(Ordering::Relaxed), before + 1);
+        cfg.timeout_secs = 7;
+        shared_client(&cfg).unwrap();
+        assert_eq!(CLIENT_BUILDS.load(Ordering::Relaxed), before + 2);
+    }
+
     #[test]
     fn rest_cursor_roundtrip() {
         let c = RestCursor {
             page: 2,
             per_page: 30,
             path: None,
+            after: None,
         };
         let s = encode_rest_cursor(c.clone());
         let d = decode_rest_cursor(&s).unwrap();
@@ -902,4 +1779,48 @@ mod tests {
         assert_eq!(s5.code, "upstream_error");
         assert!(s5.retriable);
     }
+
+    #[test]
+    fn link_header_last_page() {
+        let mut h = HeaderMap::new();
+        h.insert(
+            "link",
+            "<https://api.github.com/repos/o/r/tags?per_page=30&page=2>; rel=\"next\", <https://api.github.com/repos/o/r/tags?per_page=30&page=7>; rel=\"last\""
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(last_page_from_link(&h), Some(7));
+        assert_eq!(
+            extract_next_path_from_link(&h).as_deref(),
+            Some("/repos/o/r/tags?per_page=30&page=2")
+        );
+        h.insert("link", "</x?page=3>; rel=\"prev\"".parse().unwrap());
+        assert_eq!(last_page_from_link(&h), None);
+    }
+
+    #[test]
+    fn link_header_after_token() {
+        let mut h = HeaderMap::new();
+        h.insert(
+            "link",
+            "<https://api.github.com/organizations/1/audit-log?per_page=30&before=MS0x>; rel=\"prev\", <https://api.github.com/organizations/1/audit-log?per_page=30&after=MS4y%3D>; rel=\"next\""
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(
+            next_link_query_param(&h, "after").as_deref(),
+            Some("MS4y%3D")
+        );
+        assert_eq!(next_link_query_param(&h, "before"), None);
+        let next = decode_rest_cursor(
+            &next_after_cursor(&h, 1, 30, "/orgs/acme/audit-log?per_page=30".into()).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(next.after.as_deref(), Some("MS4y="));
+        assert_eq!(next.page, 2);
+        assert_eq!(
+            next.path.as_deref(),
+            Some("/orgs/acme/audit-log?per_page=30")
+        );
+    }
 }
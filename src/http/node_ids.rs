@@ -0,0 +1,113 @@
+//! Resolve GraphQL node ids from the numbers users know (review comment ids), so mutation
+//! tools can accept friendly inputs and look the id up internally.
+#![allow(non_snake_case)] // GraphQL field names map directly
+
+use super::{graphql_post, ErrorCode, ErrorInfo};
+use crate::config::Config;
+use reqwest::Client;
+use serde::Deserialize;
+
+// Review threads are scanned page by page; stop after this many pages (10k threads).
+const MAX_THREAD_PAGES: usize = 100;
+
+fn not_found(what: &str) -> ErrorInfo {
+    ErrorInfo {
+        code: ErrorCode::NotFound,
+        message: format!("{} not found", what),
+        retriable: false,
+    }
+}
+
+// Find the review thread on PR `number` that contains the review comment `comment_id`
+// (the REST id, i.e. GraphQL databaseId).
+pub async fn resolve_review_thread_id(
+    client: &Client,
+    cfg: &Config,
+    owner: &str,
+    repo: &str,
+    number: i64,
+    comment_id: i64,
+) -> Result<String, ErrorInfo> {
+    #[derive(Deserialize)]
+    struct Comment {
+        databaseId: Option<i64>,
+    }
+    #[derive(Deserialize)]
+    struct Comments {
+        nodes: Vec<Comment>,
+    }
+    #[derive(Deserialize)]
+    struct Thread {
+        id: String,
+        comments: Comments,
+    }
+    #[derive(Deserialize)]
+    struct PageInfo {
+        hasNextPage: bool,
+        endCursor: Option<String>,
+    }
+    #[derive(Deserialize)]
+    struct Threads {
+        nodes: Vec<Thread>,
+        pageInfo: PageInfo,
+    }
+    #[derive(Deserialize)]
+    struct Pr {
+        reviewThreads: Threads,
+    }
+    #[derive(Deserialize)]
+    struct Repo {
+        pullRequest: Option<Pr>,
+    }
+    #[derive(Deserialize)]
+    struct Data {
+        repository: Option<Repo>,
+    }
+    let query = r#"
+    query ResolveThreadId($owner: String!, $repo: String!, $number: Int!, $after: String) {
+      repository(owner: $owner, name: $repo) {
+        pullRequest(number: $number) {
+          reviewThreads(first: 100, after: $after) {
+            nodes { id comments(first: 100) { nodes { databaseId } } }
+            pageInfo { hasNextPage endCursor }
+          }
+        }
+      }
+    }
+    "#;
+    let mut after: Option<String> = None;
+    for _ in 0..MAX_THREAD_PAGES {
+        let vars = serde_json::json!({
+            "owner": owner, "repo": repo, "number": number, "after": after,
+        });
+        let (data, _meta, err) =
+            graphql_post::<serde_json::Value, Data, serde_json::Value>(client, cfg, query, &vars)
+                .await;
+        let threads = match data.and_then(|d| d.repository) {
+            Some(Repo {
+                pullRequest: Some(pr),
+            }) => pr.reviewThreads,
+            Some(Repo { pullRequest: None }) => {
+                return Err(not_found(&format!("pullRequest #{}", number)))
+            }
+            None => return Err(err.unwrap_or_else(|| not_found("Repository"))),
+        };
+        let hit = threads.nodes.into_iter().find(|t| {
+            t.comments
+                .nodes
+                .iter()
+                .any(|c| c.databaseId == Some(comment_id))
+        });
+        if let Some(t) = hit {
+            return Ok(t.id);
+        }
+        if !threads.pageInfo.hasNextPage {
+            break;
+        }
+        after = threads.pageInfo.endCursor;
+    }
+    Err(not_found(&format!(
+        "Review thread for comment {}",
+        comment_id
+    )))
+}
@@ -1,3 +1,4 @@
+use crate::config::{Config, DEFAULT_MAX_RESPONSE_BYTES, DEFAULT_RATE_WARN_PERCENT};
 use serde_json::Value;
 use std::cell::Cell;
 
@@ -5,6 +6,7 @@ use std::cell::Cell;
 // Use a const initializer to satisfy clippy::missing_const_for_thread_local on Rust 1.90 (MSRV).
 thread_local! {
     static INCLUDE_RATE: Cell<bool> = const { Cell::new(false) };
+    static INCLUDE_PAGES: Cell<bool> = const { Cell::new(false) };
 }
 
 // Set the include-rate flag for the current thread (one tools/call invocation).
@@ -38,11 +40,66 @@ fn current_include_rate() -> bool {
     INCLUDE_RATE.with(|c| c.get())
 }
 
+// RAII guard scoping the `_include_pages` flag (explicit page/per_page/total_pages) to a call.
+pub struct IncludePagesGuard(bool);
+
+impl IncludePagesGuard {
+    pub fn set(flag: bool) -> Self {
+        Self(INCLUDE_PAGES.with(|c| c.replace(flag)))
+    }
+}
+
+impl Drop for IncludePagesGuard {
+    fn drop(&mut self) {
+        INCLUDE_PAGES.with(|c| c.set(self.0));
+    }
+}
+
+fn current_include_pages() -> bool {
+    INCLUDE_PAGES.with(|c| c.get())
+}
+
+// Non-fatal heads-up when the remaining budget is below `percent`% of the limit. GitHub's
+// limit is `remaining + used` for both REST headers and GraphQL `rateLimit`.
+fn rate_warning(rate: &Value, percent: u32) -> Option<String> {
+    let remaining = rate.get("remaining")?.as_i64()?;
+    let used = rate.get("used").and_then(|v| v.as_i64()).unwrap_or(0);
+    let limit = remaining + used;
+    if percent == 0 || limit <= 0 || remaining * 100 >= limit * i64::from(percent) {
+        return None;
+    }
+    let resets = rate
+        .get("reset_at")
+        .and_then(|v| v.as_str())
+        .map(|t| format!(", resets at {}", t))
+        .unwrap_or_default();
+    Some(format!(
+        "approaching rate limit: {} of {} remaining{}",
+        remaining, limit, resets
+    ))
+}
+
+// List contract: `items` is an array (possibly empty) whenever `error` is absent and null when
+// it is present. Handlers map an absent upstream body to None, so settle it here for all of them.
+fn normalize_items(structured: &mut Value) {
+    let Some(obj) = structured.as_object_mut() else {
+        return;
+    };
+    let failed = obj.get("error").is_some_and(|e| !e.is_null());
+    match obj.get_mut("items") {
+        Some(items) if failed => *items = Value::Null,
+        Some(items) if items.is_null() => *items = Value::Array(Vec::new()),
+        _ => {}
+    }
+}
+
 // Prune meta fields according to include_rate and has_more.
+// - When the rate budget is low: add warning (independent of include_rate).
 // - When has_more is false/missing: drop has_more and next_cursor.
 // - When include_rate is false: drop rate.
+// - When include_pages is false: drop page, per_page and total_pages.
 // - Drop meta entirely if it becomes empty.
-fn prune_meta(structured: &mut Value, include_rate: bool) {
+fn prune_meta(structured: &mut Value, include_rate: bool, include_pages: bool) {
     let Some(obj) = structured.as_object_mut() else {
         return;
     };
@@ -53,6 +110,13 @@ fn prune_meta(structured: &mut Value, include_rate: bool) {
         return;
     };
 
+    let percent = Config::current()
+        .map(|c| c.rate_warn_percent)
+        .unwrap_or(DEFAULT_RATE_WARN_PERCENT);
+    if let Some(w) = meta_obj.get("rate").and_then(|r| rate_warning(r, percent)) {
+        meta_obj.insert("warning".to_string(), Value::String(w));
+    }
+
     let has_more = meta_obj
         .get("has_more")
         .and_then(|v| v.as_bool())
@@ -65,12 +129,118 @@ fn prune_meta(structured: &mut Value, include_rate: bool) {
     if !include_rate {
         meta_obj.remove("rate");
     }
+    if !include_pages {
+        meta_obj.remove("page");
+        meta_obj.remove("per_page");
+        meta_obj.remove("total_pages");
+    }
 
     if meta_obj.is_empty() {
         obj.remove("meta");
     }
 }
 
+// Strings shorter than this are left alone when shrinking an oversized result.
+const MIN_CUT_CHARS: usize = 64;
+
+fn json_len(v: &Value) -> usize {
+    serde_json::to_string(v).map(|s| s.len()).unwrap_or(0)
+}
+
+// Cut `s` to at most `max` bytes on a char boundary, marking the cut.
+fn cut_str(s: &mut String, max: usize) {
+    const MARK: &str = "…(truncated)";
+    if s.len() <= max {
+        return;
+    }
+    // Below the marker's own length there is no room for it; cut bare.
+    let mark = if max < MARK.len() { "" } else { MARK };
+    let mut idx = max - mark.len();
+    while !s.is_char_boundary(idx) {
+        idx -= 1;
+    }
+    s.truncate(idx);
+    s.push_str(mark);
+}
+
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+// JSON pointer and serialized size of the biggest string or array that can still be cut.
+// Base64 payloads (`*_base64`, or `content` beside `encoding: "base64"`) never are.
+fn find_largest(v: &Value, ptr: String, best: &mut Option<(usize, String)>) {
+    let size = match v {
+        Value::String(s) if s.chars().count() > MIN_CUT_CHARS => s.len(),
+        Value::Array(a) if a.len() > 1 => json_len(v),
+        _ => 0,
+    };
+    if size > best.as_ref().map(|b| b.0).unwrap_or(0) {
+        *best = Some((size, ptr.clone()));
+    }
+    match v {
+        Value::Array(a) => {
+            for (i, item) in a.iter().enumerate() {
+                find_largest(item, format!("{}/{}", ptr, i), best);
+            }
+        }
+        Value::Object(m) => {
+            let base64_content = m.get("encoding").and_then(Value::as_str) == Some("base64");
+            for (k, item) in m {
+                // A cut base64 payload is undecodable; leave it whole.
+                if k.ends_with("_base64") || (base64_content && k == "content") {
+                    continue;
+                }
+                find_largest(item, format!("{}/{}", ptr, escape_pointer(k)), best);
+            }
+        }
+        _ => {}
+    }
+}
+
+// Halve the biggest string or array in `v`; false once nothing is left worth cutting.
+fn shrink_largest(v: &mut Value) -> bool {
+    let mut best = None;
+    find_largest(v, String::new(), &mut best);
+    let Some((_, ptr)) = best else {
+        return false;
+    };
+    match v.pointer_mut(&ptr) {
+        Some(Value::String(s)) => {
+            let half = s.len() / 2;
+            cut_str(s, half);
+            true
+        }
+        Some(Value::Array(a)) => {
+            a.truncate(a.len() / 2);
+            true
+        }
+        _ => false,
+    }
+}
+
+// Keep a result under `max` bytes. The text block gets at most half the budget and
+// structuredContent the rest; the largest strings/arrays are halved until it fits.
+// When the text mirrors structuredContent (text_opt None) it is derived after shrinking.
+fn cap_output(structured: &mut Value, text_opt: &mut Option<String>, max: usize) {
+    let structured_len = json_len(structured);
+    let text_len = text_opt.as_ref().map(|t| t.len()).unwrap_or(structured_len);
+    if max == 0 || structured_len + text_len <= max {
+        return;
+    }
+    let budget = match text_opt {
+        Some(t) => {
+            cut_str(t, max / 2);
+            max - t.len().min(max)
+        }
+        None => max / 2,
+    };
+    while json_len(structured) > budget && shrink_largest(structured) {}
+    if let Some(obj) = structured.as_object_mut() {
+        obj.insert("truncated".to_string(), Value::Bool(true));
+    }
+}
+
 // Build an MCP-compliant result envelope for tools/call outputs.
 // - content: always a single text block so clients can render something.
 // - structuredContent: preserves the previous structured JSON shape to minimize breakage.
@@ -78,7 +248,13 @@ fn prune_meta(structured: &mut Value, include_rate: bool) {
 pub fn mcp_wrap(mut structured: Value, text_opt: Option<String>, is_error: bool) -> Value {
     // Apply output shaping immediately before wrapping.
     let include_rate = current_include_rate();
-    prune_meta(&mut structured, include_rate);
+    normalize_items(&mut structured);
+    prune_meta(&mut structured, include_rate, current_include_pages());
+    let max = Config::current()
+        .map(|c| c.max_response_bytes)
+        .unwrap_or(DEFAULT_MAX_RESPONSE_BYTES);
+    let mut text_opt = text_opt;
+    cap_output(&mut structured, &mut text_opt, max);
     let text = match text_opt {
         Some(s) => s,
         None => serde_json::to_string(&structured).unwrap_or_else(|_| "{}".to_string()),
@@ -94,3 +270,38 @@ pub fn mcp_wrap(mut structured: Value, text_opt: Option<String>, is_error: bool)
     }
     obj
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{cap_output, cut_str};
+
+    #[test]
+    fn cut_str_never_exceeds_max() {
+        let mut s = "é".repeat(20);
+        cut_str(&mut s, 25);
+        assert!(s.len() <= 25);
+        assert!(s.ends_with("…(truncated)"));
+
+        // Budgets smaller than the marker still hold, without it
+        for max in 0..8 {
+            let mut s = "é".repeat(20);
+            cut_str(&mut s, max);
+            assert!(s.len() <= max, "{} > {}", s.len(), max);
+            assert!(s.chars().all(|c| c == 'é'));
+        }
+    }
+
+    #[test]
+    fn cap_output_leaves_base64_payloads_whole() {
+        let blob = "QUJD".repeat(100);
+        let mut v = serde_json::json!({
+            "item": {"content_base64": blob, "notes": "n".repeat(300)},
+            "file": {"encoding": "base64", "content": blob}
+        });
+        cap_output(&mut v, &mut Some("x".into()), 1000);
+        assert_eq!(v["truncated"], true);
+        assert_eq!(v["item"]["content_base64"], blob.as_str());
+        assert_eq!(v["file"]["content"], blob.as_str());
+        assert!(v["item"]["notes"].as_str().unwrap().len() < 300);
+    }
+}
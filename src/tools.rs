@@ -1,18 +1,1056 @@
+pub use crate::types::ErrorCode;
 use crate::types::RateMeta;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ToolDescriptor {
     pub name: String,
+    // Filled in by tool_descriptors() from the name; see annotations_for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<ToolAnnotations>,
     pub description: String,
     #[serde(rename = "inputSchema")]
     pub input_schema: serde_json::Value,
 }
 
+// Capability registry kept alongside tool_descriptors(): one entry per callable tool with
+// its category and the fine-grained token permissions it needs. Scopes are hints for planning
+// least-privilege tokens; the raw escape hatches depend on the request and list none.
+// Whether a tool mutates and whether it is currently enabled come from the server's gating.
+pub struct ToolCapability {
+    pub name: &'static str,
+    pub category: &'static str,
+    pub scopes: &'static [&'static str],
+}
+
+const fn cap(
+    name: &'static str,
+    category: &'static str,
+    scopes: &'static [&'static str],
+) -> ToolCapability {
+    ToolCapability {
+        name,
+        category,
+        scopes,
+    }
+}
+
+pub const TOOL_CAPABILITIES: &[ToolCapability] = &[
+    cap("ping", "meta", &[]),
+    cap("list_capabilities", "meta", &[]),
+    cap("batch", "meta", &[]),
+    cap("get_rate_limit", "meta", &[]),
+    cap("list_issues", "issues", &["issues:read"]),
+    cap("get_issue", "issues", &["issues:read"]),
+    cap("get_issues", "issues", &["issues:read"]),
+    cap("list_issue_comments_plain", "issues", &["issues:read"]),
+    cap("create_issue", "issues", &["issues:write"]),
+    cap("update_issue", "issues", &["issues:write"]),
+    cap("add_issue_comment", "issues", &["issues:write"]),
+    cap("list_issues_for_user", "issues", &["issues:read"]),
+    cap("list_milestone_issues", "issues", &["issues:read"]),
+    cap("get_item", "issues", &["issues:read", "pull_requests:read"]),
+    cap(
+        "update_comment",
+        "issues",
+        &["issues:write", "pull_requests:write"],
+    ),
+    cap(
+        "delete_comment",
+        "issues",
+        &["issues:write", "pull_requests:write"],
+    ),
+    cap("list_pull_requests", "pulls", &["pull_requests:read"]),
+    cap("get_pull_request", "pulls", &["pull_requests:read"]),
+    cap("get_pull_requests", "pulls", &["pull_requests:read"]),
+    cap("find_pull_request", "pulls", &["pull_requests:read"]),
+    cap("pr_summary", "pulls", &["pull_requests:read"]),
+    cap(
+        "get_pr_status_summary",
+        "pulls",
+        &["pull_requests:read", "checks:read", "statuses:read"],
+    ),
+    cap(
+        "wait_for_checks",
+        "pulls",
+        &["pull_requests:read", "checks:read", "statuses:read"],
+    ),
+    cap("list_pr_comments_plain", "pulls", &["pull_requests:read"]),
+    cap(
+        "list_pr_review_comments_plain",
+        "pulls",
+        &["pull_requests:read"],
+    ),
+    cap("list_pr_review_comments", "pulls", &["pull_requests:read"]),
+    cap(
+        "list_pr_review_threads_light",
+        "pulls",
+        &["pull_requests:read"],
+    ),
+    cap(
+        "resolve_pr_review_thread",
+        "pulls",
+        &["pull_requests:write"],
+    ),
+    cap(
+        "unresolve_pr_review_thread",
+        "pulls",
+        &["pull_requests:write"],
+    ),
+    cap("list_pr_reviews_light", "pulls", &["pull_requests:read"]),
+    cap("list_pr_reviews", "pulls", &["pull_requests:read"]),
+    cap("get_pr_review", "pulls", &["pull_requests:read"]),
+    cap("list_pr_commits_light", "pulls", &["pull_requests:read"]),
+    cap("list_pr_commits", "pulls", &["pull_requests:read"]),
+    cap("list_pr_files_light", "pulls", &["pull_requests:read"]),
+    cap("list_pr_files", "pulls", &["pull_requests:read"]),
+    cap("get_pr_diff", "pulls", &["pull_requests:read"]),
+    cap("get_pr_patch", "pulls", &["pull_requests:read"]),
+    cap("reply_to_review_comment", "pulls", &["pull_requests:write"]),
+    cap("create_review_comment", "pulls", &["pull_requests:write"]),
+    cap("list_prs_for_commit", "pulls", &["pull_requests:read"]),
+    cap("list_available_merge_methods", "pulls", &["metadata:read"]),
+    cap(
+        "merge_pr",
+        "pulls",
+        &["contents:write", "pull_requests:write"],
+    ),
+    cap("create_pull_request", "pulls", &["pull_requests:write"]),
+    cap("update_pull_request", "pulls", &["pull_requests:write"]),
+    cap(
+        "close_pull_request",
+        "pulls",
+        &["pull_requests:write", "contents:write"],
+    ),
+    cap("list_workflows_light", "actions", &["actions:read"]),
+    cap("get_workflow", "actions", &["actions:read"]),
+    cap("list_workflow_runs_light", "actions", &["actions:read"]),
+    cap("get_workflow_run_light", "actions", &["actions:read"]),
+    cap("wait_for_workflow_run", "actions", &["actions:read"]),
+    cap("list_workflow_jobs_light", "actions", &["actions:read"]),
+    cap("get_workflow_job_logs", "actions", &["actions:read"]),
+    cap("rerun_workflow_run", "actions", &["actions:write"]),
+    cap("rerun_workflow_run_failed", "actions", &["actions:write"]),
+    cap("cancel_workflow_run", "actions", &["actions:write"]),
+    cap("dispatch_workflow", "actions", &["actions:write"]),
+    cap("list_commits", "git-data", &["contents:read"]),
+    cap("get_commit", "git-data", &["contents:read"]),
+    cap("list_tags", "git-data", &["contents:read"]),
+    cap("get_tag", "git-data", &["contents:read"]),
+    cap("list_branches", "git-data", &["contents:read"]),
+    cap("resolve_ref", "git-data", &["contents:read"]),
+    cap("list_branches_for_commit", "git-data", &["contents:read"]),
+    cap("list_commit_statuses", "git-data", &["statuses:read"]),
+    cap("find_files", "git-data", &["contents:read"]),
+    cap("get_repo", "repos", &["metadata:read"]),
+    cap("get_license", "repos", &["contents:read"]),
+    cap("get_file_contents", "repos", &["contents:read"]),
+    cap("create_or_update_file", "repos", &["contents:write"]),
+    cap(
+        "repo_overview",
+        "repos",
+        &["metadata:read", "contents:read"],
+    ),
+    cap(
+        "repo_activity_digest",
+        "repos",
+        &["metadata:read", "contents:read"],
+    ),
+    cap("list_releases", "repos", &["contents:read"]),
+    cap("get_release", "repos", &["contents:read"]),
+    cap("download_release_asset", "repos", &["contents:read"]),
+    cap("upload_release_asset", "repos", &["contents:write"]),
+    cap("list_starred_repositories", "repos", &["starring:read"]),
+    cap("list_watched_repositories", "repos", &["watching:read"]),
+    cap("set_repo_subscription", "repos", &["watching:write"]),
+    cap("delete_repo_subscription", "repos", &["watching:write"]),
+    cap(
+        "fork_repository",
+        "repos",
+        &["administration:write", "contents:read"],
+    ),
+    cap(
+        "search_issues",
+        "search",
+        &["issues:read", "pull_requests:read"],
+    ),
+    cap("search_pull_requests", "search", &["pull_requests:read"]),
+    cap("search_repositories", "search", &["metadata:read"]),
+    cap("list_pending_reviews", "search", &["pull_requests:read"]),
+    cap("update_gist", "gists", &["gists:write"]),
+    cap("delete_gist", "gists", &["gists:write"]),
+    cap("star_gist", "gists", &["gists:write"]),
+    cap("unstar_gist", "gists", &["gists:write"]),
+    cap("list_repo_secrets_light", "admin", &["secrets:read"]),
+    cap("list_repo_variables_light", "admin", &["variables:read"]),
+    cap("list_environments_light", "admin", &["environments:read"]),
+    cap(
+        "list_environment_variables_light",
+        "admin",
+        &["environments:read"],
+    ),
+    cap("get_environment", "admin", &["environments:read"]),
+    cap("list_deploy_keys", "admin", &["administration:read"]),
+    cap(
+        "list_org_audit_log",
+        "admin",
+        &["organization_administration:read"],
+    ),
+    cap("update_repository", "admin", &["administration:write"]),
+    cap("sync_labels", "admin", &["issues:write"]),
+    cap("graphql_query", "raw", &[]),
+    cap("rest_request", "raw", &[]),
+];
+
+// Tools that mutate GitHub state. Hidden from tools/list and rejected by tools/call when
+// GITHUB_MCP_READ_ONLY is set.
+pub const WRITE_TOOLS: &[&str] = &[
+    "resolve_pr_review_thread",
+    "unresolve_pr_review_thread",
+    "rerun_workflow_run",
+    "rerun_workflow_run_failed",
+    "cancel_workflow_run",
+    "dispatch_workflow",
+    "merge_pr",
+    "create_issue",
+    "update_issue",
+    "add_issue_comment",
+    "create_pull_request",
+    "update_pull_request",
+    "close_pull_request",
+    "fork_repository",
+    "reply_to_review_comment",
+    "create_review_comment",
+    "update_comment",
+    "delete_comment",
+    "upload_release_asset",
+    "create_or_update_file",
+    "set_repo_subscription",
+    "delete_repo_subscription",
+    "update_gist",
+    "delete_gist",
+    "star_gist",
+    "unstar_gist",
+    "update_repository",
+    "sync_labels",
+];
+
+// Writes that remove or overwrite data callers may not get back (merges, deletions, pruning,
+// archiving). Clients use destructiveHint to confirm before calling.
+const DESTRUCTIVE_TOOLS: &[&str] = &[
+    "merge_pr",
+    "close_pull_request",
+    "cancel_workflow_run",
+    "delete_comment",
+    "delete_repo_subscription",
+    "update_repository",
+    "sync_labels",
+    "update_gist",
+    "delete_gist",
+];
+
+// Writes where repeating the same call has no further effect.
+const IDEMPOTENT_WRITE_TOOLS: &[&str] = &[
+    "resolve_pr_review_thread",
+    "unresolve_pr_review_thread",
+    "update_issue",
+    "update_pull_request",
+    "close_pull_request",
+    "update_comment",
+    "delete_comment",
+    "set_repo_subscription",
+    "delete_repo_subscription",
+    "update_repository",
+    "sync_labels",
+    "update_gist",
+    "delete_gist",
+    "star_gist",
+    "unstar_gist",
+];
+
+// MCP tool annotations (hints only; gating is enforced separately).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolAnnotations {
+    pub read_only_hint: bool,
+    // Only meaningful when read_only_hint is false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destructive_hint: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotent_hint: Option<bool>,
+    pub open_world_hint: bool,
+}
+
+pub fn annotations_for(name: &str) -> ToolAnnotations {
+    // Only ping and list_capabilities stay local; everything else talks to GitHub.
+    let open_world_hint = !matches!(name, "ping" | "list_capabilities");
+    // The raw escape hatches (and batch, which can run any tool) can send anything, so
+    // assume the worst.
+    if matches!(name, "graphql_query" | "rest_request" | "batch") {
+        return ToolAnnotations {
+            read_only_hint: false,
+            destructive_hint: Some(true),
+            idempotent_hint: Some(false),
+            open_world_hint,
+        };
+    }
+    if !WRITE_TOOLS.contains(&name) {
+        return ToolAnnotations {
+            read_only_hint: true,
+            destructive_hint: None,
+            idempotent_hint: None,
+            open_world_hint,
+        };
+    }
+    ToolAnnotations {
+        read_only_hint: false,
+        destructive_hint: Some(DESTRUCTIVE_TOOLS.contains(&name)),
+        idempotent_hint: Some(IDEMPOTENT_WRITE_TOOLS.contains(&name)),
+        open_world_hint,
+    }
+}
+
+// Property docs merged into every inputSchema by tool_descriptors(): (name, title, description).
+// Descriptions written inline in a descriptor win, then per-tool overrides, then these.
+const PROPERTY_DOCS: &[(&str, &str, &str)] = &[
+    (
+        "owner",
+        "Owner",
+        "Repository owner (user or organization login)",
+    ),
+    ("repo", "Repository", "Repository name without the owner"),
+    (
+        "cursor",
+        "Cursor",
+        "Opaque pagination cursor from a previous meta.next_cursor; omit for the first page",
+    ),
+    ("limit", "Limit", "Page size, 1..=100 (default 30)"),
+    ("number", "Number", "Pull request number"),
+    ("include_author", "Include author", "Include author logins"),
+    (
+        "since",
+        "Since",
+        "Only items updated at or after this ISO 8601 timestamp",
+    ),
+    (
+        "updated_since",
+        "Updated since",
+        "Incremental sync: only items updated at or after this ISO 8601 timestamp, oldest update first; pass meta.max_updated_at back on the next run",
+    ),
+    ("until", "Until", "Only items up to this ISO 8601 timestamp"),
+    (
+        "body_max_chars",
+        "Body max chars",
+        "Truncate bodies to this many characters (default 2000)",
+    ),
+    (
+        "include_body",
+        "Include body",
+        "Include body text (truncated to body_max_chars)",
+    ),
+    (
+        "include_counts",
+        "Include counts",
+        "Add labels_count and assignees_count to each item",
+    ),
+    (
+        "calls",
+        "Calls",
+        "Tool calls to run in order, each {name, arguments}; reserved flags go in each call's arguments",
+    ),
+    ("sort", "Sort", "Sort field"),
+    ("direction", "Direction", "Sort direction"),
+    ("order", "Order", "Sort order for the chosen sort field"),
+    ("state", "State", "Filter by state"),
+    (
+        "sha_length",
+        "SHA length",
+        "full (default) or short 7-character SHAs in the output",
+    ),
+    ("body", "Body", "Markdown body text"),
+    ("comment_id", "Comment ID", "Numeric REST id of the comment"),
+    (
+        "page",
+        "Page",
+        "1-based REST page number (ignored when cursor is set)",
+    ),
+    ("per_page", "Per page", "REST page size, 1..=100"),
+    ("ref", "Ref", "Branch, tag, or commit SHA"),
+    ("labels", "Labels", "Label names"),
+    ("path", "Path", "File path relative to the repository root"),
+    ("sha", "SHA", "Commit SHA"),
+    ("gist_id", "Gist ID", "Gist identifier as shown in its URL"),
+    ("branch", "Branch", "Branch name"),
+    ("base", "Base", "Base branch name"),
+    ("head", "Head", "Head branch name, or user:branch for forks"),
+    (
+        "include_location",
+        "Include location",
+        "Include file path and line information for each comment",
+    ),
+    (
+        "q",
+        "Query",
+        "GitHub search query syntax, e.g. 'repo:o/r is:open label:bug'",
+    ),
+    (
+        "thread_id",
+        "Thread ID",
+        "GraphQL node id of the review thread",
+    ),
+    (
+        "include_patch",
+        "Include patch",
+        "Include the unified diff patch per file",
+    ),
+    (
+        "include_files",
+        "Include files",
+        "Include the list of changed files",
+    ),
+    (
+        "timeout_secs",
+        "Timeout (seconds)",
+        "Maximum time to wait, 1..=1800 (default 300)",
+    ),
+    (
+        "include_stats",
+        "Include stats",
+        "Include addition/deletion counts",
+    ),
+    (
+        "include_verification",
+        "Include verification",
+        "Include whether the commit signature is verified and why",
+    ),
+    (
+        "include_protection_summary",
+        "Include protection summary",
+        "For protected branches, fetch required review count and required checks (one request per protected branch)",
+    ),
+    ("tag", "Tag", "Tag name"),
+    ("release_id", "Release ID", "Numeric release id"),
+    ("milestone", "Milestone", "Milestone number"),
+    ("title", "Title", "New title"),
+    (
+        "numbers",
+        "Numbers",
+        "Issue or pull request numbers to fetch in one call",
+    ),
+    (
+        "comment_type",
+        "Comment type",
+        "issue (conversation comment, default) or review (inline review comment)",
+    ),
+    ("name", "Name", "New name"),
+    ("description", "Description", "New description"),
+    ("query", "Query", "Query text"),
+    ("message", "Message", "Text to echo back"),
+    (
+        "category",
+        "Category",
+        "Only return this category (e.g. issues, pulls, actions, git-data, search, admin)",
+    ),
+    (
+        "assignee",
+        "Assignee",
+        "Filter by assignee login; none for unassigned, * for any assignee",
+    ),
+    (
+        "creator",
+        "Creator",
+        "Filter by the login that opened the issue",
+    ),
+    ("mentions", "Mentions", "Filter by a mentioned login"),
+    (
+        "include_assignees",
+        "Include assignees",
+        "Include assignee logins",
+    ),
+    (
+        "include_checks",
+        "Include checks",
+        "Include the status-check summary of the head commit",
+    ),
+    (
+        "include_reviewers",
+        "Include reviewers",
+        "Include requested reviewers",
+    ),
+    (
+        "reviewer",
+        "Reviewer",
+        "Login whose review is requested",
+    ),
+    (
+        "include_drafts",
+        "Include drafts",
+        "Include draft pull requests",
+    ),
+    (
+        "include_reviews",
+        "Include reviews",
+        "Include submitted reviews",
+    ),
+    ("run_id", "Run ID", "Numeric workflow run id"),
+    (
+        "review_id",
+        "Review ID",
+        "Numeric review id (database_id from list_pr_reviews)",
+    ),
+    (
+        "environment_name",
+        "Environment",
+        "Deployment environment name",
+    ),
+    ("author", "Author", "Filter by commit author login or email"),
+    (
+        "include_object",
+        "Include object",
+        "Reserved; tag object details are not returned yet",
+    ),
+    (
+        "resolve_annotated",
+        "Resolve annotated",
+        "Peel annotated tags to the commit they point at (default true)",
+    ),
+    (
+        "protected",
+        "Protected",
+        "Only protected (true) or unprotected (false) branches",
+    ),
+    ("exclude_drafts", "Exclude drafts", "Skip draft releases"),
+    (
+        "exclude_prereleases",
+        "Exclude prereleases",
+        "Skip prereleases",
+    ),
+    (
+        "include_starred_at",
+        "Include starred_at",
+        "Include when each repository was starred",
+    ),
+    (
+        "commit_message",
+        "Commit message",
+        "Extra detail for the merge commit message",
+    ),
+    ("commit_title", "Commit title", "Title for the merge commit"),
+    (
+        "merge_method",
+        "Merge method",
+        "merge, squash, or rebase (see list_available_merge_methods)",
+    ),
+    (
+        "assignees",
+        "Assignees",
+        "Replace assignees with these logins",
+    ),
+    ("draft", "Draft", "Open the pull request as a draft"),
+    (
+        "maintainer_can_modify",
+        "Maintainer can modify",
+        "Allow maintainers of the base repository to push to the head branch",
+    ),
+    (
+        "delete_branch",
+        "Delete branch",
+        "Also delete the head branch when it lives in this repository",
+    ),
+    (
+        "organization",
+        "Organization",
+        "Fork into this organization instead of the authenticated user",
+    ),
+    (
+        "commit_id",
+        "Commit ID",
+        "SHA of the commit to comment on; defaults to the PR head",
+    ),
+    (
+        "line",
+        "Line",
+        "Line in the diff to comment on (last line for multi-line comments)",
+    ),
+    (
+        "side",
+        "Side",
+        "Diff side of line: LEFT (deletions) or RIGHT (additions/context)",
+    ),
+    (
+        "start_line",
+        "Start line",
+        "First line of a multi-line comment",
+    ),
+    ("start_side", "Start side", "Diff side of start_line"),
+    ("asset_id", "Asset ID", "Numeric release asset id"),
+    (
+        "max_bytes",
+        "Max bytes",
+        "Refuse assets larger than this (default 1 MiB, max 10 MiB)",
+    ),
+    ("content", "Content", "Base64-encoded file bytes"),
+    (
+        "content_type",
+        "Content type",
+        "MIME type of the asset (default application/octet-stream)",
+    ),
+    (
+        "label",
+        "Label",
+        "Display label shown instead of the file name",
+    ),
+    (
+        "max_results",
+        "Max results",
+        "Maximum paths returned, 1..=1000 (default 100)",
+    ),
+    ("pattern", "Pattern", "Glob pattern"),
+    (
+        "ignored",
+        "Ignored",
+        "Ignore all notifications from the repository",
+    ),
+    (
+        "subscribed",
+        "Subscribed",
+        "Receive notifications from the repository",
+    ),
+    (
+        "files",
+        "Files",
+        "Map of file name to {content, filename}; a null value deletes the file",
+    ),
+    (
+        "filter",
+        "Filter",
+        "Which issues to return relative to the user (default assigned)",
+    ),
+    (
+        "owned_only",
+        "Owned only",
+        "Limit to repositories the user owns or is a member of",
+    ),
+    (
+        "archived",
+        "Archived",
+        "Archive (true) or unarchive (false) the repository",
+    ),
+    (
+        "confirm_archive",
+        "Confirm archive",
+        "Must be true when archived=true",
+    ),
+    (
+        "default_branch",
+        "Default branch",
+        "New default branch; it must already exist",
+    ),
+    ("has_discussions", "Discussions", "Enable discussions"),
+    ("has_issues", "Issues", "Enable issues"),
+    ("has_projects", "Projects", "Enable projects"),
+    ("has_wiki", "Wiki", "Enable the wiki"),
+    ("homepage", "Homepage", "Homepage URL"),
+    (
+        "private",
+        "Private",
+        "Make the repository private (true) or public (false)",
+    ),
+    (
+        "visibility",
+        "Visibility",
+        "public, private, or internal (organization repos on Enterprise)",
+    ),
+    (
+        "dry_run",
+        "Dry run",
+        "Report the planned changes without applying them",
+    ),
+    ("prune", "Prune", "Delete labels not in the desired set"),
+    (
+        "include_pull_requests",
+        "Include pull requests",
+        "Also list pull requests in the milestone (default true)",
+    ),
+    ("variables", "Variables", "GraphQL variables object"),
+    (
+        "accept",
+        "Accept",
+        "Accept header override (default application/vnd.github+json)",
+    ),
+    ("method", "Method", "HTTP method"),
+    (
+        "workflow_id",
+        "Workflow",
+        "Workflow id, or its file name under .github/workflows (e.g. ci.yml)",
+    ),
+    (
+        "inputs",
+        "Inputs",
+        "workflow_dispatch inputs as a JSON object, passed through unchanged",
+    ),
+    ("org", "Organization", "Organization login"),
+    ("language", "Language", "Primary language, e.g. rust"),
+    (
+        "stars",
+        "Stars",
+        "Star count filter, e.g. >100 or 10..50",
+    ),
+    (
+        "pushed",
+        "Pushed",
+        "Last push date filter, e.g. >2025-01-01",
+    ),
+    ("topic", "Topic", "Repository topic"),
+    (
+        "created",
+        "Created",
+        "Creation date filter, e.g. >=2025-01-01",
+    ),
+    (
+        "updated",
+        "Updated",
+        "Last update date filter, e.g. >2025-06-01",
+    ),
+    ("user", "User", "User login"),
+    (
+        "phrase",
+        "Phrase",
+        "Audit log search phrase, e.g. \"action:repo.create actor:octocat\"",
+    ),
+    (
+        "include",
+        "Include",
+        "Event sources: web (default), git, or all",
+    ),
+];
+
+// (tool, property, description) where the shared wording would be wrong or vague.
+const PROPERTY_DOC_OVERRIDES: &[(&str, &str, &str)] = &[
+    ("get_issue", "number", "Issue number"),
+    ("list_issue_comments_plain", "number", "Issue number"),
+    ("update_issue", "number", "Issue number"),
+    (
+        "add_issue_comment",
+        "number",
+        "Issue or pull request number",
+    ),
+    ("add_issue_comment", "body", "Comment text (Markdown)"),
+    ("get_item", "number", "Issue or pull request number"),
+    ("update_issue", "state", "New state"),
+    ("update_pull_request", "state", "New state"),
+    (
+        "update_issue",
+        "labels",
+        "Replace the labels with these names",
+    ),
+    (
+        "update_issue",
+        "milestone",
+        "Milestone number; replaces the current milestone",
+    ),
+    ("update_issue", "body", "New body (Markdown)"),
+    ("create_issue", "title", "Issue title"),
+    ("create_issue", "body", "Issue body (Markdown)"),
+    ("create_issue", "labels", "Label names to apply"),
+    ("create_issue", "assignees", "Logins to assign"),
+    ("create_pull_request", "title", "Pull request title"),
+    (
+        "create_pull_request",
+        "body",
+        "Pull request body (Markdown)",
+    ),
+    (
+        "create_pull_request",
+        "base",
+        "Branch the changes should be merged into",
+    ),
+    ("update_pull_request", "body", "New body (Markdown)"),
+    (
+        "update_pull_request",
+        "base",
+        "Retarget the pull request to this base branch",
+    ),
+    ("update_comment", "body", "New comment body (Markdown)"),
+    (
+        "list_issues",
+        "labels",
+        "Only issues with all of these labels",
+    ),
+    (
+        "list_issues_for_user",
+        "labels",
+        "Only issues with all of these labels",
+    ),
+    ("list_milestone_issues", "milestone", "Milestone number"),
+    (
+        "list_branches",
+        "sort",
+        "name keeps GitHub's order; protected_first moves protected branches to the front of the page",
+    ),
+    (
+        "list_commits",
+        "sha",
+        "Branch name or SHA to start listing from (default branch when omitted)",
+    ),
+    ("list_commits", "path", "Only commits touching this path"),
+    (
+        "list_commits",
+        "since",
+        "Only commits after this ISO 8601 timestamp",
+    ),
+    (
+        "list_commits",
+        "until",
+        "Only commits before this ISO 8601 timestamp",
+    ),
+    (
+        "merge_pr",
+        "sha",
+        "Expected head SHA; the merge fails if the head moved",
+    ),
+    (
+        "get_pr_diff",
+        "path",
+        "Only return the diff section for this file",
+    ),
+    (
+        "create_review_comment",
+        "path",
+        "Path of the file to comment on",
+    ),
+    ("create_review_comment", "body", "Comment text (Markdown)"),
+    ("search_issues", "q", "Free-text query or raw qualifiers; optional when a structured qualifier is given"),
+    ("search_issues", "state", "open or closed"),
+    ("search_issues", "label", "Only results with this label"),
+    ("search_issues", "author", "Author login"),
+    ("search_issues", "assignee", "Assignee login"),
+    ("search_issues", "milestone", "Milestone title"),
+    ("search_issues", "repo", "owner/name of the repository to search in"),
+    ("search_issues", "org", "Only results in this organization's repositories"),
+    ("search_pull_requests", "q", "Free-text query or raw qualifiers; optional when a structured qualifier is given"),
+    ("search_pull_requests", "state", "open or closed"),
+    ("search_pull_requests", "label", "Only results with this label"),
+    ("search_pull_requests", "author", "Author login"),
+    ("search_pull_requests", "assignee", "Assignee login"),
+    ("search_pull_requests", "milestone", "Milestone title"),
+    ("search_pull_requests", "repo", "owner/name of the repository to search in"),
+    ("search_pull_requests", "org", "Only results in this organization's repositories"),
+    (
+        "search_repositories",
+        "q",
+        "Free-text query or raw qualifiers; optional when a structured qualifier is given",
+    ),
+    (
+        "search_repositories",
+        "org",
+        "Only repositories owned by this organization",
+    ),
+    (
+        "search_repositories",
+        "user",
+        "Only repositories owned by this user",
+    ),
+    ("create_or_update_file", "message", "Commit message"),
+    (
+        "create_or_update_file",
+        "branch",
+        "Branch to commit to (default branch when omitted)",
+    ),
+    (
+        "create_or_update_file",
+        "content",
+        "New file contents as plain text; encoded by the server",
+    ),
+    (
+        "create_or_update_file",
+        "sha",
+        "Blob SHA of the file being replaced; required when the file already exists",
+    ),
+    (
+        "get_file_contents",
+        "path",
+        "File or directory path relative to the repository root; empty for the root",
+    ),
+    (
+        "get_file_contents",
+        "max_bytes",
+        "Binary files larger than this return truncated: true instead of content (default and max 1 MiB)",
+    ),
+    ("reply_to_review_comment", "body", "Reply text (Markdown)"),
+    (
+        "reply_to_review_comment",
+        "comment_id",
+        "Id of the top-level review comment to reply to",
+    ),
+    (
+        "resolve_pr_review_thread",
+        "number",
+        "Pull request number (with comment_id, instead of thread_id)",
+    ),
+    (
+        "unresolve_pr_review_thread",
+        "number",
+        "Pull request number (with comment_id, instead of thread_id)",
+    ),
+    (
+        "resolve_pr_review_thread",
+        "comment_id",
+        "Review comment id in the thread (with number, instead of thread_id)",
+    ),
+    (
+        "unresolve_pr_review_thread",
+        "comment_id",
+        "Review comment id in the thread (with number, instead of thread_id)",
+    ),
+    (
+        "wait_for_checks",
+        "number",
+        "Pull request number; mutually exclusive with ref",
+    ),
+    (
+        "list_pending_reviews",
+        "owner",
+        "Repository owner; use with repo, or pass org instead",
+    ),
+    (
+        "list_pending_reviews",
+        "org",
+        "Organization whose repositories to cover; alternative to owner+repo",
+    ),
+    (
+        "list_pending_reviews",
+        "reviewer",
+        "Only PRs whose review is requested from this login (review-requested:); default is review:required",
+    ),
+    (
+        "list_pending_reviews",
+        "include_reviewers",
+        "Fetch requested reviewers for each PR (default true)",
+    ),
+    (
+        "get_pr_review",
+        "include_location",
+        "Include file path and line information for each comment (default true)",
+    ),
+    (
+        "dispatch_workflow",
+        "ref",
+        "Branch or tag to run the workflow on",
+    ),
+    (
+        "wait_for_checks",
+        "ref",
+        "Branch, tag, or SHA; mutually exclusive with number",
+    ),
+    (
+        "list_starred_repositories",
+        "direction",
+        "Sort direction (default desc)",
+    ),
+    (
+        "search_issues",
+        "sort",
+        "comments, reactions, created, updated, ... (best match when omitted)",
+    ),
+    (
+        "search_pull_requests",
+        "sort",
+        "comments, reactions, created, updated, ... (best match when omitted)",
+    ),
+    (
+        "search_repositories",
+        "sort",
+        "stars, forks, help-wanted-issues, or updated (best match when omitted)",
+    ),
+    ("upload_release_asset", "name", "File name of the asset"),
+    ("update_repository", "name", "Rename the repository"),
+    ("update_repository", "description", "New short description"),
+    ("update_gist", "description", "New gist description"),
+    (
+        "sync_labels",
+        "labels",
+        "Desired labels: {name, color, description}",
+    ),
+    (
+        "graphql_query",
+        "query",
+        "GraphQL document, passed through as-is",
+    ),
+    (
+        "rest_request",
+        "body",
+        "JSON request body for POST/PUT/PATCH",
+    ),
+    (
+        "list_issues",
+        "since",
+        "Only issues updated at or after this ISO 8601 timestamp",
+    ),
+    (
+        "list_issues_for_user",
+        "since",
+        "Only issues updated at or after this ISO 8601 timestamp",
+    ),
+    (
+        "list_issues",
+        "sort",
+        "Sort by created, updated, or comments",
+    ),
+    (
+        "list_issues_for_user",
+        "sort",
+        "Sort by created, updated, or comments",
+    ),
+    (
+        "list_pull_requests",
+        "base",
+        "Only pull requests into this base branch",
+    ),
+    (
+        "find_pull_request",
+        "base",
+        "Only pull requests into this base branch",
+    ),
+    (
+        "list_pull_requests",
+        "head",
+        "Only pull requests from this head branch name",
+    ),
+];
+
+fn document_properties(tool: &str, schema: &mut serde_json::Value) {
+    let Some(props) = schema.get_mut("properties").and_then(|p| p.as_object_mut()) else {
+        return;
+    };
+    for (name, prop) in props.iter_mut() {
+        let Some(prop) = prop.as_object_mut() else {
+            continue;
+        };
+        let shared = PROPERTY_DOCS.iter().find(|(n, _, _)| n == name);
+        if !prop.contains_key("title") {
+            if let Some((_, title, _)) = shared {
+                prop.insert("title".into(), (*title).into());
+            }
+        }
+        if !prop.contains_key("description") {
+            let description = PROPERTY_DOC_OVERRIDES
+                .iter()
+                .find(|(t, n, _)| *t == tool && n == name)
+                .map(|(_, _, d)| *d)
+                .or(shared.map(|(_, _, d)| *d));
+            if let Some(d) = description {
+                prop.insert("description".into(), d.into());
+            }
+        }
+    }
+}
+
 pub fn tool_descriptors() -> Vec<ToolDescriptor> {
     // Milestone 4 will append real GitHub tools; for now includes ping and Issues tools.
     let ping = ToolDescriptor {
         name: "ping".into(),
+        annotations: None,
         description: "Health check; echoes a message.".into(),
         input_schema: serde_json::json!({
             "type": "object",
@@ -25,6 +1063,7 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
 
     let list_issues = ToolDescriptor {
         name: "list_issues".into(),
+        annotations: None,
         description: "List issues in a repository".into(),
         input_schema: serde_json::json!({
             "type": "object",
@@ -38,11 +1077,15 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
                 "assignee": {"type": "string"},
                 "mentions": {"type": "string"},
                 "since": {"type": "string"},
+                "updated_since": {"type": "string"},
                 "sort": {"type": "string", "enum": ["created", "updated", "comments"]},
                 "direction": {"type": "string", "enum": ["asc", "desc"]},
                 "cursor": {"type": "string"},
                 "limit": {"type": "integer"},
-                "include_author": {"type": "boolean"}
+                "include_author": {"type": "boolean"},
+                "include_body": {"type": "boolean"},
+                "body_max_chars": {"type": "integer", "minimum": 1},
+                "include_counts": {"type": "boolean"}
             },
             "required": ["owner", "repo"]
         }),
@@ -50,6 +1093,7 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
 
     let get_issue = ToolDescriptor {
         name: "get_issue".into(),
+        annotations: None,
         description: "Get a single issue by number".into(),
         input_schema: serde_json::json!({
             "type": "object",
@@ -66,6 +1110,7 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
 
     let list_issue_comments_plain = ToolDescriptor {
         name: "list_issue_comments_plain".into(),
+        annotations: None,
         description: "List issue comments (plain)".into(),
         input_schema: serde_json::json!({
             "type": "object",
@@ -84,6 +1129,7 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
 
     let list_prs = ToolDescriptor {
         name: "list_pull_requests".into(),
+        annotations: None,
         description: "List pull requests".into(),
         input_schema: serde_json::json!({
             "type": "object",
@@ -96,7 +1142,9 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
                 "head": {"type": "string"},
                 "cursor": {"type": "string"},
                 "limit": {"type": "integer"},
-                "include_author": {"type": "boolean"}
+                "include_author": {"type": "boolean"},
+                "include_body": {"type": "boolean"},
+                "body_max_chars": {"type": "integer", "minimum": 1}
             },
             "required": ["owner", "repo"]
         }),
@@ -104,6 +1152,7 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
 
     let get_pr = ToolDescriptor {
         name: "get_pull_request".into(),
+        annotations: None,
         description: "Get a single PR".into(),
         input_schema: serde_json::json!({
             "type": "object",
@@ -120,20 +1169,22 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
 
     let list_pr_comments = ToolDescriptor {
         name: "list_pr_comments_plain".into(),
+        annotations: None,
         description: "List PR issue comments (plain)".into(),
         input_schema: serde_json::json!({
             "type":"object","additionalProperties":false,
-            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"number":{"type":"integer"},"cursor":{"type":"string"},"limit":{"type":"integer"},"include_author":{"type":"boolean"}},
+            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"number":{"type":"integer"},"cursor":{"type":"string"},"limit":{"type":"integer"},"include_author":{"type":"boolean"},"since":{"type":"string","format":"date-time"}},
             "required":["owner","repo","number"]
         }),
     };
 
     let list_pr_review_comments = ToolDescriptor {
         name: "list_pr_review_comments_plain".into(),
+        annotations: None,
         description: "List PR review comments (plain)".into(),
         input_schema: serde_json::json!({
             "type":"object","additionalProperties":false,
-            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"number":{"type":"integer"},"cursor":{"type":"string"},"limit":{"type":"integer"},"include_author":{"type":"boolean"},"include_location":{"type":"boolean"}},
+            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"number":{"type":"integer"},"cursor":{"type":"string"},"limit":{"type":"integer"},"include_author":{"type":"boolean"},"since":{"type":"string","format":"date-time"},"include_location":{"type":"boolean"}},
             "required":["owner","repo","number"]
         }),
     };
@@ -141,16 +1192,18 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
     // Unified aliases (non-deprecated): prefer these going forward
     let list_pr_review_comments_unified = ToolDescriptor {
         name: "list_pr_review_comments".into(),
+        annotations: None,
         description: "List PR review comments (unified; flags control optional fields)".into(),
         input_schema: serde_json::json!({
             "type":"object","additionalProperties":false,
-            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"number":{"type":"integer"},"cursor":{"type":"string"},"limit":{"type":"integer"},"include_author":{"type":"boolean"},"include_location":{"type":"boolean"}},
+            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"number":{"type":"integer"},"cursor":{"type":"string"},"limit":{"type":"integer"},"include_author":{"type":"boolean"},"since":{"type":"string","format":"date-time"},"include_location":{"type":"boolean"}},
             "required":["owner","repo","number"]
         }),
     };
 
     let list_pr_review_threads = ToolDescriptor {
         name: "list_pr_review_threads_light".into(),
+        annotations: None,
         description: "List PR review threads (light)".into(),
         input_schema: serde_json::json!({
             "type":"object","additionalProperties":false,
@@ -161,38 +1214,62 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
 
     let resolve_thread = ToolDescriptor {
         name: "resolve_pr_review_thread".into(),
-        description: "Resolve a PR review thread".into(),
-        input_schema: serde_json::json!({"type":"object","additionalProperties":false,"properties":{"thread_id":{"type":"string"}},"required":["thread_id"]}),
+        annotations: None,
+        description: "Resolve a PR review thread (by thread_id, or PR number + review comment_id)"
+            .into(),
+        input_schema: serde_json::json!({"type":"object","additionalProperties":false,
+            "properties":{"thread_id":{"type":"string"},"owner":{"type":"string"},"repo":{"type":"string"},
+                "number":{"type":"integer"},"comment_id":{"type":"integer"}}}),
     };
 
     let unresolve_thread = ToolDescriptor {
         name: "unresolve_pr_review_thread".into(),
-        description: "Unresolve a PR review thread".into(),
-        input_schema: serde_json::json!({"type":"object","additionalProperties":false,"properties":{"thread_id":{"type":"string"}},"required":["thread_id"]}),
+        annotations: None,
+        description:
+            "Unresolve a PR review thread (by thread_id, or PR number + review comment_id)".into(),
+        input_schema: serde_json::json!({"type":"object","additionalProperties":false,
+            "properties":{"thread_id":{"type":"string"},"owner":{"type":"string"},"repo":{"type":"string"},
+                "number":{"type":"integer"},"comment_id":{"type":"integer"}}}),
     };
 
     let list_pr_reviews = ToolDescriptor {
         name: "list_pr_reviews_light".into(),
+        annotations: None,
         description: "List PR reviews (light)".into(),
         input_schema: serde_json::json!({
             "type":"object","additionalProperties":false,
-            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"number":{"type":"integer"},"cursor":{"type":"string"},"limit":{"type":"integer"},"include_author":{"type":"boolean"}},
+            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"number":{"type":"integer"},"cursor":{"type":"string"},"limit":{"type":"integer"},"include_author":{"type":"boolean"},"include_body":{"type":"boolean"},"body_max_chars":{"type":"integer","minimum":1}},
             "required":["owner","repo","number"]
         }),
     };
 
     let list_pr_reviews_unified = ToolDescriptor {
         name: "list_pr_reviews".into(),
+        annotations: None,
         description: "List PR reviews (unified)".into(),
         input_schema: serde_json::json!({
             "type":"object","additionalProperties":false,
-            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"number":{"type":"integer"},"cursor":{"type":"string"},"limit":{"type":"integer"},"include_author":{"type":"boolean"}},
+            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"number":{"type":"integer"},"cursor":{"type":"string"},"limit":{"type":"integer"},"include_author":{"type":"boolean"},"include_body":{"type":"boolean"},"body_max_chars":{"type":"integer","minimum":1}},
             "required":["owner","repo","number"]
         }),
     };
 
+    let get_pr_review = ToolDescriptor {
+        name: "get_pr_review".into(),
+        annotations: None,
+        description:
+            "Get one PR review (state, body, author) with its inline comments; comments paginate"
+                .into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"number":{"type":"integer"},"review_id":{"type":"integer"},"cursor":{"type":"string"},"limit":{"type":"integer"},"include_location":{"type":"boolean"}},
+            "required":["owner","repo","number","review_id"]
+        }),
+    };
+
     let list_pr_commits = ToolDescriptor {
         name: "list_pr_commits_light".into(),
+        annotations: None,
         description: "List PR commits (light)".into(),
         input_schema: serde_json::json!({
             "type":"object","additionalProperties":false,
@@ -203,16 +1280,18 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
 
     let list_pr_commits_unified = ToolDescriptor {
         name: "list_pr_commits".into(),
+        annotations: None,
         description: "List PR commits (unified)".into(),
         input_schema: serde_json::json!({
             "type":"object","additionalProperties":false,
-            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"number":{"type":"integer"},"cursor":{"type":"string"},"limit":{"type":"integer"},"include_author":{"type":"boolean"}},
+            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"number":{"type":"integer"},"cursor":{"type":"string"},"limit":{"type":"integer"},"include_author":{"type":"boolean"},"sha_length":{"type":"string","enum":["full","short"]}},
             "required":["owner","repo","number"]
         }),
     };
 
     let list_pr_files = ToolDescriptor {
         name: "list_pr_files_light".into(),
+        annotations: None,
         description: "List PR files (REST)".into(),
         input_schema: serde_json::json!({
             "type":"object","additionalProperties":false,
@@ -223,6 +1302,7 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
 
     let list_pr_files_unified = ToolDescriptor {
         name: "list_pr_files".into(),
+        annotations: None,
         description: "List PR files (unified; REST)".into(),
         input_schema: serde_json::json!({
             "type":"object","additionalProperties":false,
@@ -233,17 +1313,20 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
 
     let get_pr_diff = ToolDescriptor {
         name: "get_pr_diff".into(),
-        description: "Get PR diff (REST)".into(),
-        input_schema: serde_json::json!({"type":"object","additionalProperties":false,"properties":{"owner":{"type":"string"},"repo":{"type":"string"},"number":{"type":"integer"}},"required":["owner","repo","number"]}),
+        annotations: None,
+        description: "Get PR diff (REST); pass path to get a single file's diff".into(),
+        input_schema: serde_json::json!({"type":"object","additionalProperties":false,"properties":{"owner":{"type":"string"},"repo":{"type":"string"},"number":{"type":"integer"},"path":{"type":"string"}},"required":["owner","repo","number"]}),
     };
     let get_pr_patch = ToolDescriptor {
         name: "get_pr_patch".into(),
+        annotations: None,
         description: "Get PR patch (REST)".into(),
         input_schema: serde_json::json!({"type":"object","additionalProperties":false,"properties":{"owner":{"type":"string"},"repo":{"type":"string"},"number":{"type":"integer"}},"required":["owner","repo","number"]}),
     };
 
     let pr_summary = ToolDescriptor {
         name: "pr_summary".into(),
+        annotations: None,
         description: "Get PR summary including checks, files, reviews".into(),
         input_schema: serde_json::json!({
             "type": "object",
@@ -254,7 +1337,9 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
                 "number": {"type": "integer"},
                 "include_checks": {"type": "boolean"},
                 "include_files": {"type": "boolean"},
-                "include_reviews": {"type": "boolean"}
+                "include_reviews": {"type": "boolean"},
+                "include_reviewers": {"type": "boolean"},
+                "include_assignees": {"type": "boolean"}
             },
             "required": ["owner", "repo", "number"]
         }),
@@ -263,6 +1348,7 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
     // New methods per Issue #91
     let list_commits = ToolDescriptor {
         name: "list_commits".into(),
+        annotations: None,
         description: "List commits for a repository".into(),
         input_schema: serde_json::json!({
             "type":"object","additionalProperties":false,
@@ -277,13 +1363,16 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
                 "cursor":{"type":"string"},
                 "limit":{"type":"integer"},
                 "include_author":{"type":"boolean"},
-                "include_stats":{"type":"boolean"}
+                "include_stats":{"type":"boolean"},
+                "include_verification":{"type":"boolean"},
+                "sha_length":{"type":"string","enum":["full","short"]}
             },
             "required":["owner","repo"]
         }),
     };
     let get_commit = ToolDescriptor {
         name: "get_commit".into(),
+        annotations: None,
         description: "Get a single commit by SHA or ref".into(),
         input_schema: serde_json::json!({
             "type":"object","additionalProperties":false,
@@ -292,49 +1381,57 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
                 "repo":{"type":"string"},
                 "ref":{"type":"string"},
                 "include_stats":{"type":"boolean"},
-                "include_files":{"type":"boolean"}
+                "include_files":{"type":"boolean"},
+                "include_verification":{"type":"boolean"},
+                "sha_length":{"type":"string","enum":["full","short"]}
             },
             "required":["owner","repo","ref"]
         }),
     };
     let list_tags = ToolDescriptor {
         name: "list_tags".into(),
+        annotations: None,
         description: "List tags for a repository".into(),
         input_schema: serde_json::json!({
             "type":"object","additionalProperties":false,
-            "properties":{ "owner":{"type":"string"}, "repo":{"type":"string"}, "cursor":{"type":"string"}, "limit":{"type":"integer"}, "include_object":{"type":"boolean"}},
+            "properties":{ "owner":{"type":"string"}, "repo":{"type":"string"}, "cursor":{"type":"string"}, "limit":{"type":"integer"}, "include_object":{"type":"boolean"}, "sha_length":{"type":"string","enum":["full","short"]}},
             "required":["owner","repo"]
         }),
     };
     let get_tag = ToolDescriptor {
         name: "get_tag".into(),
+        annotations: None,
         description: "Get a tag by name (resolves annotated)".into(),
         input_schema: serde_json::json!({
             "type":"object","additionalProperties":false,
-            "properties":{ "owner":{"type":"string"}, "repo":{"type":"string"}, "tag":{"type":"string"}, "resolve_annotated":{"type":"boolean"}},
+            "properties":{ "owner":{"type":"string"}, "repo":{"type":"string"}, "tag":{"type":"string"}, "resolve_annotated":{"type":"boolean"}, "sha_length":{"type":"string","enum":["full","short"]}},
             "required":["owner","repo","tag"]
         }),
     };
     let list_branches = ToolDescriptor {
         name: "list_branches".into(),
-        description: "List branches in a repository".into(),
+        annotations: None,
+        description: "List branches in a repository; include_protection_summary adds required reviews and checks for protected branches (one extra request each)".into(),
         input_schema: serde_json::json!({
             "type":"object","additionalProperties":false,
-            "properties":{ "owner":{"type":"string"}, "repo":{"type":"string"}, "protected":{"type":"boolean"}, "cursor":{"type":"string"}, "limit":{"type":"integer"}},
+            "properties":{ "owner":{"type":"string"}, "repo":{"type":"string"}, "protected":{"type":"boolean"}, "cursor":{"type":"string"}, "limit":{"type":"integer"}, "sha_length":{"type":"string","enum":["full","short"]},
+                "sort":{"type":"string","enum":["name","protected_first"]}, "include_protection_summary":{"type":"boolean"}},
             "required":["owner","repo"]
         }),
     };
     let list_releases = ToolDescriptor {
         name: "list_releases".into(),
-        description: "List releases for a repository".into(),
+        annotations: None,
+        description: "List releases for a repository (exclude_* filters apply per page; pages may be short while has_more is true)".into(),
         input_schema: serde_json::json!({
             "type":"object","additionalProperties":false,
-            "properties":{ "owner":{"type":"string"}, "repo":{"type":"string"}, "cursor":{"type":"string"}, "limit":{"type":"integer"}},
+            "properties":{ "owner":{"type":"string"}, "repo":{"type":"string"}, "cursor":{"type":"string"}, "limit":{"type":"integer"}, "exclude_drafts":{"type":"boolean"}, "exclude_prereleases":{"type":"boolean"}},
             "required":["owner","repo"]
         }),
     };
     let get_release = ToolDescriptor {
         name: "get_release".into(),
+        annotations: None,
         description: "Get a release by id or tag".into(),
         input_schema: serde_json::json!({
             "type":"object","additionalProperties":false,
@@ -344,15 +1441,94 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
     };
     let list_starred_repositories = ToolDescriptor {
         name: "list_starred_repositories".into(),
+        annotations: None,
         description: "List repositories starred by the authenticated user".into(),
         input_schema: serde_json::json!({
             "type":"object","additionalProperties":false,
-            "properties":{ "cursor":{"type":"string"}, "limit":{"type":"integer"}, "sort":{"type":"string","enum":["created","updated"]}, "direction":{"type":"string","enum":["asc","desc"]}, "include_starred_at":{"type":"boolean"}},
+            "properties":{ "cursor":{"type":"string"}, "limit":{"type":"integer"}, "sort":{"type":"string","enum":["created","updated","starred"],"description":"created/starred: when you starred the repo (default); updated: the repo's last push, not star time. starred also returns starred_at"}, "direction":{"type":"string","enum":["asc","desc"]}, "include_starred_at":{"type":"boolean"}},
+            "required":[]
+        }),
+    };
+    let list_watched_repositories = ToolDescriptor {
+        name: "list_watched_repositories".into(),
+        annotations: None,
+        description: "List repositories watched by the authenticated user".into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties":{ "cursor":{"type":"string"}, "limit":{"type":"integer"}},
             "required":[]
         }),
     };
+    let get_repo = ToolDescriptor {
+        name: "get_repo".into(),
+        annotations: None,
+        description: "Get repository metadata including clone_url, ssh_url and git_url".into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties":{"owner":{"type":"string"},"repo":{"type":"string"}},
+            "required":["owner","repo"]
+        }),
+    };
+    let get_file_contents = ToolDescriptor {
+        name: "get_file_contents".into(),
+        annotations: None,
+        description: "Read a file (decoded text, or base64 for binary files up to max_bytes) or list a directory at an optional ref".into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties":{
+                "owner":{"type":"string"},
+                "repo":{"type":"string"},
+                "path":{"type":"string"},
+                "ref":{"type":"string"},
+                "max_bytes":{"type":"integer","minimum":1,"maximum":1048576}
+            },
+            "required":["owner","repo","path"]
+        }),
+    };
+    let create_or_update_file = ToolDescriptor {
+        name: "create_or_update_file".into(),
+        annotations: None,
+        description: "Commit one text file to a branch; pass the current sha (from get_file_contents) to replace an existing file".into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties":{
+                "owner":{"type":"string"},
+                "repo":{"type":"string"},
+                "path":{"type":"string"},
+                "message":{"type":"string"},
+                "content":{"type":"string"},
+                "branch":{"type":"string"},
+                "sha":{"type":"string"}
+            },
+            "required":["owner","repo","path","message","content"]
+        }),
+    };
+    let get_license = ToolDescriptor {
+        name: "get_license".into(),
+        annotations: None,
+        description:
+            "Get the repository's detected license: SPDX id, name and decoded text (null when none)"
+                .into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties":{"owner":{"type":"string"},"repo":{"type":"string"}},
+            "required":["owner","repo"]
+        }),
+    };
+    let list_available_merge_methods = ToolDescriptor {
+        name: "list_available_merge_methods".into(),
+        annotations: None,
+        description: "Merge methods merge_pr may use in a repository, plus delete_branch_on_merge"
+            .into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties":{"owner":{"type":"string"},"repo":{"type":"string"}},
+            "required":["owner","repo"]
+        }),
+    };
     let merge_pr = ToolDescriptor {
         name: "merge_pr".into(),
+        annotations: None,
         description: "Merge a pull request (requires write permissions)".into(),
         input_schema: serde_json::json!({
             "type":"object","additionalProperties":false,
@@ -368,30 +1544,60 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
     };
     let search_issues = ToolDescriptor {
         name: "search_issues".into(),
-        description: "Search issues via GitHub Search API".into(),
+        annotations: None,
+        description: "Search issues and pull requests via GitHub Search API; structured qualifiers (state, label, author, assignee, milestone, created, updated, repo, org) are merged into q and the composed query is returned".into(),
         input_schema: serde_json::json!({"type":"object","additionalProperties":false,
-            "properties": {"q":{"type":"string"}, "sort":{"type":"string"}, "order":{"type":"string","enum":["asc","desc"]}, "cursor":{"type":"string"}, "limit":{"type":"integer"}},
-            "required":["q"]
+            "properties": {"q":{"type":"string"},
+                "state":{"type":"string","enum":["open","closed"]}, "label":{"type":"string"}, "author":{"type":"string"}, "assignee":{"type":"string"}, "milestone":{"type":"string"},
+                "created":{"type":"string"}, "updated":{"type":"string"}, "repo":{"type":"string"}, "org":{"type":"string"},
+                "sort":{"type":"string"}, "order":{"type":"string","enum":["asc","desc"]}, "cursor":{"type":"string"}, "limit":{"type":"integer"}}
         }),
     };
     let search_pull_requests = ToolDescriptor {
         name: "search_pull_requests".into(),
-        description: "Search pull requests via GitHub Search API".into(),
+        annotations: None,
+        description: "Search pull requests via GitHub Search API (adds is:pr unless q has an is:/type: issue or pr qualifier); structured qualifiers are merged into q as in search_issues and the composed query is returned".into(),
         input_schema: serde_json::json!({"type":"object","additionalProperties":false,
-            "properties": {"q":{"type":"string"}, "sort":{"type":"string"}, "order":{"type":"string","enum":["asc","desc"]}, "cursor":{"type":"string"}, "limit":{"type":"integer"}},
-            "required":["q"]
+            "properties": {"q":{"type":"string"},
+                "state":{"type":"string","enum":["open","closed"]}, "label":{"type":"string"}, "author":{"type":"string"}, "assignee":{"type":"string"}, "milestone":{"type":"string"},
+                "created":{"type":"string"}, "updated":{"type":"string"}, "repo":{"type":"string"}, "org":{"type":"string"},
+                "sort":{"type":"string"}, "order":{"type":"string","enum":["asc","desc"]}, "cursor":{"type":"string"}, "limit":{"type":"integer"}}
         }),
     };
     let search_repositories = ToolDescriptor {
         name: "search_repositories".into(),
-        description: "Search repositories via GitHub Search API".into(),
+        annotations: None,
+        description: "Search repositories via GitHub Search API; structured qualifiers (language, stars, pushed, topic, org, user) are merged into q and the composed query is returned".into(),
+        input_schema: serde_json::json!({"type":"object","additionalProperties":false,
+            "properties": {"q":{"type":"string"},
+                "language":{"type":"string"}, "stars":{"type":"string"}, "pushed":{"type":"string"}, "topic":{"type":"string"}, "org":{"type":"string"}, "user":{"type":"string"},
+                "sort":{"type":"string"}, "order":{"type":"string","enum":["asc","desc"]}, "cursor":{"type":"string"}, "limit":{"type":"integer"}}
+        }),
+    };
+    let list_pending_reviews = ToolDescriptor {
+        name: "list_pending_reviews".into(),
+        annotations: None,
+        description: "Open PRs awaiting review in a repo (owner+repo) or org, oldest first, with requested reviewers and age in days".into(),
         input_schema: serde_json::json!({"type":"object","additionalProperties":false,
-            "properties": {"q":{"type":"string"}, "sort":{"type":"string"}, "order":{"type":"string","enum":["asc","desc"]}, "cursor":{"type":"string"}, "limit":{"type":"integer"}},
-            "required":["q"]
+            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"org":{"type":"string"},
+                "reviewer":{"type":"string"},"include_drafts":{"type":"boolean"},"include_reviewers":{"type":"boolean"},
+                "cursor":{"type":"string"},"limit":{"type":"integer"}}
+        }),
+    };
+    let create_issue = ToolDescriptor {
+        name: "create_issue".into(),
+        annotations: None,
+        description: "Open a new issue with optional body, labels, assignees and milestone".into(),
+        input_schema: serde_json::json!({"type":"object","additionalProperties":false,
+            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},
+                "title":{"type":"string"},"body":{"type":"string"},"labels":{"type":"array","items":{"type":"string"}},
+                "assignees":{"type":"array","items":{"type":"string"}},"milestone":{"type":"integer"}},
+            "required":["owner","repo","title"]
         }),
     };
     let update_issue = ToolDescriptor {
         name: "update_issue".into(),
+        annotations: None,
         description: "Update an issue: title/body/labels/assignees/state/milestone".into(),
         input_schema: serde_json::json!({"type":"object","additionalProperties":false,
             "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"number":{"type":"integer"},
@@ -400,8 +1606,29 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
             "required":["owner","repo","number"]
         }),
     };
+    let add_issue_comment = ToolDescriptor {
+        name: "add_issue_comment".into(),
+        annotations: None,
+        description: "Post a comment on an issue or pull request conversation".into(),
+        input_schema: serde_json::json!({"type":"object","additionalProperties":false,
+            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"number":{"type":"integer"},"body":{"type":"string"}},
+            "required":["owner","repo","number","body"]
+        }),
+    };
+    let create_pull_request = ToolDescriptor {
+        name: "create_pull_request".into(),
+        annotations: None,
+        description: "Open a pull request from head into base; returns number, state and html_url"
+            .into(),
+        input_schema: serde_json::json!({"type":"object","additionalProperties":false,
+            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"title":{"type":"string"},"head":{"type":"string"},"base":{"type":"string"},
+                "body":{"type":"string"},"draft":{"type":"boolean"},"maintainer_can_modify":{"type":"boolean"}},
+            "required":["owner","repo","title","head","base"]
+        }),
+    };
     let update_pull_request = ToolDescriptor {
         name: "update_pull_request".into(),
+        annotations: None,
         description: "Update a pull request: title/body/state/base/maintainer_can_modify".into(),
         input_schema: serde_json::json!({"type":"object","additionalProperties":false,
             "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"number":{"type":"integer"},
@@ -409,8 +1636,19 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
             "required":["owner","repo","number"]
         }),
     };
+    let close_pull_request = ToolDescriptor {
+        name: "close_pull_request".into(),
+        annotations: None,
+        description: "Close a pull request; delete_branch also removes its head branch unless it lives in a fork".into(),
+        input_schema: serde_json::json!({"type":"object","additionalProperties":false,
+            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"number":{"type":"integer"},
+                "delete_branch":{"type":"boolean"}},
+            "required":["owner","repo","number"]
+        }),
+    };
     let fork_repository = ToolDescriptor {
         name: "fork_repository".into(),
+        annotations: None,
         description: "Fork a repository to the authenticated user or an organization".into(),
         input_schema: serde_json::json!({"type":"object","additionalProperties":false,
             "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"organization":{"type":"string"}},
@@ -418,9 +1656,54 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
         }),
     };
 
+    let get_workflow = ToolDescriptor {
+        name: "get_workflow".into(),
+        annotations: None,
+        description: "Get a single workflow by numeric id or file name (e.g. ci.yml)".into(),
+        input_schema: serde_json::json!({"type":"object","additionalProperties":false,
+            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},
+                "workflow_id":{"type":["integer","string"]}},
+            "required":["owner","repo","workflow_id"]
+        }),
+    };
+
+    let dispatch_workflow = ToolDescriptor {
+        name: "dispatch_workflow".into(),
+        annotations: None,
+        description: "Trigger a workflow_dispatch event for a workflow (numeric id or file name) on a ref; GitHub returns no run id".into(),
+        input_schema: serde_json::json!({"type":"object","additionalProperties":false,
+            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},
+                "workflow_id":{"type":["integer","string"]},"ref":{"type":"string"},"inputs":{"type":"object"}},
+            "required":["owner","repo","workflow_id","ref"]
+        }),
+    };
+
+    let wait_for_workflow_run = ToolDescriptor {
+        name: "wait_for_workflow_run".into(),
+        annotations: None,
+        description: "Poll a workflow run until it completes or timeout_secs (default 300, max 1800) elapses; reports timed_out".into(),
+        input_schema: serde_json::json!({"type":"object","additionalProperties":false,
+            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"run_id":{"type":"integer"},
+                "timeout_secs":{"type":"integer","minimum":1,"maximum":1800}},
+            "required":["owner","repo","run_id"]
+        }),
+    };
+
+    let wait_for_checks = ToolDescriptor {
+        name: "wait_for_checks".into(),
+        annotations: None,
+        description: "Poll the status-check rollup of a pull request (number) or commit (ref) until nothing is pending or timeout_secs (default 300, max 1800) elapses".into(),
+        input_schema: serde_json::json!({"type":"object","additionalProperties":false,
+            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"number":{"type":"integer"},"ref":{"type":"string"},
+                "timeout_secs":{"type":"integer","minimum":1,"maximum":1800}},
+            "required":["owner","repo"]
+        }),
+    };
+
     // Secrets, Variables, Environments (REST light)
     let list_repo_secrets_light = ToolDescriptor {
         name: "list_repo_secrets_light".into(),
+        annotations: None,
         description: "List repository Actions secrets (metadata only)".into(),
         input_schema: serde_json::json!({
             "type":"object","additionalProperties":false,
@@ -436,6 +1719,7 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
     };
     let list_repo_variables_light = ToolDescriptor {
         name: "list_repo_variables_light".into(),
+        annotations: None,
         description: "List repository Actions variables (may include values)".into(),
         input_schema: serde_json::json!({
             "type":"object","additionalProperties":false,
@@ -451,6 +1735,7 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
     };
     let list_environments_light = ToolDescriptor {
         name: "list_environments_light".into(),
+        annotations: None,
         description: "List repository environments (light)".into(),
         input_schema: serde_json::json!({
             "type":"object","additionalProperties":false,
@@ -466,6 +1751,7 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
     };
     let list_environment_variables_light = ToolDescriptor {
         name: "list_environment_variables_light".into(),
+        annotations: None,
         description: "List environment-scoped Actions variables (may include values)".into(),
         input_schema: serde_json::json!({
             "type":"object","additionalProperties":false,
@@ -480,81 +1766,678 @@ pub fn tool_descriptors() -> Vec<ToolDescriptor> {
             "required":["owner","repo","environment_name"]
         }),
     };
+    let get_environment = ToolDescriptor {
+        name: "get_environment".into(),
+        annotations: None,
+        description:
+            "Get a deployment environment's protection rules (wait timer, reviewers, branch policy)"
+                .into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties":{
+                "owner":{"type":"string"},
+                "repo":{"type":"string"},
+                "environment_name":{"type":"string"}
+            },
+            "required":["owner","repo","environment_name"]
+        }),
+    };
+    let list_deploy_keys = ToolDescriptor {
+        name: "list_deploy_keys".into(),
+        annotations: None,
+        description: "List repository deploy keys (public key, read_only, created_at)".into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties":{
+                "owner":{"type":"string"},
+                "repo":{"type":"string"},
+                "cursor":{"type":"string"},
+                "limit":{"type":"integer"}
+            },
+            "required":["owner","repo"]
+        }),
+    };
+    let list_org_audit_log = ToolDescriptor {
+        name: "list_org_audit_log".into(),
+        annotations: None,
+        description: "List organization audit log events (requires an org owner token)".into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties":{
+                "org":{"type":"string"},
+                "phrase":{"type":"string"},
+                "include":{"type":"string","enum":["web","git","all"]},
+                "cursor":{"type":"string"},
+                "limit":{"type":"integer"}
+            },
+            "required":["org"]
+        }),
+    };
 
-    vec![
-        ping,
-        list_issues,
-        get_issue,
-        list_issue_comments_plain,
-        list_prs,
-        get_pr,
-        list_pr_comments,
-        list_pr_review_comments,
-        list_pr_review_comments_unified,
-        list_pr_review_threads,
-        resolve_thread,
-        unresolve_thread,
-        list_pr_reviews,
-        list_pr_reviews_unified,
-        list_pr_commits,
-        list_pr_commits_unified,
-        list_pr_files,
-        list_pr_files_unified,
-        get_pr_diff,
-        get_pr_patch,
-        pr_summary,
-        list_repo_secrets_light,
-        list_repo_variables_light,
-        list_environments_light,
-        list_environment_variables_light,
-        // New methods
-        list_commits,
-        get_commit,
-        list_tags,
-        get_tag,
-        list_branches,
-        list_releases,
-        get_release,
-        list_starred_repositories,
-        merge_pr,
-        search_issues,
-        search_pull_requests,
-        search_repositories,
-        update_issue,
-        update_pull_request,
-        fork_repository,
-    ]
-}
+    let repo_overview = ToolDescriptor {
+        name: "repo_overview".into(),
+        annotations: None,
+        description:
+            "Repository overview: metadata, open issue/PR counts, latest release, languages".into(),
+        input_schema: serde_json::json!({"type":"object","additionalProperties":false,
+            "properties": {"owner":{"type":"string"},"repo":{"type":"string"}},
+            "required":["owner","repo"]
+        }),
+    };
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct PingInput {
-    pub message: Option<String>,
-}
+    let get_issues = ToolDescriptor {
+        name: "get_issues".into(),
+        annotations: None,
+        description: "Get several issues by number (max 100, fetched in chunks of 20)".into(),
+        input_schema: serde_json::json!({"type":"object","additionalProperties":false,
+            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},
+                "numbers":{"type":"array","items":{"type":"integer"},"minItems":1,"maxItems":100},
+                "include_author":{"type":"boolean"}},
+            "required":["owner","repo","numbers"]
+        }),
+    };
+    let get_pull_requests = ToolDescriptor {
+        name: "get_pull_requests".into(),
+        annotations: None,
+        description: "Get several pull requests by number (max 100, fetched in chunks of 20)"
+            .into(),
+        input_schema: serde_json::json!({"type":"object","additionalProperties":false,
+            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},
+                "numbers":{"type":"array","items":{"type":"integer"},"minItems":1,"maxItems":100},
+                "include_author":{"type":"boolean"}},
+            "required":["owner","repo","numbers"]
+        }),
+    };
+    let resolve_ref = ToolDescriptor {
+        name: "resolve_ref".into(),
+        annotations: None,
+        description: "Resolve a branch, tag, or sha to a commit sha and report the ref type".into(),
+        input_schema: serde_json::json!({"type":"object","additionalProperties":false,
+            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"ref":{"type":"string"}},
+            "required":["owner","repo","ref"]
+        }),
+    };
+    let list_prs_for_commit = ToolDescriptor {
+        name: "list_prs_for_commit".into(),
+        annotations: None,
+        description: "List pull requests associated with a commit".into(),
+        input_schema: serde_json::json!({"type":"object","additionalProperties":false,
+            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"sha":{"type":"string"},"cursor":{"type":"string"},"limit":{"type":"integer"}},
+            "required":["owner","repo","sha"]
+        }),
+    };
+    let list_branches_for_commit = ToolDescriptor {
+        name: "list_branches_for_commit".into(),
+        annotations: None,
+        description: "List branches whose head is the given commit".into(),
+        input_schema: serde_json::json!({"type":"object","additionalProperties":false,
+            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"sha":{"type":"string"}},
+            "required":["owner","repo","sha"]
+        }),
+    };
+    let list_commit_statuses = ToolDescriptor {
+        name: "list_commit_statuses".into(),
+        annotations: None,
+        description: "List legacy commit statuses for a ref (newest first)".into(),
+        input_schema: serde_json::json!({"type":"object","additionalProperties":false,
+            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},"ref":{"type":"string"},"cursor":{"type":"string"},"limit":{"type":"integer"}},
+            "required":["owner","repo","ref"]
+        }),
+    };
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct PingOutput {
-    pub message: String,
-}
+    let reply_to_review_comment = ToolDescriptor {
+        name: "reply_to_review_comment".into(),
+        annotations: None,
+        description: "Reply to a PR review comment thread".into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties":{
+                "owner":{"type":"string"},
+                "repo":{"type":"string"},
+                "number":{"type":"integer"},
+                "comment_id":{"type":"integer"},
+                "body":{"type":"string"}
+            },
+            "required":["owner","repo","number","comment_id","body"]
+        }),
+    };
 
-// Shared result meta and error shapes used across tools.
-// RateMeta lives in types.rs; use the shared definition to avoid duplication.
+    let create_review_comment = ToolDescriptor {
+        name: "create_review_comment".into(),
+        annotations: None,
+        description: "Create an inline PR review comment on a diff line or range".into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties":{
+                "owner":{"type":"string"},
+                "repo":{"type":"string"},
+                "number":{"type":"integer"},
+                "body":{"type":"string"},
+                "commit_id":{"type":"string"},
+                "path":{"type":"string"},
+                "line":{"type":"integer"},
+                "start_line":{"type":"integer"},
+                "side":{"type":"string","enum":["LEFT","RIGHT","left","right"]},
+                "start_side":{"type":"string","enum":["LEFT","RIGHT","left","right"]}
+            },
+            "required":["owner","repo","number","body","commit_id","path","line"]
+        }),
+    };
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
-pub struct Meta {
-    pub next_cursor: Option<String>,
-    pub has_more: bool,
-    pub rate: Option<RateMeta>,
-}
+    let update_comment = ToolDescriptor {
+        name: "update_comment".into(),
+        annotations: None,
+        description: "Edit an issue/PR conversation comment or a review comment".into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties":{
+                "owner":{"type":"string"},
+                "repo":{"type":"string"},
+                "comment_id":{"type":"integer"},
+                "comment_type":{"type":"string","enum":["issue","review"]},
+                "body":{"type":"string"}
+            },
+            "required":["owner","repo","comment_id","body"]
+        }),
+    };
+    let delete_comment = ToolDescriptor {
+        name: "delete_comment".into(),
+        annotations: None,
+        description: "Delete an issue/PR conversation comment or a review comment".into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties":{
+                "owner":{"type":"string"},
+                "repo":{"type":"string"},
+                "comment_id":{"type":"integer"},
+                "comment_type":{"type":"string","enum":["issue","review"]}
+            },
+            "required":["owner","repo","comment_id"]
+        }),
+    };
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
-pub struct ErrorShape {
-    pub code: String,
-    pub message: String,
+    let download_release_asset = ToolDescriptor {
+        name: "download_release_asset".into(),
+        annotations: None,
+        description: "Download a release asset as base64 (capped by max_bytes)".into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties":{
+                "owner":{"type":"string"},
+                "repo":{"type":"string"},
+                "asset_id":{"type":"integer"},
+                "max_bytes":{"type":"integer","minimum":1,"maximum":6288384}
+            },
+            "required":["owner","repo","asset_id"]
+        }),
+    };
+
+    let upload_release_asset = ToolDescriptor {
+        name: "upload_release_asset".into(),
+        annotations: None,
+        description: "Upload a base64-encoded file as a release asset".into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties":{
+                "owner":{"type":"string"},
+                "repo":{"type":"string"},
+                "release_id":{"type":"integer"},
+                "name":{"type":"string"},
+                "label":{"type":"string"},
+                "content_type":{"type":"string"},
+                "content":{"type":"string","description":"base64-encoded file bytes"}
+            },
+            "required":["owner","repo","release_id","name","content"]
+        }),
+    };
+
+    let find_files = ToolDescriptor {
+        name: "find_files".into(),
+        annotations: None,
+        description: "Find file paths in a repository tree by glob or substring".into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties":{
+                "owner":{"type":"string"},
+                "repo":{"type":"string"},
+                "ref":{"type":"string","description":"branch, tag or sha; default HEAD"},
+                "pattern":{"type":"string","description":"glob: * within a segment, ** across segments, ?; without '/' matches file names"},
+                "query":{"type":"string","description":"case-insensitive substring of the path"},
+                "max_results":{"type":"integer","minimum":1,"maximum":1000}
+            },
+            "required":["owner","repo"]
+        }),
+    };
+
+    let set_repo_subscription = ToolDescriptor {
+        name: "set_repo_subscription".into(),
+        annotations: None,
+        description: "Watch, unwatch or ignore a repository's notifications".into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties":{
+                "owner":{"type":"string"},
+                "repo":{"type":"string"},
+                "subscribed":{"type":"boolean"},
+                "ignored":{"type":"boolean"}
+            },
+            "required":["owner","repo"]
+        }),
+    };
+    let delete_repo_subscription = ToolDescriptor {
+        name: "delete_repo_subscription".into(),
+        annotations: None,
+        description: "Remove the repository subscription (back to default notifications)".into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties":{ "owner":{"type":"string"}, "repo":{"type":"string"} },
+            "required":["owner","repo"]
+        }),
+    };
+
+    // Gists
+    let update_gist = ToolDescriptor {
+        name: "update_gist".into(),
+        annotations: None,
+        description: "Update a gist's description and files; a null file entry deletes that file"
+            .into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties":{
+                "gist_id":{"type":"string"},
+                "description":{"type":"string"},
+                "files":{"type":"object","additionalProperties":{
+                    "type":["object","null"],"additionalProperties":false,
+                    "properties":{"content":{"type":"string"},"filename":{"type":"string"}}
+                }}
+            },
+            "required":["gist_id"]
+        }),
+    };
+    let gist_id_schema = serde_json::json!({
+        "type":"object","additionalProperties":false,
+        "properties":{ "gist_id":{"type":"string"} },
+        "required":["gist_id"]
+    });
+    let delete_gist = ToolDescriptor {
+        name: "delete_gist".into(),
+        annotations: None,
+        description: "Delete a gist".into(),
+        input_schema: gist_id_schema.clone(),
+    };
+    let star_gist = ToolDescriptor {
+        name: "star_gist".into(),
+        annotations: None,
+        description: "Star a gist".into(),
+        input_schema: gist_id_schema.clone(),
+    };
+    let unstar_gist = ToolDescriptor {
+        name: "unstar_gist".into(),
+        annotations: None,
+        description: "Unstar a gist".into(),
+        input_schema: gist_id_schema,
+    };
+
+    let list_issues_for_user = ToolDescriptor {
+        name: "list_issues_for_user".into(),
+        annotations: None,
+        description: "List issues across all repositories for the authenticated user (dashboard)"
+            .into(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "filter": {"type": "string", "enum": ["assigned", "created", "mentioned", "subscribed", "repos", "all"]},
+                "state": {"type": "string", "enum": ["open", "closed", "all"]},
+                "labels": {"type": "array", "items": {"type": "string"}},
+                "since": {"type": "string"},
+                "sort": {"type": "string", "enum": ["created", "updated", "comments"]},
+                "direction": {"type": "string", "enum": ["asc", "desc"]},
+                "owned_only": {"type": "boolean"},
+                "cursor": {"type": "string"},
+                "limit": {"type": "integer"},
+                "include_author": {"type": "boolean"},
+                "include_body": {"type": "boolean"},
+                "body_max_chars": {"type": "integer", "minimum": 1}
+            }
+        }),
+    };
+
+    let list_milestone_issues = ToolDescriptor {
+        name: "list_milestone_issues".into(),
+        annotations: None,
+        description: "List issues and pull requests in a milestone, with milestone progress".into(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "owner": {"type": "string"},
+                "repo": {"type": "string"},
+                "milestone": {"type": "integer", "minimum": 1},
+                "state": {"type": "string", "enum": ["open", "closed", "all"]},
+                "include_pull_requests": {"type": "boolean"},
+                "cursor": {"type": "string"},
+                "limit": {"type": "integer"},
+                "include_author": {"type": "boolean"},
+                "include_body": {"type": "boolean"},
+                "body_max_chars": {"type": "integer", "minimum": 1}
+            },
+            "required": ["owner", "repo", "milestone"]
+        }),
+    };
+
+    // Escape hatch; only listed when GITHUB_MCP_ENABLE_RAW_GRAPHQL is set.
+    let list_capabilities = ToolDescriptor {
+        name: "list_capabilities".into(),
+        annotations: None,
+        description: "List all tools grouped by category with token permission hints, whether they mutate, and whether they are enabled".into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties":{"category":{"type":"string"}}
+        }),
+    };
+    let batch = ToolDescriptor {
+        name: "batch".into(),
+        annotations: None,
+        description: "Run up to 20 tool calls in order and return each result; every sub-call is gated like a direct call".into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties":{
+                "calls":{
+                    "type":"array","minItems":1,"maxItems":BATCH_MAX_CALLS,
+                    "items":{
+                        "type":"object","additionalProperties":false,
+                        "properties":{"name":{"type":"string"},"arguments":{"type":"object"}},
+                        "required":["name"]
+                    }
+                }
+            },
+            "required":["calls"]
+        }),
+    };
+    let get_rate_limit = ToolDescriptor {
+        name: "get_rate_limit".into(),
+        annotations: None,
+        description: "Current rate-limit budget (limit, remaining, used, reset_at) for the core, graphql, search and code_search resources; checking it does not count against the limit".into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties":{}
+        }),
+    };
+    let graphql_query = ToolDescriptor {
+        name: "graphql_query".into(),
+        annotations: None,
+        description: "Run a raw GraphQL query and return data/errors as-is (opt-in)".into(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "query": {"type": "string"},
+                "variables": {"type": "object"}
+            },
+            "required": ["query"]
+        }),
+    };
+
+    // Escape hatch; only listed when GITHUB_MCP_ENABLE_RAW_REST is set.
+    let rest_request = ToolDescriptor {
+        name: "rest_request".into(),
+        annotations: None,
+        description:
+            "Call a REST endpoint by path under the API base and return status + JSON (opt-in)"
+                .into(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "method": {"type": "string", "enum": ["GET", "POST", "PUT", "PATCH", "DELETE"]},
+                "path": {"type": "string", "description": "Path relative to the API base, e.g. /repos/o/r/topics"},
+                "body": {},
+                "accept": {"type": "string"}
+            },
+            "required": ["method", "path"]
+        }),
+    };
+
+    let repo_activity_digest = ToolDescriptor {
+        name: "repo_activity_digest".into(),
+        annotations: None,
+        description: "Counts of new issues, new PRs, merged PRs and releases in a date window"
+            .into(),
+        input_schema: serde_json::json!({"type":"object","additionalProperties":false,
+            "properties": {"owner":{"type":"string"},"repo":{"type":"string"},
+                "since":{"type":"string","description":"YYYY-MM-DD or RFC3339"},
+                "until":{"type":"string","description":"YYYY-MM-DD or RFC3339; open-ended when omitted"}},
+            "required":["owner","repo","since"]
+        }),
+    };
+
+    let get_item = ToolDescriptor {
+        name: "get_item".into(),
+        annotations: None,
+        description:
+            "Get an issue or pull request by number when the kind is unknown; returns type".into(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "owner": {"type": "string"},
+                "repo": {"type": "string"},
+                "number": {"type": "integer"},
+                "include_author": {"type": "boolean"}
+            },
+            "required": ["owner", "repo", "number"]
+        }),
+    };
+
+    let update_repository = ToolDescriptor {
+        name: "update_repository".into(),
+        annotations: None,
+        description: "Update repository settings (description, visibility, default branch, features, archive)".into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties":{
+                "owner":{"type":"string"}, "repo":{"type":"string"},
+                "name":{"type":"string"},
+                "description":{"type":"string"},
+                "homepage":{"type":"string"},
+                "private":{"type":"boolean"},
+                "visibility":{"type":"string","enum":["public","private","internal"]},
+                "archived":{"type":"boolean"},
+                "confirm_archive":{"type":"boolean","description":"Required with archived=true; archiving cannot be undone via the API"},
+                "default_branch":{"type":"string"},
+                "has_issues":{"type":"boolean"},
+                "has_projects":{"type":"boolean"},
+                "has_wiki":{"type":"boolean"},
+                "has_discussions":{"type":"boolean"}
+            },
+            "required":["owner","repo"]
+        }),
+    };
+
+    let sync_labels = ToolDescriptor {
+        name: "sync_labels".into(),
+        annotations: None,
+        description: "Reconcile repository labels with a desired set (create/update, optionally prune extras)".into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties":{
+                "owner":{"type":"string"}, "repo":{"type":"string"},
+                "labels":{"type":"array","items":{
+                    "type":"object","additionalProperties":false,
+                    "properties":{
+                        "name":{"type":"string"},
+                        "color":{"type":"string","description":"6-digit hex, with or without leading #"},
+                        "description":{"type":"string"}
+                    },
+                    "required":["name","color"]
+                }},
+                "prune":{"type":"boolean"},
+                "dry_run":{"type":"boolean"}
+            },
+            "required":["owner","repo","labels"]
+        }),
+    };
+
+    let find_pull_request = ToolDescriptor {
+        name: "find_pull_request".into(),
+        annotations: None,
+        description: "Find the open pull request for a head branch (null if none)".into(),
+        input_schema: serde_json::json!({
+            "type":"object","additionalProperties":false,
+            "properties":{
+                "owner":{"type":"string"}, "repo":{"type":"string"},
+                "head":{"type":"string","description":"Branch name, or user:branch for forks"},
+                "base":{"type":"string"},
+                "include_author":{"type":"boolean"}
+            },
+            "required":["owner","repo","head"]
+        }),
+    };
+
+    let mut tools = vec![
+        ping,
+        list_capabilities,
+        batch,
+        get_rate_limit,
+        list_issues,
+        get_issue,
+        list_issue_comments_plain,
+        list_prs,
+        get_pr,
+        list_pr_comments,
+        list_pr_review_comments,
+        list_pr_review_comments_unified,
+        list_pr_review_threads,
+        resolve_thread,
+        unresolve_thread,
+        list_pr_reviews,
+        list_pr_reviews_unified,
+        get_pr_review,
+        list_pr_commits,
+        list_pr_commits_unified,
+        list_pr_files,
+        list_pr_files_unified,
+        get_pr_diff,
+        get_pr_patch,
+        pr_summary,
+        get_workflow,
+        dispatch_workflow,
+        wait_for_workflow_run,
+        wait_for_checks,
+        list_repo_secrets_light,
+        list_repo_variables_light,
+        list_environments_light,
+        list_environment_variables_light,
+        get_environment,
+        list_deploy_keys,
+        list_org_audit_log,
+        // New methods
+        list_commits,
+        get_commit,
+        list_tags,
+        get_tag,
+        list_branches,
+        list_releases,
+        get_release,
+        list_starred_repositories,
+        list_watched_repositories,
+        get_repo,
+        get_license,
+        get_file_contents,
+        create_or_update_file,
+        list_available_merge_methods,
+        merge_pr,
+        search_issues,
+        search_pull_requests,
+        search_repositories,
+        list_pending_reviews,
+        create_issue,
+        update_issue,
+        add_issue_comment,
+        create_pull_request,
+        update_pull_request,
+        close_pull_request,
+        fork_repository,
+        repo_overview,
+        get_issues,
+        get_pull_requests,
+        resolve_ref,
+        list_prs_for_commit,
+        list_branches_for_commit,
+        list_commit_statuses,
+        reply_to_review_comment,
+        create_review_comment,
+        update_comment,
+        delete_comment,
+        download_release_asset,
+        upload_release_asset,
+        find_files,
+        set_repo_subscription,
+        delete_repo_subscription,
+        update_gist,
+        delete_gist,
+        star_gist,
+        unstar_gist,
+        list_issues_for_user,
+        update_repository,
+        sync_labels,
+        find_pull_request,
+        list_milestone_issues,
+        graphql_query,
+        rest_request,
+        repo_activity_digest,
+        get_item,
+    ];
+    for t in &mut tools {
+        t.annotations = Some(annotations_for(&t.name));
+        document_properties(&t.name, &mut t.input_schema);
+    }
+    tools
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PingInput {
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PingOutput {
+    pub message: String,
+}
+
+// Shared result meta and error shapes used across tools.
+// RateMeta lives in types.rs; use the shared definition to avoid duplication.
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct Meta {
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+    pub rate: Option<RateMeta>,
+    // Explicit REST page position (1-based); only emitted with `_include_pages`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per_page: Option<u32>,
+    // From the Link header's rel="last"; equals `page` on the final page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_pages: Option<u32>,
+    // High-watermark for incremental syncs (list_issues `updated_since`): the newest
+    // updated_at seen so far, to be passed back as `updated_since` on the next run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_updated_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ErrorShape {
+    pub code: ErrorCode,
+    pub message: String,
     pub retriable: bool,
 }
 
 // Issues tool inputs
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ListIssuesInput {
     pub owner: String,
     pub repo: String,
@@ -564,11 +2447,16 @@ pub struct ListIssuesInput {
     pub assignee: Option<String>,
     pub mentions: Option<String>,
     pub since: Option<String>,
+    // Incremental-sync mode: implies since, sort=updated, direction=asc.
+    pub updated_since: Option<String>,
     pub sort: Option<String>,
     pub direction: Option<String>,
     pub cursor: Option<String>,
     pub limit: Option<u32>,
     pub include_author: Option<bool>,
+    pub include_body: Option<bool>,
+    pub body_max_chars: Option<usize>,
+    pub include_counts: Option<bool>,
 }
 
 #[derive(Debug, Serialize)]
@@ -579,19 +2467,39 @@ pub struct ListIssuesOutputItem {
     pub state: String,
     pub created_at: String,
     pub updated_at: String,
+    // Web URL for people; absent only when upstream omits it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub html_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub author_login: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    // Only with include_counts: totals without fetching the label/assignee lists.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels_count: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignees_count: Option<i64>,
+    // "owner/name"; only list_issues_for_user, whose items span repositories, sets it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct ListIssuesOutput {
     pub items: Option<Vec<ListIssuesOutputItem>>,
     pub meta: Meta,
+    // Set to "search" when the request was served by the Search API (assignee "none"/"*").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backend: Option<String>,
+    // GraphQL returned the list but reported errors for some fields; items may be incomplete.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partial_error: Option<ErrorShape>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<ErrorShape>,
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct GetIssueInput {
     pub owner: String,
     pub repo: String,
@@ -610,6 +2518,8 @@ pub struct GetIssueOutputItem {
     pub created_at: String,
     pub updated_at: String,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub html_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub author_login: Option<String>,
 }
 
@@ -622,6 +2532,7 @@ pub struct GetIssueOutput {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ListIssueCommentsInput {
     pub owner: String,
     pub repo: String,
@@ -649,8 +2560,34 @@ pub struct ListIssueCommentsOutput {
     pub error: Option<ErrorShape>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AddIssueCommentInput {
+    pub owner: String,
+    pub repo: String,
+    pub number: i64,
+    pub body: String,
+}
+#[derive(Debug, Serialize)]
+pub struct AddedIssueCommentItem {
+    // REST id; what update_comment and delete_comment take as comment_id.
+    pub id: i64,
+    pub body: String,
+    pub created_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author_login: Option<String>,
+}
+#[derive(Debug, Serialize)]
+pub struct AddIssueCommentOutput {
+    pub item: Option<AddedIssueCommentItem>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
 // PR inputs/outputs
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ListPullRequestsInput {
     pub owner: String,
     pub repo: String,
@@ -660,6 +2597,8 @@ pub struct ListPullRequestsInput {
     pub cursor: Option<String>,
     pub limit: Option<u32>,
     pub include_author: Option<bool>,
+    pub include_body: Option<bool>,
+    pub body_max_chars: Option<usize>,
 }
 
 #[derive(Debug, Serialize)]
@@ -671,7 +2610,11 @@ pub struct ListPullRequestsItem {
     pub created_at: String,
     pub updated_at: String,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub html_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub author_login: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -683,6 +2626,7 @@ pub struct ListPullRequestsOutput {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct GetPullRequestInput {
     pub owner: String,
     pub repo: String,
@@ -701,6 +2645,8 @@ pub struct GetPullRequestItem {
     pub is_draft: bool,
     pub created_at: String,
     pub updated_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub html_url: Option<String>,
     pub merged: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub merged_at: Option<String>,
@@ -717,6 +2663,7 @@ pub struct GetPullRequestOutput {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ListPrCommentsInput {
     pub owner: String,
     pub repo: String,
@@ -724,6 +2671,7 @@ pub struct ListPrCommentsInput {
     pub cursor: Option<String>,
     pub limit: Option<u32>,
     pub include_author: Option<bool>,
+    pub since: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -745,6 +2693,7 @@ pub struct ListPrCommentsOutput {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ListPrReviewCommentsInput {
     pub owner: String,
     pub repo: String,
@@ -753,6 +2702,7 @@ pub struct ListPrReviewCommentsInput {
     pub limit: Option<u32>,
     pub include_author: Option<bool>,
     pub include_location: Option<bool>,
+    pub since: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -783,6 +2733,13 @@ pub struct ReviewCommentItem {
     pub commit_sha: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub original_commit_sha: Option<String>,
+    // Threading: numeric REST ids, so replies can be matched to their parent via in_reply_to_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub in_reply_to_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pull_request_review_id: Option<i64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -794,6 +2751,7 @@ pub struct ListPrReviewCommentsOutput {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ListPrReviewThreadsInput {
     pub owner: String,
     pub repo: String,
@@ -833,13 +2791,19 @@ pub struct ListPrReviewThreadsOutput {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ResolveThreadInput {
-    pub thread_id: String,
+    // Either the thread node id, or owner/repo/number plus the id of a review comment in it.
+    pub thread_id: Option<String>,
+    pub owner: Option<String>,
+    pub repo: Option<String>,
+    pub number: Option<i64>,
+    pub comment_id: Option<i64>,
 }
 #[derive(Debug, Serialize)]
 pub struct ResolveThreadOutput {
     pub ok: bool,
-    pub thread_id: String,
+    pub thread_id: Option<String>,
     pub is_resolved: bool,
     pub meta: Meta,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -847,6 +2811,7 @@ pub struct ResolveThreadOutput {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ListPrReviewsInput {
     pub owner: String,
     pub repo: String,
@@ -854,15 +2819,22 @@ pub struct ListPrReviewsInput {
     pub cursor: Option<String>,
     pub limit: Option<u32>,
     pub include_author: Option<bool>,
+    pub include_body: Option<bool>,
+    pub body_max_chars: Option<usize>,
 }
 #[derive(Debug, Serialize)]
 pub struct PrReviewItem {
     pub id: String,
+    // Numeric REST id; what get_pr_review takes as review_id.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database_id: Option<i64>,
     pub state: String,
     pub submitted_at: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub author_login: Option<String>,
-}
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
 #[derive(Debug, Serialize)]
 pub struct ListPrReviewsOutput {
     pub items: Option<Vec<PrReviewItem>>,
@@ -872,6 +2844,43 @@ pub struct ListPrReviewsOutput {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GetPrReviewInput {
+    pub owner: String,
+    pub repo: String,
+    pub number: i64,
+    pub review_id: i64,
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+    pub include_location: Option<bool>,
+}
+#[derive(Debug, Serialize)]
+pub struct PrReviewDetailItem {
+    pub id: String,
+    pub database_id: i64,
+    pub state: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author_login: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    pub submitted_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_sha: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub html_url: Option<String>,
+}
+// `item` is the review; `items` are its inline comments, paginated via meta.
+#[derive(Debug, Serialize)]
+pub struct GetPrReviewOutput {
+    pub item: Option<PrReviewDetailItem>,
+    pub items: Option<Vec<ReviewCommentItem>>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ListPrCommitsInput {
     pub owner: String,
     pub repo: String,
@@ -879,6 +2888,7 @@ pub struct ListPrCommitsInput {
     pub cursor: Option<String>,
     pub limit: Option<u32>,
     pub include_author: Option<bool>,
+    pub sha_length: Option<String>,
 }
 #[derive(Debug, Serialize)]
 pub struct PrCommitItem {
@@ -886,6 +2896,8 @@ pub struct PrCommitItem {
     pub title: String,
     pub authored_at: String,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub html_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub author_login: Option<String>,
 }
 #[derive(Debug, Serialize)]
@@ -897,6 +2909,7 @@ pub struct ListPrCommitsOutput {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ListPrFilesInput {
     pub owner: String,
     pub repo: String,
@@ -925,10 +2938,13 @@ pub struct ListPrFilesOutput {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct GetPrTextInput {
     pub owner: String,
     pub repo: String,
     pub number: i64,
+    // get_pr_diff only: keep just this file's section of the diff
+    pub path: Option<String>,
 }
 #[derive(Debug, Serialize)]
 pub struct GetPrTextOutput {
@@ -941,6 +2957,7 @@ pub struct GetPrTextOutput {
 
 // Actions / Workflows (REST) inputs/outputs
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ListWorkflowsInput {
     pub owner: String,
     pub repo: String,
@@ -962,7 +2979,58 @@ pub struct ListWorkflowsOutput {
     pub error: Option<ErrorShape>,
 }
 
+// Workflows are addressable by numeric id or by file name.
 #[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum WorkflowRef {
+    Id(i64),
+    File(String),
+}
+impl WorkflowRef {
+    pub fn path_segment(&self) -> String {
+        match self {
+            WorkflowRef::Id(id) => id.to_string(),
+            WorkflowRef::File(name) => crate::http::encode_path_segment(name),
+        }
+    }
+}
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GetWorkflowInput {
+    pub owner: String,
+    pub repo: String,
+    pub workflow_id: WorkflowRef,
+}
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DispatchWorkflowInput {
+    pub owner: String,
+    pub repo: String,
+    pub workflow_id: WorkflowRef,
+    pub r#ref: String,
+    pub inputs: Option<serde_json::Map<String, serde_json::Value>>,
+}
+#[derive(Debug, Serialize)]
+pub struct WorkflowDetailItem {
+    pub id: i64,
+    pub name: String,
+    pub path: String,
+    pub state: String,
+    pub created_at: String,
+    pub updated_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub badge_url: Option<String>,
+}
+#[derive(Debug, Serialize)]
+pub struct GetWorkflowOutput {
+    pub item: Option<WorkflowDetailItem>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ListWorkflowRunsInput {
     pub owner: String,
     pub repo: String,
@@ -989,6 +3057,7 @@ pub struct ListWorkflowRunsOutput {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct GetWorkflowRunInput {
     pub owner: String,
     pub repo: String,
@@ -1004,6 +3073,59 @@ pub struct GetWorkflowRunOutput {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WaitForWorkflowRunInput {
+    pub owner: String,
+    pub repo: String,
+    pub run_id: i64,
+    pub timeout_secs: Option<u64>,
+}
+#[derive(Debug, Serialize)]
+pub struct WaitForWorkflowRunOutput {
+    pub item: Option<WorkflowRunItem>,
+    pub timed_out: bool,
+    pub polls: u32,
+    pub waited_ms: u64,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WaitForChecksInput {
+    pub owner: String,
+    pub repo: String,
+    pub number: Option<i64>,
+    pub r#ref: Option<String>,
+    pub timeout_secs: Option<u64>,
+}
+#[derive(Debug, Serialize)]
+pub struct CheckCounts {
+    pub success: i32,
+    pub pending: i32,
+    pub failure: i32,
+}
+#[derive(Debug, Serialize)]
+pub struct ChecksStateItem {
+    pub sha: Option<String>,
+    pub overall_state: String,
+    pub counts: CheckCounts,
+    pub failing_contexts: Vec<String>,
+}
+#[derive(Debug, Serialize)]
+pub struct WaitForChecksOutput {
+    pub item: Option<ChecksStateItem>,
+    pub timed_out: bool,
+    pub polls: u32,
+    pub waited_ms: u64,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ListWorkflowJobsInput {
     pub owner: String,
     pub repo: String,
@@ -1030,12 +3152,15 @@ pub struct ListWorkflowJobsOutput {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct GetJobLogsInput {
     pub owner: String,
     pub repo: String,
     pub job_id: i64,
     pub tail_lines: Option<usize>,
     pub include_timestamps: Option<bool>,
+    // Cap on downloaded (compressed) bytes; the transfer stops there and truncated is set.
+    pub max_bytes: Option<u64>,
 }
 #[derive(Debug, Serialize)]
 pub struct GetJobLogsOutput {
@@ -1047,6 +3172,7 @@ pub struct GetJobLogsOutput {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct RunIdInput {
     pub owner: String,
     pub repo: String,
@@ -1064,6 +3190,7 @@ pub struct OkOutput {
 
 // Secrets / Variables / Environments inputs and outputs (REST light)
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct RepoInput {
     pub owner: String,
     pub repo: String,
@@ -1072,6 +3199,7 @@ pub struct RepoInput {
     pub per_page: Option<u32>,
 }
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct EnvVarsInput {
     pub owner: String,
     pub repo: String,
@@ -1081,6 +3209,48 @@ pub struct EnvVarsInput {
     pub per_page: Option<u32>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GetEnvironmentInput {
+    pub owner: String,
+    pub repo: String,
+    pub environment_name: String,
+}
+#[derive(Debug, Serialize)]
+pub struct EnvironmentReviewer {
+    // "User" or "Team".
+    #[serde(rename = "type")]
+    pub kind: String,
+    // User login or team slug.
+    pub login: Option<String>,
+}
+#[derive(Debug, Serialize)]
+pub struct DeploymentBranchPolicy {
+    pub protected_branches: bool,
+    pub custom_branch_policies: bool,
+}
+#[derive(Debug, Serialize)]
+pub struct EnvironmentDetailItem {
+    pub name: String,
+    // Minutes a deployment waits before it may proceed; absent without a wait timer rule.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wait_timer: Option<i64>,
+    pub reviewers: Vec<EnvironmentReviewer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prevent_self_review: Option<bool>,
+    // Null when any branch may deploy.
+    pub deployment_branch_policy: Option<DeploymentBranchPolicy>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+#[derive(Debug, Serialize)]
+pub struct GetEnvironmentOutput {
+    pub item: Option<EnvironmentDetailItem>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct RepoSecretItem {
     pub name: String,
@@ -1123,8 +3293,70 @@ pub struct ListEnvironmentsOutput {
     pub error: Option<ErrorShape>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ListDeployKeysInput {
+    pub owner: String,
+    pub repo: String,
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+}
+#[derive(Debug, Serialize)]
+pub struct DeployKeyItem {
+    pub id: i64,
+    pub title: String,
+    // Public half only; GitHub never returns the private key.
+    pub key: String,
+    pub read_only: bool,
+    pub created_at: Option<String>,
+}
+#[derive(Debug, Serialize)]
+pub struct ListDeployKeysOutput {
+    pub items: Option<Vec<DeployKeyItem>>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ListOrgAuditLogInput {
+    pub org: String,
+    // Audit log search phrase, e.g. "action:repo.create actor:octocat"
+    pub phrase: Option<String>,
+    // web, git or all (GitHub defaults to web)
+    pub include: Option<String>,
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+}
+#[derive(Debug, Serialize)]
+pub struct AuditLogEntry {
+    pub action: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    pub created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub org: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repo: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub document_id: Option<String>,
+}
+#[derive(Debug, Serialize)]
+pub struct ListOrgAuditLogOutput {
+    pub items: Option<Vec<AuditLogEntry>>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
 // New unified/simple outputs for added tools
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ListCommitsInput {
     pub owner: String,
     pub repo: String,
@@ -1137,6 +3369,8 @@ pub struct ListCommitsInput {
     pub limit: Option<u32>,
     pub include_author: Option<bool>,
     pub include_stats: Option<bool>,
+    pub include_verification: Option<bool>,
+    pub sha_length: Option<String>,
 }
 #[derive(Debug, Serialize)]
 pub struct ListCommitsItem {
@@ -1144,11 +3378,15 @@ pub struct ListCommitsItem {
     pub title: String,
     pub authored_at: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub html_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub author_login: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub committer_login: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stats: Option<CommitStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification: Option<CommitVerification>,
 }
 #[derive(Debug, Serialize)]
 pub struct CommitStats {
@@ -1156,6 +3394,13 @@ pub struct CommitStats {
     pub deletions: i64,
     pub total: i64,
 }
+// Signature check as reported in the REST payload's `commit.verification`; `reason` is
+// GitHub's code such as "valid", "unsigned" or "unknown_key".
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommitVerification {
+    pub verified: bool,
+    pub reason: String,
+}
 #[derive(Debug, Serialize)]
 pub struct ListCommitsOutput {
     pub items: Option<Vec<ListCommitsItem>>,
@@ -1165,12 +3410,15 @@ pub struct ListCommitsOutput {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct GetCommitInput {
     pub owner: String,
     pub repo: String,
     pub r#ref: String,
     pub include_stats: Option<bool>,
     pub include_files: Option<bool>,
+    pub include_verification: Option<bool>,
+    pub sha_length: Option<String>,
 }
 #[derive(Debug, Serialize)]
 pub struct CommitParent {
@@ -1192,6 +3440,8 @@ pub struct GetCommitItem {
     pub message: String,
     pub authored_at: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub html_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub author_login: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub committer_login: Option<String>,
@@ -1200,6 +3450,8 @@ pub struct GetCommitItem {
     pub stats: Option<CommitStats>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub files: Option<Vec<CommitFile>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification: Option<CommitVerification>,
 }
 #[derive(Debug, Serialize)]
 pub struct GetCommitOutput {
@@ -1210,6 +3462,7 @@ pub struct GetCommitOutput {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ListTagsInput {
     pub owner: String,
     pub repo: String,
@@ -1218,6 +3471,7 @@ pub struct ListTagsInput {
     // Currently unused until we return tag object details; kept for API parity
     #[allow(dead_code)]
     pub include_object: Option<bool>,
+    pub sha_length: Option<String>,
 }
 #[derive(Debug, Serialize)]
 pub struct TagItem {
@@ -1240,11 +3494,13 @@ pub struct ListTagsOutput {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct GetTagInput {
     pub owner: String,
     pub repo: String,
     pub tag: String,
     pub resolve_annotated: Option<bool>,
+    pub sha_length: Option<String>,
 }
 #[derive(Debug, Serialize)]
 pub struct GetTagItem {
@@ -1265,18 +3521,36 @@ pub struct GetTagOutput {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ListBranchesInput {
     pub owner: String,
     pub repo: String,
     pub protected: Option<bool>,
     pub cursor: Option<String>,
     pub limit: Option<u32>,
+    pub sha_length: Option<String>,
+    // "name" (GitHub's order, default) or "protected_first".
+    pub sort: Option<String>,
+    pub include_protection_summary: Option<bool>,
 }
 #[derive(Debug, Serialize)]
 pub struct BranchItem {
     pub name: String,
     pub commit_sha: String,
     pub protected: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protection: Option<BranchProtectionSummary>,
+    // The summary could not be fetched (reading protection needs admin access).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protection_error: Option<ErrorShape>,
+}
+#[derive(Debug, Serialize)]
+pub struct BranchProtectionSummary {
+    // Absent when pull request reviews are not required.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required_approving_review_count: Option<i64>,
+    pub required_checks: Vec<String>,
+    pub enforce_admins: bool,
 }
 #[derive(Debug, Serialize)]
 pub struct ListBranchesOutput {
@@ -1287,11 +3561,14 @@ pub struct ListBranchesOutput {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ListReleasesInput {
     pub owner: String,
     pub repo: String,
     pub cursor: Option<String>,
     pub limit: Option<u32>,
+    pub exclude_drafts: Option<bool>,
+    pub exclude_prereleases: Option<bool>,
 }
 #[derive(Debug, Serialize)]
 pub struct ReleaseItem {
@@ -1315,6 +3592,7 @@ pub struct ListReleasesOutput {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct GetReleaseInput {
     pub owner: String,
     pub repo: String,
@@ -1354,6 +3632,7 @@ pub struct GetReleaseOutput {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ListStarredReposInput {
     pub cursor: Option<String>,
     pub limit: Option<u32>,
@@ -1383,6 +3662,33 @@ pub struct ListStarredReposOutput {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ListWatchedReposInput {
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+}
+#[derive(Debug, Serialize)]
+pub struct WatchedRepoItem {
+    pub full_name: String,
+    pub private: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    pub stargazers_count: i64,
+    pub html_url: String,
+    pub pushed_at: Option<String>,
+}
+#[derive(Debug, Serialize)]
+pub struct ListWatchedReposOutput {
+    pub items: Option<Vec<WatchedRepoItem>>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct MergePrInput {
     pub owner: String,
     pub repo: String,
@@ -1408,98 +3714,377 @@ pub struct MergePrOutput {
 }
 
 #[derive(Debug, Deserialize)]
-pub struct SearchInput {
-    pub q: String,
-    pub sort: Option<String>,
-    pub order: Option<String>,
-    pub cursor: Option<String>,
-    pub limit: Option<u32>,
+#[serde(deny_unknown_fields)]
+pub struct RepoRefInput {
+    pub owner: String,
+    pub repo: String,
 }
 #[derive(Debug, Serialize)]
-pub struct SearchIssueItem {
-    pub id: i64,
-    pub number: i64,
-    pub title: String,
-    pub state: String,
-    pub repo_full_name: String,
-    pub is_pull_request: bool,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub author_login: Option<String>,
-    pub created_at: String,
-    pub updated_at: String,
+pub struct MergeMethodsItem {
+    // merge_method values accepted by merge_pr, in GitHub's order (merge, squash, rebase).
+    pub methods: Vec<String>,
+    // The allow_* flags are only visible with push access; null when GitHub omits them.
+    pub allow_merge_commit: Option<bool>,
+    pub allow_squash_merge: Option<bool>,
+    pub allow_rebase_merge: Option<bool>,
+    pub delete_branch_on_merge: Option<bool>,
 }
 #[derive(Debug, Serialize)]
-pub struct SearchIssuesOutput {
-    pub items: Option<Vec<SearchIssueItem>>,
-    pub total_count: i64,
-    pub incomplete_results: bool,
+pub struct MergeMethodsOutput {
+    pub item: Option<MergeMethodsItem>,
     pub meta: Meta,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<ErrorShape>,
 }
+
 #[derive(Debug, Serialize)]
-pub struct SearchRepoItem {
+pub struct RepoItem {
     pub full_name: String,
-    pub private: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub language: Option<String>,
-    pub stargazers_count: i64,
-    pub forks_count: i64,
-    pub open_issues_count: i64,
+    pub private: bool,
+    pub fork: bool,
+    pub archived: bool,
+    pub default_branch: String,
     pub html_url: String,
+    // Remote URLs for handing off to a local `git clone`.
+    pub clone_url: String,
+    pub ssh_url: String,
+    pub git_url: String,
 }
 #[derive(Debug, Serialize)]
-pub struct SearchReposOutput {
-    pub items: Option<Vec<SearchRepoItem>>,
-    pub total_count: i64,
-    pub incomplete_results: bool,
+pub struct GetRepoOutput {
+    pub item: Option<RepoItem>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LicenseItem {
+    // "NOASSERTION" when GitHub found a license file it couldn't classify.
+    pub spdx_id: Option<String>,
+    pub name: Option<String>,
+    pub path: String,
+    pub text: String,
+}
+#[derive(Debug, Serialize)]
+pub struct GetLicenseOutput {
+    // Always present; null when GitHub detected no license.
+    pub license: Option<LicenseItem>,
     pub meta: Meta,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<ErrorShape>,
 }
 
 #[derive(Debug, Deserialize)]
-pub struct UpdateIssueInput {
+#[serde(deny_unknown_fields)]
+pub struct GetFileContentsInput {
     pub owner: String,
     pub repo: String,
-    pub number: i64,
-    pub title: Option<String>,
-    pub body: Option<String>,
-    pub labels: Option<Vec<String>>,
-    pub assignees: Option<Vec<String>>,
-    pub state: Option<String>,
-    pub milestone: Option<i64>,
+    pub path: String,
+    pub r#ref: Option<String>,
+    pub max_bytes: Option<u64>,
 }
 #[derive(Debug, Serialize)]
-pub struct UpdatedIssueItem {
-    pub id: i64,
-    pub number: i64,
-    pub title: String,
+pub struct FileContentItem {
+    pub path: String,
+    pub sha: String,
+    pub size: u64,
+    // Encoding of `content`: "utf-8" once decoded, "base64" for binary files, otherwise
+    // GitHub's own value (e.g. "none" for files too large for the contents API).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub body: Option<String>,
-    pub state: String,
-    pub labels: Vec<String>,
-    pub assignees: Vec<String>,
+    pub encoding: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub milestone: Option<i64>,
-    pub updated_at: String,
+    pub content: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
 }
 #[derive(Debug, Serialize)]
-pub struct UpdateIssueOutput {
-    pub item: Option<UpdatedIssueItem>,
+pub struct DirEntryItem {
+    pub name: String,
+    pub path: String,
+    // file, dir, symlink or submodule
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub sha: String,
+}
+#[derive(Debug, Serialize)]
+pub struct GetFileContentsOutput {
+    // `item` for a file, `items` for a directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item: Option<FileContentItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<Vec<DirEntryItem>>,
     pub meta: Meta,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<ErrorShape>,
 }
 
 #[derive(Debug, Deserialize)]
-pub struct UpdatePullRequestInput {
+#[serde(deny_unknown_fields)]
+pub struct CreateOrUpdateFileInput {
     pub owner: String,
     pub repo: String,
-    pub number: i64,
-    pub title: Option<String>,
+    pub path: String,
+    pub message: String,
+    // Plain text; base64-encoded by the server.
+    pub content: String,
+    pub branch: Option<String>,
+    pub sha: Option<String>,
+}
+#[derive(Debug, Serialize)]
+pub struct CommittedFileItem {
+    pub path: String,
+    pub commit_sha: String,
+    pub file_sha: String,
+    // false when an existing file was replaced.
+    pub created: bool,
+}
+#[derive(Debug, Serialize)]
+pub struct CreateOrUpdateFileOutput {
+    pub item: Option<CommittedFileItem>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+// Composed search request shared by the search_* handlers; the limit is validated upstream.
+#[derive(Debug)]
+pub struct SearchInput {
+    pub q: String,
+    pub sort: Option<String>,
+    pub order: Option<String>,
+    pub cursor: Option<String>,
+}
+// search_issues / search_pull_requests: raw `q` plus structured qualifiers composed
+// server-side.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SearchIssuesInput {
+    pub q: Option<String>,
+    pub state: Option<String>,
+    pub label: Option<String>,
+    pub author: Option<String>,
+    pub assignee: Option<String>,
+    pub milestone: Option<String>,
+    pub created: Option<String>,
+    pub updated: Option<String>,
+    pub repo: Option<String>,
+    pub org: Option<String>,
+    pub sort: Option<String>,
+    pub order: Option<String>,
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+}
+// search_repositories: raw `q` plus structured qualifiers composed server-side.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SearchReposInput {
+    pub q: Option<String>,
+    pub language: Option<String>,
+    pub stars: Option<String>,
+    pub pushed: Option<String>,
+    pub topic: Option<String>,
+    pub org: Option<String>,
+    pub user: Option<String>,
+    pub sort: Option<String>,
+    pub order: Option<String>,
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+}
+#[derive(Debug, Serialize)]
+pub struct SearchIssueItem {
+    pub id: i64,
+    pub number: i64,
+    pub title: String,
+    pub state: String,
+    pub repo_full_name: String,
+    pub is_pull_request: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author_login: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ListPendingReviewsInput {
+    pub owner: Option<String>,
+    pub repo: Option<String>,
+    pub org: Option<String>,
+    pub reviewer: Option<String>,
+    pub include_drafts: Option<bool>,
+    pub include_reviewers: Option<bool>,
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+}
+#[derive(Debug, Serialize)]
+pub struct RequestedReviewers {
+    pub users: Vec<String>,
+    pub teams: Vec<String>,
+}
+#[derive(Debug, Serialize)]
+pub struct PendingReviewItem {
+    pub number: i64,
+    pub title: String,
+    pub repo_full_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author_login: Option<String>,
+    pub created_at: String,
+    // Whole days since the PR was opened.
+    pub age_days: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requested_reviewers: Option<RequestedReviewers>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reviewers_error: Option<ErrorShape>,
+}
+#[derive(Debug, Serialize)]
+pub struct ListPendingReviewsOutput {
+    pub items: Option<Vec<PendingReviewItem>>,
+    pub total_count: i64,
+    pub query: String,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+#[derive(Debug, Serialize)]
+pub struct SearchIssuesOutput {
+    pub items: Option<Vec<SearchIssueItem>>,
+    pub total_count: i64,
+    pub incomplete_results: bool,
+    // The `q` actually sent, after merging structured qualifiers.
+    pub query: String,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+#[derive(Debug, Serialize)]
+pub struct SearchRepoItem {
+    pub full_name: String,
+    pub private: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    pub stargazers_count: i64,
+    pub forks_count: i64,
+    pub open_issues_count: i64,
+    pub html_url: String,
+}
+#[derive(Debug, Serialize)]
+pub struct SearchReposOutput {
+    pub items: Option<Vec<SearchRepoItem>>,
+    pub total_count: i64,
+    pub incomplete_results: bool,
+    // The `q` actually sent, after merging structured qualifiers.
+    pub query: String,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateIssueInput {
+    pub owner: String,
+    pub repo: String,
+    pub number: i64,
+    pub title: Option<String>,
+    pub body: Option<String>,
+    pub labels: Option<Vec<String>>,
+    pub assignees: Option<Vec<String>>,
+    pub state: Option<String>,
+    pub milestone: Option<i64>,
+}
+#[derive(Debug, Serialize)]
+pub struct UpdatedIssueItem {
+    pub id: i64,
+    pub number: i64,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    pub state: String,
+    pub labels: Vec<String>,
+    pub assignees: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<i64>,
+    pub updated_at: String,
+}
+#[derive(Debug, Serialize)]
+pub struct UpdateIssueOutput {
+    pub item: Option<UpdatedIssueItem>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CreateIssueInput {
+    pub owner: String,
+    pub repo: String,
+    pub title: String,
+    pub body: Option<String>,
+    pub labels: Option<Vec<String>>,
+    pub assignees: Option<Vec<String>>,
+    pub milestone: Option<i64>,
+}
+#[derive(Debug, Serialize)]
+pub struct CreatedIssueItem {
+    pub id: i64,
+    pub number: i64,
+    pub title: String,
+    pub state: String,
+    pub html_url: String,
+    pub labels: Vec<String>,
+    pub assignees: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<i64>,
+    pub created_at: String,
+}
+#[derive(Debug, Serialize)]
+pub struct CreateIssueOutput {
+    pub item: Option<CreatedIssueItem>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CreatePullRequestInput {
+    pub owner: String,
+    pub repo: String,
+    pub title: String,
+    pub head: String,
+    pub base: String,
+    pub body: Option<String>,
+    pub draft: Option<bool>,
+    pub maintainer_can_modify: Option<bool>,
+}
+#[derive(Debug, Serialize)]
+pub struct CreatedPrItem {
+    pub number: i64,
+    pub id: i64,
+    pub state: String,
+    pub is_draft: bool,
+    pub html_url: String,
+}
+#[derive(Debug, Serialize)]
+pub struct CreatePullRequestOutput {
+    pub item: Option<CreatedPrItem>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UpdatePullRequestInput {
+    pub owner: String,
+    pub repo: String,
+    pub number: i64,
+    pub title: Option<String>,
     pub body: Option<String>,
     pub state: Option<String>,
     pub base: Option<String>,
@@ -1525,6 +4110,27 @@ pub struct UpdatePullRequestOutput {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClosePullRequestInput {
+    pub owner: String,
+    pub repo: String,
+    pub number: i64,
+    pub delete_branch: Option<bool>,
+}
+#[derive(Debug, Serialize)]
+pub struct ClosePullRequestOutput {
+    pub item: Option<UpdatedPrItem>,
+    pub branch_deleted: bool,
+    // Set when the PR was closed but deleting its branch failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch_error: Option<ErrorShape>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ForkRepositoryInput {
     pub owner: String,
     pub repo: String,
@@ -1546,3 +4152,813 @@ pub struct ForkRepositoryOutput {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<ErrorShape>,
 }
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RepoOverviewInput {
+    pub owner: String,
+    pub repo: String,
+}
+#[derive(Debug, Serialize)]
+pub struct RepoOverviewItem {
+    pub full_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub private: bool,
+    pub archived: bool,
+    pub default_branch: String,
+    pub stargazers_count: i64,
+    pub forks_count: i64,
+    pub html_url: String,
+    pub open_issues_count: Option<i64>,
+    pub open_pull_requests_count: Option<i64>,
+    pub latest_release: Option<ReleaseItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub languages: Option<std::collections::BTreeMap<String, i64>>,
+    // Per-section failures (latest_release, languages, counts); the overview is still returned.
+    #[serde(skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub section_errors: std::collections::BTreeMap<String, ErrorShape>,
+}
+#[derive(Debug, Serialize)]
+pub struct RepoOverviewOutput {
+    pub item: Option<RepoOverviewItem>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GetIssuesInput {
+    pub owner: String,
+    pub repo: String,
+    pub numbers: Vec<i64>,
+    pub include_author: Option<bool>,
+}
+#[derive(Debug, Serialize)]
+pub struct GetIssuesOutput {
+    // Keyed by issue number (as a string).
+    pub items: Option<std::collections::BTreeMap<String, GetIssueOutputItem>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub not_found: Vec<i64>,
+    // Numbers that failed (their chunk request, or the lookup itself with an error other than
+    // NOT_FOUND), keyed like `items`; the rest of the batch is kept.
+    #[serde(skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub errors: std::collections::BTreeMap<String, ErrorShape>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GetPullRequestsInput {
+    pub owner: String,
+    pub repo: String,
+    pub numbers: Vec<i64>,
+    pub include_author: Option<bool>,
+}
+#[derive(Debug, Serialize)]
+pub struct GetPullRequestsOutput {
+    // Keyed by PR number (as a string).
+    pub items: Option<std::collections::BTreeMap<String, GetPullRequestItem>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub not_found: Vec<i64>,
+    // Numbers that failed (their chunk request, or the lookup itself with an error other than
+    // NOT_FOUND), keyed like `items`; the rest of the batch is kept.
+    #[serde(skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub errors: std::collections::BTreeMap<String, ErrorShape>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResolveRefInput {
+    pub owner: String,
+    pub repo: String,
+    pub r#ref: String,
+}
+#[derive(Debug, Serialize)]
+pub struct ResolveRefItem {
+    pub r#ref: String,
+    pub sha: String,
+    // "branch", "tag", or "sha"
+    pub ref_type: String,
+    pub is_default: bool,
+}
+#[derive(Debug, Serialize)]
+pub struct ResolveRefOutput {
+    pub item: Option<ResolveRefItem>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ListPrsForCommitInput {
+    pub owner: String,
+    pub repo: String,
+    pub sha: String,
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+}
+#[derive(Debug, Serialize)]
+pub struct CommitPullItem {
+    pub number: i64,
+    pub title: String,
+    pub state: String,
+    pub merged: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merged_at: Option<String>,
+}
+#[derive(Debug, Serialize)]
+pub struct ListPrsForCommitOutput {
+    pub items: Option<Vec<CommitPullItem>>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ListBranchesForCommitInput {
+    pub owner: String,
+    pub repo: String,
+    pub sha: String,
+}
+#[derive(Debug, Serialize)]
+pub struct CommitBranchItem {
+    pub name: String,
+    pub protected: bool,
+}
+#[derive(Debug, Serialize)]
+pub struct ListBranchesForCommitOutput {
+    pub items: Option<Vec<CommitBranchItem>>,
+    // "branches_where_head" or "branch_scan" when the preview endpoint was unavailable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    // True when branch_scan stopped at its page cap; branches past it were not checked.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub incomplete: bool,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ListCommitStatusesInput {
+    pub owner: String,
+    pub repo: String,
+    pub r#ref: String,
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+}
+#[derive(Debug, Serialize)]
+pub struct CommitStatusItem {
+    pub context: String,
+    pub state: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_url: Option<String>,
+    pub created_at: Option<String>,
+}
+#[derive(Debug, Serialize)]
+pub struct ListCommitStatusesOutput {
+    pub items: Option<Vec<CommitStatusItem>>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReplyToReviewCommentInput {
+    pub owner: String,
+    pub repo: String,
+    pub number: i64,
+    pub comment_id: i64,
+    pub body: String,
+}
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CreateReviewCommentInput {
+    pub owner: String,
+    pub repo: String,
+    pub number: i64,
+    pub body: String,
+    pub commit_id: String,
+    pub path: String,
+    pub line: i64,
+    pub start_line: Option<i64>,
+    pub side: Option<String>,
+    pub start_side: Option<String>,
+}
+#[derive(Debug, Serialize)]
+pub struct CreatedReviewCommentItem {
+    pub id: String,
+    pub database_id: i64,
+    pub created_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub in_reply_to_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_line: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub side: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_side: Option<String>,
+}
+#[derive(Debug, Serialize)]
+pub struct CreateReviewCommentOutput {
+    pub item: Option<CreatedReviewCommentItem>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateCommentInput {
+    pub owner: String,
+    pub repo: String,
+    pub comment_id: i64,
+    pub comment_type: Option<String>,
+    pub body: String,
+}
+#[derive(Debug, Serialize)]
+pub struct UpdatedCommentItem {
+    pub id: String,
+    pub database_id: i64,
+    pub body: String,
+    pub updated_at: String,
+}
+#[derive(Debug, Serialize)]
+pub struct UpdateCommentOutput {
+    pub item: Option<UpdatedCommentItem>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DeleteCommentInput {
+    pub owner: String,
+    pub repo: String,
+    pub comment_id: i64,
+    pub comment_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DownloadReleaseAssetInput {
+    pub owner: String,
+    pub repo: String,
+    pub asset_id: i64,
+    pub max_bytes: Option<u64>,
+}
+#[derive(Debug, Serialize)]
+pub struct ReleaseAssetContent {
+    pub id: i64,
+    pub name: String,
+    pub content_type: String,
+    pub size: u64,
+    pub content_base64: String,
+}
+#[derive(Debug, Serialize)]
+pub struct DownloadReleaseAssetOutput {
+    pub item: Option<ReleaseAssetContent>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UploadReleaseAssetInput {
+    pub owner: String,
+    pub repo: String,
+    pub release_id: i64,
+    pub name: String,
+    pub label: Option<String>,
+    pub content_type: Option<String>,
+    pub content: String,
+}
+#[derive(Debug, Serialize)]
+pub struct UploadedAssetItem {
+    pub id: i64,
+    pub name: String,
+    pub size: i64,
+    pub browser_download_url: String,
+}
+#[derive(Debug, Serialize)]
+pub struct UploadReleaseAssetOutput {
+    pub item: Option<UploadedAssetItem>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FindFilesInput {
+    pub owner: String,
+    pub repo: String,
+    pub r#ref: Option<String>,
+    pub pattern: Option<String>,
+    pub query: Option<String>,
+    pub max_results: Option<usize>,
+}
+#[derive(Debug, Serialize)]
+pub struct FoundFileItem {
+    pub path: String,
+    pub sha: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<i64>,
+}
+#[derive(Debug, Serialize)]
+pub struct FindFilesOutput {
+    pub items: Option<Vec<FoundFileItem>>,
+    // More matches existed beyond max_results.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capped: Option<bool>,
+    // GitHub truncated the recursive tree (very large repos); matches may be missing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tree_truncated: Option<bool>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SetRepoSubscriptionInput {
+    pub owner: String,
+    pub repo: String,
+    pub subscribed: Option<bool>,
+    pub ignored: Option<bool>,
+}
+#[derive(Debug, Serialize)]
+pub struct RepoSubscriptionItem {
+    pub subscribed: bool,
+    pub ignored: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+}
+#[derive(Debug, Serialize)]
+pub struct RepoSubscriptionOutput {
+    pub item: Option<RepoSubscriptionItem>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DeleteRepoSubscriptionInput {
+    pub owner: String,
+    pub repo: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ListIssuesForUserInput {
+    pub filter: Option<String>,
+    pub state: Option<String>,
+    pub labels: Option<Vec<String>>,
+    pub since: Option<String>,
+    pub sort: Option<String>,
+    pub direction: Option<String>,
+    // true: GET /user/issues (owned + member repos only); default GET /issues (also org repos).
+    pub owned_only: Option<bool>,
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+    pub include_author: Option<bool>,
+    pub include_body: Option<bool>,
+    pub body_max_chars: Option<usize>,
+}
+#[derive(Debug, Serialize)]
+pub struct ListIssuesForUserOutput {
+    pub items: Option<Vec<ListIssuesOutputItem>>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateRepositoryInput {
+    pub owner: String,
+    pub repo: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub homepage: Option<String>,
+    pub private: Option<bool>,
+    pub visibility: Option<String>,
+    pub archived: Option<bool>,
+    pub confirm_archive: Option<bool>,
+    pub default_branch: Option<String>,
+    pub has_issues: Option<bool>,
+    pub has_projects: Option<bool>,
+    pub has_wiki: Option<bool>,
+    pub has_discussions: Option<bool>,
+}
+#[derive(Debug, Serialize)]
+pub struct RepoSettingsItem {
+    pub full_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<String>,
+    pub private: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<String>,
+    pub archived: bool,
+    pub default_branch: String,
+    pub has_issues: bool,
+    pub has_projects: bool,
+    pub has_wiki: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_discussions: Option<bool>,
+    pub html_url: String,
+    pub updated_at: String,
+}
+#[derive(Debug, Serialize)]
+pub struct UpdateRepositoryOutput {
+    pub item: Option<RepoSettingsItem>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DesiredLabel {
+    pub name: String,
+    pub color: String,
+    pub description: Option<String>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SyncLabelsInput {
+    pub owner: String,
+    pub repo: String,
+    pub labels: Vec<DesiredLabel>,
+    pub prune: Option<bool>,
+    pub dry_run: Option<bool>,
+}
+#[derive(Debug, Serialize)]
+pub struct LabelSyncFailure {
+    pub name: String,
+    pub action: String,
+    pub error: ErrorShape,
+}
+#[derive(Debug, Serialize)]
+pub struct LabelSyncItem {
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    pub deleted: Vec<String>,
+    pub unchanged: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub failed: Vec<LabelSyncFailure>,
+    pub dry_run: bool,
+}
+#[derive(Debug, Serialize)]
+pub struct SyncLabelsOutput {
+    pub item: Option<LabelSyncItem>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FindPullRequestInput {
+    pub owner: String,
+    pub repo: String,
+    pub head: String,
+    pub base: Option<String>,
+    pub include_author: Option<bool>,
+}
+#[derive(Debug, Serialize)]
+pub struct FindPullRequestOutput {
+    pub number: Option<i64>,
+    pub item: Option<ListPullRequestsItem>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ListMilestoneIssuesInput {
+    pub owner: String,
+    pub repo: String,
+    // Milestone number (not its title).
+    pub milestone: i64,
+    // GitHub defaults to open.
+    pub state: Option<String>,
+    // Default true; false keeps issues only.
+    pub include_pull_requests: Option<bool>,
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+    pub include_author: Option<bool>,
+    pub include_body: Option<bool>,
+    pub body_max_chars: Option<usize>,
+}
+#[derive(Debug, Serialize)]
+pub struct MilestoneSummary {
+    pub number: i64,
+    pub title: String,
+    pub state: String,
+    pub open_issues: i64,
+    pub closed_issues: i64,
+    pub due_on: Option<String>,
+}
+#[derive(Debug, Serialize)]
+pub struct MilestoneIssueItem {
+    pub id: String,
+    pub number: i64,
+    pub title: String,
+    pub state: String,
+    pub is_pull_request: bool,
+    pub created_at: String,
+    pub updated_at: String,
+    pub closed_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author_login: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+#[derive(Debug, Serialize)]
+pub struct ListMilestoneIssuesOutput {
+    pub milestone: Option<MilestoneSummary>,
+    pub items: Option<Vec<MilestoneIssueItem>>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GraphqlQueryInput {
+    pub query: String,
+    pub variables: Option<serde_json::Value>,
+}
+#[derive(Debug, Serialize)]
+pub struct GraphqlQueryOutput {
+    pub data: Option<serde_json::Value>,
+    // GraphQL `errors` array, verbatim.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<serde_json::Value>>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RestRequestInput {
+    pub method: String,
+    pub path: String,
+    pub body: Option<serde_json::Value>,
+    pub accept: Option<String>,
+}
+#[derive(Debug, Serialize)]
+pub struct RestRequestOutput {
+    // HTTP status of the final attempt; absent when no response was received.
+    pub status: Option<u16>,
+    pub body: Option<serde_json::Value>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RepoActivityDigestInput {
+    pub owner: String,
+    pub repo: String,
+    pub since: String,
+    pub until: Option<String>,
+}
+#[derive(Debug, Serialize)]
+pub struct RepoActivityDigest {
+    pub since: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<String>,
+    pub new_issues: Option<i64>,
+    pub new_pull_requests: Option<i64>,
+    pub merged_pull_requests: Option<i64>,
+    pub releases: Option<i64>,
+    // Tags of the releases published in the window, newest first.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub release_tags: Vec<String>,
+    // True when the window reaches further back than the release pages read; `releases`
+    // then undercounts.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub releases_incomplete: bool,
+    // Per-section failures; the other counts are still returned.
+    #[serde(skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub section_errors: std::collections::BTreeMap<String, ErrorShape>,
+}
+#[derive(Debug, Serialize)]
+pub struct RepoActivityDigestOutput {
+    pub item: Option<RepoActivityDigest>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum IssueOrPullRequestItem {
+    Issue(GetIssueOutputItem),
+    PullRequest(GetPullRequestItem),
+}
+#[derive(Debug, Serialize)]
+pub struct GetItemOutput {
+    // "issue" or "pull_request"; absent when nothing was found.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub item_type: Option<String>,
+    pub item: Option<IssueOrPullRequestItem>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GistIdInput {
+    pub gist_id: String,
+}
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GistFileUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    // Renames the file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateGistInput {
+    pub gist_id: String,
+    pub description: Option<String>,
+    // A null entry deletes the file; kept as null when serialized to GitHub.
+    pub files: Option<std::collections::BTreeMap<String, Option<GistFileUpdate>>>,
+}
+#[derive(Debug, Serialize)]
+pub struct GistItem {
+    pub id: String,
+    pub description: Option<String>,
+    pub public: bool,
+    pub html_url: String,
+    pub files: Vec<String>,
+    pub updated_at: String,
+}
+#[derive(Debug, Serialize)]
+pub struct UpdateGistOutput {
+    pub item: Option<GistItem>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ListCapabilitiesInput {
+    pub category: Option<String>,
+}
+#[derive(Debug, Serialize)]
+pub struct CapabilityTool {
+    pub name: String,
+    pub mutates: bool,
+    pub scopes: Vec<String>,
+    pub enabled: bool,
+    // "read-only mode", "not in allowlist", "denied" or "disabled".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disabled_reason: Option<String>,
+}
+#[derive(Debug, Serialize)]
+pub struct CapabilityCategory {
+    pub category: String,
+    pub tools: Vec<CapabilityTool>,
+}
+#[derive(Debug, Serialize)]
+pub struct ListCapabilitiesOutput {
+    pub read_only: bool,
+    pub categories: Vec<CapabilityCategory>,
+}
+
+// Upper bound on sub-calls in one `batch`.
+pub const BATCH_MAX_CALLS: usize = 20;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BatchInput {
+    pub calls: Vec<BatchCall>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BatchCall {
+    pub name: String,
+    #[serde(default)]
+    pub arguments: serde_json::Value,
+}
+#[derive(Debug, Serialize)]
+pub struct BatchResultItem {
+    pub name: String,
+    // The sub-call's structuredContent; absent when it was rejected at the JSON-RPC level.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+    pub is_error: bool,
+    // Unknown or gated tool, invalid params: the JSON-RPC error the call would have returned.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rpc_error: Option<BatchRpcError>,
+}
+#[derive(Debug, Serialize)]
+pub struct BatchRpcError {
+    pub code: i64,
+    pub message: String,
+}
+#[derive(Debug, Serialize)]
+pub struct BatchOutput {
+    pub items: Vec<BatchResultItem>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GetRateLimitInput {}
+#[derive(Debug, Serialize)]
+pub struct RateLimitResource {
+    pub limit: i32,
+    pub remaining: i32,
+    pub used: i32,
+    pub reset_at: Option<String>,
+}
+#[derive(Debug, Serialize)]
+pub struct RateLimitItem {
+    pub core: RateLimitResource,
+    // Older GitHub Enterprise Server releases omit some resources.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub graphql: Option<RateLimitResource>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search: Option<RateLimitResource>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code_search: Option<RateLimitResource>,
+}
+#[derive(Debug, Serialize)]
+pub struct GetRateLimitOutput {
+    pub item: Option<RateLimitItem>,
+    pub meta: Meta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorShape>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_schemas_are_valid_draft7_with_declared_required_keys() {
+        let tools = tool_descriptors();
+        let mut names = std::collections::BTreeSet::new();
+        for t in &tools {
+            assert!(names.insert(t.name.clone()), "duplicate tool {}", t.name);
+            let schema = &t.input_schema;
+            if let Err(e) = jsonschema::draft7::meta::validate(schema) {
+                panic!("{}: invalid inputSchema: {}", t.name, e);
+            }
+            assert_eq!(schema["type"], "object", "{}: type", t.name);
+            assert_eq!(
+                schema["additionalProperties"], false,
+                "{}: additionalProperties",
+                t.name
+            );
+            let props = schema["properties"]
+                .as_object()
+                .unwrap_or_else(|| panic!("{}: missing properties", t.name));
+            for key in schema["required"].as_array().into_iter().flatten() {
+                let key = key.as_str().unwrap();
+                assert!(
+                    props.contains_key(key),
+                    "{}: required {} not in properties",
+                    t.name,
+                    key
+                );
+            }
+        }
+    }
+}
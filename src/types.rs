@@ -6,4 +6,67 @@ pub struct RateMeta {
     pub remaining: Option<i32>,
     pub used: Option<i32>,
     pub reset_at: Option<String>,
+    // GraphQL only: points this query cost and the nodes it could return (`rateLimit`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_count: Option<i32>,
+}
+
+// Every error code the server emits. Serialized as the snake_case strings clients already see.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    BadRequest,
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    Conflict,
+    RateLimited,
+    // 5xx and transport failures; retriable.
+    UpstreamError,
+    // Unexpected local failures (client build, response decoding).
+    ServerError,
+    // 403 with X-GitHub-SSO: the token is not authorized for the org's SAML SSO.
+    SsoRequired,
+    // Merge refused by GitHub (branch protection, conflicts, checks).
+    MergeNotAllowed,
+    // Search API result window (1000 items) exhausted.
+    SearchLimitReached,
+    // GITHUB_MCP_CALL_DEADLINE_SECS ran out before the call's requests finished.
+    DeadlineExceeded,
+    // The call's `_timeout_secs` ran out.
+    Timeout,
+}
+
+impl ErrorCode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::BadRequest => "bad_request",
+            ErrorCode::Unauthorized => "unauthorized",
+            ErrorCode::Forbidden => "forbidden",
+            ErrorCode::NotFound => "not_found",
+            ErrorCode::Conflict => "conflict",
+            ErrorCode::RateLimited => "rate_limited",
+            ErrorCode::UpstreamError => "upstream_error",
+            ErrorCode::ServerError => "server_error",
+            ErrorCode::SsoRequired => "sso_required",
+            ErrorCode::MergeNotAllowed => "merge_not_allowed",
+            ErrorCode::SearchLimitReached => "search_limit_reached",
+            ErrorCode::DeadlineExceeded => "deadline_exceeded",
+            ErrorCode::Timeout => "timeout",
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl PartialEq<&str> for ErrorCode {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
 }
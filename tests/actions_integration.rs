@@ -67,6 +67,63 @@ fn get_workflow_job_logs_redirect_zip_tail_and_timestamps() -> anyhow::Result<()
     Ok(())
 }
 
+#[test]
+fn get_workflow_job_logs_max_bytes_stops_download_and_keeps_complete_entries() -> anyhow::Result<()>
+{
+    let server = MockServer::start();
+    let mut zip_bytes: Vec<u8> = Vec::new();
+    {
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(&mut zip_bytes));
+        let options = FileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        writer.start_file("1_setup.txt", options)?;
+        writer.write_all(b"setup done\n")?;
+        writer.start_file("2_build.txt", options)?;
+        for i in 0..500 {
+            writeln!(writer, "compiling unit {i}")?;
+        }
+        writer.start_file("3_test.txt", options)?;
+        writer.write_all(b"tests passed\n")?;
+        writer.finish()?;
+    }
+    let redirect_url = format!("{}/tmp/big.zip", server.base_url());
+    let _api = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/actions/jobs/7/logs");
+        then.status(302).header("location", redirect_url.as_str());
+    });
+    let _blob = server.mock(|when, then| {
+        when.method(GET).path("/tmp/big.zip");
+        then.status(200).body(zip_bytes.clone());
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    let call = |args: serde_json::Value| -> anyhow::Result<serde_json::Value> {
+        let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,
+            "params":{"name":"get_workflow_job_logs","arguments": args}});
+        Ok(serde_json::from_str(&run_with_env(&req, &envs)?)?)
+    };
+
+    // Under the cap: every entry is read, nothing is truncated.
+    let v = call(serde_json::json!({"owner":"o","repo":"r","job_id":7,"max_bytes":1_000_000}))?;
+    let sc = &v["result"]["structuredContent"];
+    let logs = sc["logs"].as_str().unwrap();
+    assert!(logs.contains("setup done"));
+    assert!(logs.contains("compiling unit 499"));
+    assert!(logs.contains("tests passed"));
+    assert_eq!(sc["truncated"], false);
+
+    // Cap inside the second entry: the download stops, earlier entries survive.
+    let cap = zip_bytes.len() / 2;
+    let v = call(serde_json::json!({"owner":"o","repo":"r","job_id":7,"max_bytes":cap}))?;
+    let sc = &v["result"]["structuredContent"];
+    let logs = sc["logs"].as_str().unwrap();
+    assert!(logs.contains("setup done"));
+    assert!(logs.contains("compiling unit 0"));
+    assert!(!logs.contains("tests passed"));
+    assert_eq!(sc["truncated"], true);
+    assert!(sc.get("error").is_none());
+    Ok(())
+}
+
 #[test]
 fn rerun_and_cancel_endpoints() -> anyhow::Result<()> {
     let server = MockServer::start();
@@ -116,3 +173,160 @@ fn rerun_and_cancel_endpoints() -> anyhow::Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn cancel_retries_server_errors_before_failing() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let cancel = server.mock(|when, then| {
+        when.method(POST).path("/repos/o/r/actions/runs/300/cancel");
+        then.status(502).body("bad gateway");
+    });
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"cancel_workflow_run","arguments":{"owner":"o","repo":"r","run_id":300}}});
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_API_URL", server.base_url().as_str()),
+        ],
+    )?;
+    // Initial attempt plus five retries, then the upstream error is surfaced.
+    cancel.assert_hits(6);
+    assert!(out.contains("\"ok\":false"));
+    assert!(out.contains("\"isError\":true"));
+    Ok(())
+}
+
+#[test]
+fn wait_for_workflow_run_returns_completed_or_times_out() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let run = |id: i64, status: &str, conclusion: Option<&str>| {
+        serde_json::json!({
+            "id": id, "run_number": 7, "event": "push", "status": status,
+            "conclusion": conclusion, "head_sha": "abc",
+            "created_at": "2024-01-01T00:00:00Z", "updated_at": "2024-01-01T00:05:00Z"
+        })
+    };
+    let done = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/actions/runs/1");
+        then.status(200)
+            .json_body(run(1, "completed", Some("success")));
+    });
+    let _pending = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/actions/runs/2");
+        then.status(200).json_body(run(2, "in_progress", None));
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"wait_for_workflow_run","arguments":{"owner":"o","repo":"r","run_id":1}}});
+    let out = run_with_env(&req, &envs)?;
+    done.assert_hits(1);
+    let v: serde_json::Value = serde_json::from_str(out.trim())?;
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(sc["item"]["conclusion"], "success");
+    assert_eq!(sc["timed_out"], false);
+    assert_eq!(sc["polls"], 1);
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":2,"params":{"name":"wait_for_workflow_run","arguments":{"owner":"o","repo":"r","run_id":2,"timeout_secs":1},"_meta":{"progressToken":"tok"}}});
+    let out = run_with_env(&req, &envs)?;
+    let lines: Vec<serde_json::Value> = out
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    let (last, notes) = lines.split_last().unwrap();
+    assert!(!notes.is_empty());
+    assert!(notes
+        .iter()
+        .all(|n| n["method"] == "notifications/progress" && n["params"]["progressToken"] == "tok"));
+    let sc = &last["result"]["structuredContent"];
+    assert_eq!(sc["timed_out"], true);
+    assert_eq!(sc["item"]["status"], "in_progress");
+    assert!(sc.get("error").is_none());
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":3,"params":{"name":"wait_for_workflow_run","arguments":{"owner":"o","repo":"r","run_id":2,"timeout_secs":99999}}});
+    let out = run_with_env(&req, &envs)?;
+    assert!(out.contains("-32602"));
+    Ok(())
+}
+
+#[test]
+fn get_workflow_by_id_or_encoded_file_name() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let body = serde_json::json!({
+        "id": 161335, "node_id": "W_1", "name": "CI", "path": ".github/workflows/ci build.yml",
+        "state": "active", "created_at": "2024-01-01T00:00:00.000+00:00",
+        "updated_at": "2024-02-01T00:00:00Z",
+        "badge_url": "https://github.com/o/r/workflows/CI/badge.svg"
+    });
+    let by_id = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/actions/workflows/161335");
+        then.status(200).json_body(body.clone());
+    });
+    let by_file = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/actions/workflows/ci%20build.yml");
+        then.status(200).json_body(body.clone());
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    for workflow_id in [serde_json::json!(161335), serde_json::json!("ci build.yml")] {
+        let req = serde_json::json!({
+            "jsonrpc":"2.0","method":"tools/call","id":1,
+            "params":{"name":"get_workflow","arguments":{"owner":"o","repo":"r","workflow_id":workflow_id}}
+        });
+        let out = run_with_env(&req, &envs)?;
+        let v: serde_json::Value = serde_json::from_str(&out)?;
+        let item = &v["result"]["structuredContent"]["item"];
+        assert_eq!(item["id"], 161335);
+        assert_eq!(item["state"], "active");
+        assert_eq!(item["created_at"], "2024-01-01T00:00:00Z");
+        assert_eq!(
+            item["badge_url"],
+            "https://github.com/o/r/workflows/CI/badge.svg"
+        );
+    }
+    by_id.assert();
+    by_file.assert();
+    Ok(())
+}
+
+#[test]
+fn dispatch_workflow_posts_ref_and_inputs_and_surfaces_422() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let ok = server.mock(|when, then| {
+        when.method(POST)
+            .path("/repos/o/r/actions/workflows/release%20build.yml/dispatches")
+            .json_body(
+                serde_json::json!({"ref": "main", "inputs": {"version": "1.2.3", "dry_run": true}}),
+            );
+        then.status(204);
+    });
+    let missing = server.mock(|when, then| {
+        when.method(POST)
+            .path("/repos/o/r/actions/workflows/42/dispatches")
+            .json_body(serde_json::json!({"ref": "main"}));
+        then.status(422)
+            .json_body(serde_json::json!({"message": "Required input 'version' not provided"}));
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"dispatch_workflow","arguments":{"owner":"o","repo":"r","workflow_id":"release build.yml","ref":"main","inputs":{"version":"1.2.3","dry_run":true}}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    ok.assert();
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(sc["ok"], true);
+    assert!(sc.get("error").is_none());
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":2,"params":{"name":"dispatch_workflow","arguments":{"owner":"o","repo":"r","workflow_id":42,"ref":"main"}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    missing.assert();
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(sc["ok"], false);
+    assert_eq!(sc["error"]["code"], "bad_request");
+    assert!(sc["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("Required input 'version' not provided"));
+    Ok(())
+}
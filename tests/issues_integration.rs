@@ -1,5 +1,8 @@
 use assert_cmd::Command;
-use httpmock::{Method::POST, MockServer};
+use httpmock::{
+    Method::{GET, POST},
+    MockServer,
+};
 use std::io::Write;
 
 fn run_with_env(req: &serde_json::Value, envs: &[(&str, &str)]) -> anyhow::Result<String> {
@@ -124,3 +127,459 @@ fn list_issue_comments_happy_path() -> anyhow::Result<()> {
     assert!(out.contains("\"author_login\":\"bob\""));
     Ok(())
 }
+
+#[test]
+fn list_issues_node_limit_maps_to_bad_request() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let body = serde_json::json!({
+      "data": null,
+      "errors": [{"type":"MAX_NODE_LIMIT_EXCEEDED","message":"This query requests up to 600,000 possible nodes which exceeds the maximum limit of 500,000."}]
+    });
+    let _m = server.mock(|when, then| {
+        when.method(POST).path("/graphql");
+        then.status(200).json_body(body);
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"list_issues","arguments": {"owner":"o","repo":"r","limit":100}}
+    });
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            (
+                "GITHUB_GRAPHQL_URL",
+                &format!("{}/graphql", server.base_url()),
+            ),
+            ("GITHUB_API_URL", server.base_url().as_str()),
+        ],
+    )?;
+    assert!(out.contains("\"code\":\"bad_request\""));
+    assert!(out.contains("\"retriable\":false"));
+    Ok(())
+}
+
+#[test]
+fn get_issues_batch_aliases() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    // GitHub resolves the other aliases and reports each null one in `errors` with its path.
+    let body = serde_json::json!({
+      "data": {"repository": {
+        "n1": {"id":"I_1","number":1,"title":"One","body":"b","state":"OPEN","createdAt":"2025-01-01T00:00:00Z","updatedAt":"2025-01-01T00:00:00Z","author":{"login":"alice"}},
+        "n2": null,
+        "n3": null
+      }},
+      "errors": [
+        {"type":"NOT_FOUND","path":["repository","n2"],"message":"Could not resolve to an Issue with the number of 2."},
+        {"type":"FORBIDDEN","path":["repository","n3"],"message":"Resource not accessible by integration"}
+      ]
+    });
+    let m = server.mock(|when, then| {
+        when.method(POST)
+            .path("/graphql")
+            .body_contains("n1: issue(number: 1)")
+            .body_contains("n2: issue(number: 2)")
+            .body_contains("n3: issue(number: 3)");
+        then.status(200).json_body(body);
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"get_issues","arguments": {"owner":"o","repo":"r","numbers":[1,2,1,3]}}
+    });
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            (
+                "GITHUB_GRAPHQL_URL",
+                &format!("{}/graphql", server.base_url()),
+            ),
+            ("GITHUB_API_URL", server.base_url().as_str()),
+        ],
+    )?;
+    m.assert();
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let sc = &v["result"]["structuredContent"];
+    assert!(sc.get("error").is_none());
+    assert_eq!(sc["items"]["1"]["title"], "One");
+    assert_eq!(sc["not_found"], serde_json::json!([2]));
+    assert!(sc["errors"]["3"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("not accessible"));
+    assert!(sc["errors"].get("2").is_none());
+    Ok(())
+}
+
+#[test]
+fn get_issues_splits_large_batch_and_reports_chunk_errors() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let first: serde_json::Map<String, serde_json::Value> = (1..=20)
+        .map(|n| {
+            (
+                format!("n{}", n),
+                serde_json::json!({"id":format!("I_{}", n),"number":n,"title":format!("T{}", n),"state":"OPEN","createdAt":"2025-01-01T00:00:00Z","updatedAt":"2025-01-01T00:00:00Z","author":null}),
+            )
+        })
+        .collect();
+    let ok = server.mock(|when, then| {
+        when.method(POST)
+            .path("/graphql")
+            .body_contains("n1: issue(number: 1)");
+        then.status(200)
+            .json_body(serde_json::json!({"data": {"repository": first}}));
+    });
+    let failed = server.mock(|when, then| {
+        when.method(POST)
+            .path("/graphql")
+            .body_contains("n21: issue(number: 21)");
+        then.status(403)
+            .json_body(serde_json::json!({"message":"Resource not accessible by integration"}));
+    });
+    let numbers: Vec<i64> = (1..=22).collect();
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"get_issues","arguments": {"owner":"o","repo":"r","numbers":numbers}}
+    });
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            (
+                "GITHUB_GRAPHQL_URL",
+                &format!("{}/graphql", server.base_url()),
+            ),
+            ("GITHUB_API_URL", server.base_url().as_str()),
+        ],
+    )?;
+    ok.assert();
+    failed.assert();
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let sc = &v["result"]["structuredContent"];
+    assert!(sc.get("error").is_none());
+    assert_eq!(sc["items"].as_object().unwrap().len(), 20);
+    assert_eq!(sc["items"]["20"]["title"], "T20");
+    assert!(sc["errors"]["21"]["code"].is_string());
+    assert!(sc["errors"]["22"]["code"].is_string());
+    Ok(())
+}
+
+#[test]
+fn get_issues_rejects_oversized_batch() -> anyhow::Result<()> {
+    let numbers: Vec<i64> = (1..=101).collect();
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"get_issues","arguments": {"owner":"o","repo":"r","numbers":numbers}}
+    });
+    let out = run_with_env(&req, &[("GITHUB_TOKEN", "t")])?;
+    assert!(out.contains("-32602"));
+    Ok(())
+}
+
+#[test]
+fn list_issues_keeps_partial_data_with_graphql_errors() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let body = serde_json::json!({
+      "data": {
+        "repository": {
+          "issues": {
+            "nodes": [
+              {"id":"I_1","number":1,"title":"One","state":"OPEN","createdAt":"2025-01-01T00:00:00Z","updatedAt":"2025-01-01T00:00:00Z","author": null}
+            ],
+            "pageInfo": {"hasNextPage": false, "endCursor": null}
+          }
+        }
+      },
+      "errors": [{"message":"Could not resolve to a User", "type":"FORBIDDEN"}]
+    });
+    let _m = server.mock(|when, then| {
+        when.method(POST).path("/graphql");
+        then.status(200).json_body(body.clone());
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"list_issues","arguments": {"owner":"o","repo":"r","include_author":true}}
+    });
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            (
+                "GITHUB_GRAPHQL_URL",
+                &format!("{}/graphql", server.base_url()),
+            ),
+        ],
+    )?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let res = &v["result"];
+    assert!(res.get("isError").is_none() || res["isError"] == false);
+    let sc = &res["structuredContent"];
+    assert_eq!(sc["items"][0]["number"], 1);
+    assert!(sc.get("error").is_none());
+    assert!(sc["partial_error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("Could not resolve"));
+    Ok(())
+}
+
+#[test]
+fn list_issues_for_user_spans_repos_and_skips_prs() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let m = server.mock(|when, then| {
+        when.method(GET)
+            .path("/issues")
+            .query_param("filter", "assigned")
+            .query_param("state", "open")
+            .query_param("labels", "bug,p1")
+            .query_param("per_page", "2");
+        then.status(200)
+            .header(
+                "link",
+                format!("<{}/issues?page=2>; rel=\"next\"", server.base_url()),
+            )
+            .json_body(serde_json::json!([
+                {"node_id":"I_1","number":3,"title":"A","state":"open","user":{"login":"u"},
+                 "created_at":"2025-01-01T00:00:00Z","updated_at":"2025-01-02T00:00:00Z",
+                 "html_url":"https://github.com/o/one/issues/3",
+                 "body":"b","repository":{"full_name":"o/one"}},
+                {"node_id":"PR_1","number":4,"title":"P","state":"open","user":{"login":"u"},
+                 "created_at":"2025-01-01T00:00:00Z","updated_at":"2025-01-02T00:00:00Z",
+                 "body":null,"repository":{"full_name":"o/two"},"pull_request":{"url":"x"}}
+            ]));
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"list_issues_for_user","arguments": {
+            "filter":"assigned","state":"open","labels":["bug","p1"],"limit":2,"include_author":true
+        }}
+    });
+    let base = server.base_url();
+    let out = run_with_env(
+        &req,
+        &[("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())],
+    )?;
+    m.assert();
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let sc = &v["result"]["structuredContent"];
+    let items = sc["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["repository"], "o/one");
+    assert_eq!(items[0]["html_url"], "https://github.com/o/one/issues/3");
+    assert_eq!(items[0]["state"], "OPEN");
+    assert_eq!(items[0]["author_login"], "u");
+    assert!(items[0].get("body").is_none());
+    assert_eq!(sc["meta"]["has_more"], true);
+
+    // owned_only switches to /user/issues
+    let owned = server.mock(|when, then| {
+        when.method(GET).path("/user/issues");
+        then.status(200).json_body(serde_json::json!([]));
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":2,
+        "params":{"name":"list_issues_for_user","arguments": {"owned_only":true}}
+    });
+    run_with_env(
+        &req,
+        &[("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())],
+    )?;
+    owned.assert();
+    Ok(())
+}
+
+#[test]
+fn list_milestone_issues_returns_progress_and_items() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let ms = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/milestones/3");
+        then.status(200).json_body(serde_json::json!({
+            "number":3,"title":"v1.0","state":"open","open_issues":1,"closed_issues":1,
+            "due_on":"2025-02-01T08:00:00Z"
+        }));
+    });
+    let issues = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/issues")
+            .query_param("milestone", "3")
+            .query_param("state", "all");
+        then.status(200).json_body(serde_json::json!([
+            {"node_id":"I_1","number":7,"title":"A","state":"closed","user":{"login":"u"},
+             "created_at":"2025-01-01T00:00:00Z","updated_at":"2025-01-02T00:00:00Z",
+             "closed_at":"2025-01-02T00:00:00Z","body":"b"},
+            {"node_id":"PR_1","number":8,"title":"P","state":"open","user":{"login":"u"},
+             "created_at":"2025-01-01T00:00:00Z","updated_at":"2025-01-02T00:00:00Z",
+             "closed_at":null,"body":null,"pull_request":{"url":"x"}}
+        ]));
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"list_milestone_issues","arguments": {"owner":"o","repo":"r","milestone":3,"state":"all"}}
+    });
+    let base = server.base_url();
+    let out = run_with_env(
+        &req,
+        &[("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())],
+    )?;
+    ms.assert();
+    issues.assert();
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(sc["milestone"]["title"], "v1.0");
+    assert_eq!(sc["milestone"]["closed_issues"], 1);
+    let items = sc["items"].as_array().unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0]["state"], "CLOSED");
+    assert_eq!(items[0]["is_pull_request"], false);
+    assert_eq!(items[1]["is_pull_request"], true);
+
+    // include_pull_requests=false keeps issues only
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":2,
+        "params":{"name":"list_milestone_issues","arguments": {"owner":"o","repo":"r","milestone":3,"state":"all","include_pull_requests":false}}
+    });
+    let out = run_with_env(
+        &req,
+        &[("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())],
+    )?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let items = v["result"]["structuredContent"]["items"]
+        .as_array()
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["number"], 7);
+    Ok(())
+}
+
+#[test]
+fn get_item_resolves_pull_request_number() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _m = server.mock(|when, then| {
+        when.method(POST).path("/graphql");
+        then.status(200).json_body(serde_json::json!({
+            "data": {"repository": {
+                "issue": null,
+                "pullRequest": {"id":"PR_1","number":7,"title":"Fix","body":null,"state":"OPEN","isDraft":false,"merged":false,"mergedAt":null,"createdAt":"2025-01-01T00:00:00Z","updatedAt":"2025-01-02T00:00:00Z","author":{"login":"alice"}}
+            }},
+            "errors": [{"type":"NOT_FOUND","path":["repository","issue"],"message":"Could not resolve to an Issue with the number of 7."}]
+        }));
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"get_item","arguments": {"owner":"o","repo":"r","number":7,"include_author":true}}
+    });
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            (
+                "GITHUB_GRAPHQL_URL",
+                &format!("{}/graphql", server.base_url()),
+            ),
+        ],
+    )?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let sc = &v["result"]["structuredContent"];
+    assert!(sc.get("error").is_none());
+    assert_eq!(sc["type"], "pull_request");
+    assert_eq!(sc["item"]["number"], 7);
+    assert_eq!(sc["item"]["is_draft"], false);
+    assert_eq!(sc["item"]["author_login"], "alice");
+    Ok(())
+}
+
+#[test]
+fn add_issue_comment_returns_created_comment() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let m = server.mock(|when, then| {
+        when.method(POST)
+            .path("/repos/o/r/issues/7/comments")
+            .json_body(serde_json::json!({"body": "Looks good"}));
+        then.status(201).json_body(serde_json::json!({
+            "id": 1001, "node_id": "IC_1", "body": "Looks good",
+            "user": {"login": "octocat"},
+            "created_at": "2025-01-02T03:04:05Z", "updated_at": "2025-01-02T03:04:05Z",
+            "html_url": "https://github.com/o/r/issues/7#issuecomment-1001"
+        }));
+    });
+    let _gone = server.mock(|when, then| {
+        when.method(POST).path("/repos/o/r/issues/8/comments");
+        then.status(404)
+            .json_body(serde_json::json!({"message": "Not Found"}));
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    let call = |number: i64| {
+        serde_json::json!({
+            "jsonrpc":"2.0","method":"tools/call","id":1,
+            "params":{"name":"add_issue_comment","arguments":{"owner":"o","repo":"r","number":number,"body":"Looks good"}}
+        })
+    };
+    let out = run_with_env(&call(7), &envs)?;
+    m.assert();
+    let v: serde_json::Value = serde_json::from_str(out.trim())?;
+    assert_eq!(
+        v["result"]["structuredContent"]["item"],
+        serde_json::json!({
+            "id": 1001, "body": "Looks good",
+            "created_at": "2025-01-02T03:04:05Z", "author_login": "octocat"
+        })
+    );
+
+    let out = run_with_env(&call(8), &envs)?;
+    let v: serde_json::Value = serde_json::from_str(out.trim())?;
+    assert_eq!(v["result"]["isError"], true);
+    assert_eq!(
+        v["result"]["structuredContent"]["error"]["code"],
+        "not_found"
+    );
+    Ok(())
+}
+
+#[test]
+fn create_issue_posts_fields_and_flattens_response() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let m = server.mock(|when, then| {
+        when.method(POST)
+            .path("/repos/o/r/issues")
+            .json_body(serde_json::json!({"title": "Crash on start", "labels": ["bug"], "assignees": ["octocat"]}));
+        then.status(201).json_body(serde_json::json!({
+            "id": 42, "number": 123, "title": "Crash on start", "state": "open",
+            "html_url": "https://github.com/o/r/issues/123",
+            "labels": [{"id": 1, "name": "bug", "color": "d73a4a"}],
+            "assignees": [{"login": "octocat"}],
+            "milestone": null,
+            "created_at": "2025-01-02T03:04:05Z"
+        }));
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"create_issue","arguments":{"owner":"o","repo":"r","title":"Crash on start","labels":["bug"],"assignees":["octocat"]}}
+    });
+    let out = run_with_env(&req, &envs)?;
+    m.assert();
+    let v: serde_json::Value = serde_json::from_str(out.trim())?;
+    assert_eq!(v["result"]["content"][0]["text"], "created issue #123");
+    assert_eq!(
+        v["result"]["structuredContent"]["item"],
+        serde_json::json!({
+            "id": 42, "number": 123, "title": "Crash on start", "state": "open",
+            "html_url": "https://github.com/o/r/issues/123",
+            "labels": ["bug"], "assignees": ["octocat"],
+            "created_at": "2025-01-02T03:04:05Z"
+        })
+    );
+
+    // A missing title is rejected before any request.
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":2,
+        "params":{"name":"create_issue","arguments":{"owner":"o","repo":"r","body":"no title"}}
+    });
+    let out = run_with_env(&req, &envs)?;
+    let v: serde_json::Value = serde_json::from_str(out.trim())?;
+    assert_eq!(v["error"]["code"], -32602);
+    m.assert_hits(1);
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use assert_cmd::Command;
-use httpmock::{Method::POST, MockServer};
+use httpmock::{Method::GET, Method::POST, MockServer};
 use std::io::Write;
 
 fn run_with_env(req: &serde_json::Value, envs: &[(&str, &str)]) -> anyhow::Result<String> {
@@ -134,3 +134,225 @@ fn list_issues_order_by_mapping_created_asc() -> anyhow::Result<()> {
     assert!(out.contains("\"structuredContent\""));
     Ok(())
 }
+
+#[test]
+fn list_issues_unassigned_uses_search_backend() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let m = server.mock(|when, then| {
+        when.method(GET)
+            .path("/search/issues")
+            .query_param("q", "repo:o/r is:issue is:open no:assignee");
+        then.status(200).json_body(serde_json::json!({
+            "total_count": 1, "incomplete_results": false,
+            "items": [{"node_id":"I_9","number":9,"title":"Nobody","state":"open","user":{"login":"alice"},"created_at":"2025-01-01T00:00:00Z","updated_at":"2025-01-02T00:00:00Z"}]
+        }));
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"list_issues","arguments": {"owner":"o","repo":"r","assignee":"none","state":"open"}}
+    });
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_API_URL", server.base_url().as_str()),
+        ],
+    )?;
+    m.assert();
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(sc["backend"], "search");
+    assert_eq!(sc["items"][0]["id"], "I_9");
+    assert_eq!(sc["items"][0]["state"], "OPEN");
+    Ok(())
+}
+
+#[test]
+fn list_issues_search_backend_rejects_qualifier_injection() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let mut m = server.mock(|when, then| {
+        when.method(GET).path("/search/issues");
+        then.status(200).json_body(serde_json::json!({
+            "total_count": 0, "incomplete_results": false, "items": []
+        }));
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    for extra in [
+        serde_json::json!({"labels":["bug\" repo:x/y"]}),
+        serde_json::json!({"creator":"x is:closed"}),
+        serde_json::json!({"mentions":"x repo:other/repo"}),
+        serde_json::json!({"creator":"repo:other/repo"}),
+    ] {
+        let mut args = serde_json::json!({"owner":"o","repo":"r","assignee":"none"});
+        args.as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        let req = serde_json::json!({
+            "jsonrpc":"2.0","method":"tools/call","id":1,
+            "params":{"name":"list_issues","arguments": args}
+        });
+        let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+        assert_eq!(v["error"]["code"], -32602, "{}", extra);
+    }
+    m.assert_hits(0);
+    m.delete();
+
+    // A space-separated since is re-rendered so it stays one qualifier
+    let since = server.mock(|when, then| {
+        when.method(GET).path("/search/issues").query_param(
+            "q",
+            "repo:o/r is:issue is:open no:assignee author:alice updated:>=2025-01-01T00:00:00Z",
+        );
+        then.status(200).json_body(serde_json::json!({
+            "total_count": 0, "incomplete_results": false, "items": []
+        }));
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":2,
+        "params":{"name":"list_issues","arguments": {"owner":"o","repo":"r","assignee":"none","creator":"alice","since":"2025-01-01 00:00:00Z"}}
+    });
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    assert!(v["result"]["structuredContent"]["error"].is_null(), "{}", v);
+    since.assert();
+    Ok(())
+}
+
+#[test]
+fn list_issues_updated_since_returns_high_watermark() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let m = server.mock(|when, then| {
+        when.method(POST).path("/graphql").matches(|req| {
+            let body = std::str::from_utf8(req.body.as_deref().unwrap_or(&[])).unwrap_or("");
+            let Ok(v) = serde_json::from_str::<serde_json::Value>(body) else {
+                return false;
+            };
+            let vars = &v["variables"];
+            vars["orderBy"] == serde_json::json!({"field":"UPDATED_AT","direction":"ASC"})
+                && vars["filterBy"]["since"] == "2025-01-01T00:00:00Z"
+        });
+        then.status(200).json_body(serde_json::json!({
+          "data": {
+            "repository": {
+              "issues": {
+                "nodes": [
+                  {"id":"I_1","number":1,"title":"One","state":"OPEN","createdAt":"2024-12-01T00:00:00Z","updatedAt":"2025-01-02T00:00:00Z","author": null},
+                  {"id":"I_2","number":2,"title":"Two","state":"OPEN","createdAt":"2024-12-02T00:00:00Z","updatedAt":"2025-01-03T08:00:00Z","author": null}
+                ],
+                "pageInfo": {"hasNextPage": false, "endCursor": null}
+              }
+            }
+          }
+        }));
+    });
+    let base = server.base_url();
+    let gql = format!("{}/graphql", base);
+    let envs = [
+        ("GITHUB_TOKEN", "t"),
+        ("GITHUB_GRAPHQL_URL", gql.as_str()),
+        ("GITHUB_API_URL", base.as_str()),
+    ];
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"list_issues","arguments": {"owner":"o","repo":"r","updated_since":"2025-01-01T00:00:00Z"}}
+    });
+    let out = run_with_env(&req, &envs)?;
+    let v: serde_json::Value = serde_json::from_str(out.lines().next().unwrap())?;
+    m.assert();
+    assert_eq!(
+        v["result"]["structuredContent"]["meta"]["max_updated_at"],
+        "2025-01-03T08:00:00Z"
+    );
+
+    // updated_since picks the order itself
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":2,
+        "params":{"name":"list_issues","arguments": {"owner":"o","repo":"r","updated_since":"2025-01-01T00:00:00Z","sort":"created"}}
+    });
+    let out = run_with_env(&req, &envs)?;
+    let v: serde_json::Value = serde_json::from_str(out.lines().next().unwrap())?;
+    assert_eq!(v["error"]["code"], -32602);
+    Ok(())
+}
+
+#[test]
+fn list_issues_updated_since_keeps_watermark_when_nothing_changed() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _m = server.mock(|when, then| {
+        when.method(POST).path("/graphql");
+        then.status(200).json_body(serde_json::json!({
+          "data": {
+            "repository": {
+              "issues": {
+                "nodes": [],
+                "pageInfo": {"hasNextPage": false, "endCursor": null}
+              }
+            }
+          }
+        }));
+    });
+    let base = server.base_url();
+    let gql = format!("{}/graphql", base);
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"list_issues","arguments": {"owner":"o","repo":"r","updated_since":"2025-02-01T00:00:00Z"}}
+    });
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_GRAPHQL_URL", gql.as_str()),
+            ("GITHUB_API_URL", base.as_str()),
+        ],
+    )?;
+    let v: serde_json::Value = serde_json::from_str(out.lines().next().unwrap())?;
+    assert_eq!(
+        v["result"]["structuredContent"]["meta"]["max_updated_at"],
+        "2025-02-01T00:00:00Z"
+    );
+    Ok(())
+}
+
+#[test]
+fn list_issues_include_counts_adds_label_and_assignee_totals() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let m = server.mock(|when, then| {
+        when.method(POST)
+            .path("/graphql")
+            .json_body_partial(r#"{"variables":{"includeCounts":true}}"#)
+            .body_contains("labels @include(if: $includeCounts) { totalCount }");
+        then.status(200).json_body(serde_json::json!({
+          "data": {
+            "repository": {
+              "issues": {
+                "nodes": [
+                  {"id":"I_1","number":1,"title":"One","state":"OPEN","createdAt":"2025-01-01T00:00:00Z","updatedAt":"2025-01-01T00:00:00Z","author": null,
+                   "labels": {"totalCount": 4}, "assignees": {"totalCount": 0}}
+                ],
+                "pageInfo": {"hasNextPage": false, "endCursor": null}
+              }
+            }
+          }
+        }));
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"list_issues","arguments": {"owner":"o","repo":"r","include_counts":true}}
+    });
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            (
+                "GITHUB_GRAPHQL_URL",
+                &format!("{}/graphql", server.base_url()),
+            ),
+        ],
+    )?;
+    m.assert();
+    let v: serde_json::Value = serde_json::from_str(out.trim())?;
+    let item = &v["result"]["structuredContent"]["items"][0];
+    assert_eq!(item["labels_count"], 4);
+    assert_eq!(item["assignees_count"], 0);
+    Ok(())
+}
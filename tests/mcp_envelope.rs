@@ -1,5 +1,8 @@
 use assert_cmd::Command;
-use httpmock::{Method::GET, MockServer};
+use httpmock::{
+    Method::{GET, POST},
+    MockServer,
+};
 use std::io::Write;
 
 fn run_with_env(req: &serde_json::Value, envs: &[(&str, &str)]) -> anyhow::Result<String> {
@@ -99,3 +102,953 @@ fn mcp_envelope_success_and_error_and_ping_gating() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn read_only_mode_hides_and_rejects_write_tools() -> anyhow::Result<()> {
+    let list_req = serde_json::json!({"jsonrpc":"2.0","method":"tools/list","id":1});
+    let list_rw = run_with_env(&list_req, &[])?;
+    assert!(list_rw.contains("\"merge_pr\""));
+    let list_ro = run_with_env(&list_req, &[("GITHUB_MCP_READ_ONLY", "true")])?;
+    assert!(!list_ro.contains("\"merge_pr\""));
+    assert!(!list_ro.contains("\"reply_to_review_comment\""));
+    assert!(list_ro.contains("\"list_issues\""));
+
+    let call = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":2,
+        "params":{"name":"merge_pr","arguments":{"owner":"o","repo":"r","number":1}}
+    });
+    let out = run_with_env(
+        &call,
+        &[("GITHUB_MCP_READ_ONLY", "1"), ("GITHUB_TOKEN", "t")],
+    )?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    assert_eq!(v["error"]["code"], -32601);
+    Ok(())
+}
+
+#[test]
+fn graphql_query_is_opt_in_and_returns_raw_errors() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let m = server.mock(|when, then| {
+        when.method(POST).path("/graphql").body_contains("viewer");
+        then.status(200).json_body(serde_json::json!({
+            "data": {"viewer": {"login": "me"}, "x": null},
+            "errors": [{"message": "nope", "path": ["x"], "type": "NOT_FOUND"}]
+        }));
+    });
+    let graphql = format!("{}/graphql", server.base_url());
+    let call = |query: &str| {
+        serde_json::json!({
+            "jsonrpc":"2.0","method":"tools/call","id":1,
+            "params":{"name":"graphql_query","arguments":{"query": query}}
+        })
+    };
+    let off = run_with_env(&call("{ viewer { login } }"), &[("GITHUB_TOKEN", "t")])?;
+    let v: serde_json::Value = serde_json::from_str(&off)?;
+    assert_eq!(v["error"]["code"], -32601);
+
+    let on = run_with_env(
+        &call("{ viewer { login } x: repository(owner:\"o\", name:\"r\") { id } }"),
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_GRAPHQL_URL", graphql.as_str()),
+            ("GITHUB_MCP_ENABLE_RAW_GRAPHQL", "1"),
+        ],
+    )?;
+    m.assert();
+    let v: serde_json::Value = serde_json::from_str(&on)?;
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(sc["data"]["viewer"]["login"], "me");
+    assert_eq!(sc["errors"][0]["path"][0], "x");
+    assert!(sc.get("error").is_none());
+
+    // Read-only mode refuses mutation documents before any request is made
+    let mutation = call("# comment mentioning mutation\nmutation M { addStar(input: {starrableId: \"x\"}) { clientMutationId } }");
+    let out = run_with_env(
+        &mutation,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_GRAPHQL_URL", graphql.as_str()),
+            ("GITHUB_MCP_ENABLE_RAW_GRAPHQL", "1"),
+            ("GITHUB_MCP_READ_ONLY", "1"),
+        ],
+    )?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    assert_eq!(v["error"]["code"], -32602);
+    m.assert_hits(1);
+
+    // A quote inside a block string must not desync the check.
+    let sneaky = server.mock(|when, then| {
+        when.method(POST).path("/graphql").body_contains("addStar");
+        then.status(200).json_body(serde_json::json!({"data": {}}));
+    });
+    let ro_envs = [
+        ("GITHUB_TOKEN", "t"),
+        ("GITHUB_GRAPHQL_URL", graphql.as_str()),
+        ("GITHUB_MCP_ENABLE_RAW_GRAPHQL", "1"),
+        ("GITHUB_MCP_READ_ONLY", "1"),
+    ];
+    for doc in [
+        "fragment F on Mutation { addStar(input:{clientMutationId: \"\"\" \" \"\"\", starrableId:\"X\"}){clientMutationId} } mutation { ...F }",
+        "query Q { a: viewer { login } } subscription S { addStar }",
+        "{ viewer { login(x: \"unterminated) } } mutation { addStar }",
+    ] {
+        let v: serde_json::Value = serde_json::from_str(&run_with_env(&call(doc), &ro_envs)?)?;
+        assert_eq!(v["error"]["code"], -32602, "{doc}");
+    }
+    sneaky.assert_hits(0);
+
+    // Names that merely look like the keyword are still queries.
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(
+        &call("query Q($mutation: String = \"\"\"mutation { addStar }\"\"\") { viewer { login } }"),
+        &ro_envs,
+    )?)?;
+    assert!(v.get("error").is_none());
+    m.assert_hits(2);
+    Ok(())
+}
+
+// Sends several NDJSON requests to one server process and returns the parsed responses.
+fn run_many(
+    reqs: &[serde_json::Value],
+    envs: &[(&str, &str)],
+) -> anyhow::Result<Vec<serde_json::Value>> {
+    let mut cmd = Command::cargo_bin("github-mcp")?;
+    cmd.env_remove("GITHUB_MCP_ENABLE_PING")
+        .env_remove("GITHUB_MCP_ENABLE_RAW_GRAPHQL")
+        .env_remove("GITHUB_MCP_ENABLE_RAW_REST")
+        .env_remove("GITHUB_MCP_READ_ONLY")
+        .env_remove("GITHUB_MCP_TOOLS_ALLOW")
+        .env_remove("GITHUB_MCP_TOOLS_DENY");
+    for (k, v) in envs {
+        cmd.env(k, v);
+    }
+    let mut b = Vec::new();
+    for r in reqs {
+        writeln!(b, "{}", serde_json::to_string(r)?)?;
+    }
+    let assert = cmd.arg("--log-level").arg("warn").write_stdin(b).assert();
+    let out = String::from_utf8(assert.get_output().stdout.clone())?;
+    out.lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| Ok(serde_json::from_str(l)?))
+        .collect()
+}
+
+#[test]
+fn tools_list_and_call_agree_for_each_gating_mode() -> anyhow::Result<()> {
+    // Every tool known when everything is enabled
+    let list_req = serde_json::json!({"jsonrpc":"2.0","method":"tools/list","id":0});
+    let all = run_many(
+        std::slice::from_ref(&list_req),
+        &[
+            ("GITHUB_MCP_ENABLE_PING", "1"),
+            ("GITHUB_MCP_ENABLE_RAW_GRAPHQL", "1"),
+            ("GITHUB_MCP_ENABLE_RAW_REST", "1"),
+        ],
+    )?;
+    let all_names: Vec<String> = all[0]["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["name"].as_str().unwrap().to_string())
+        .collect();
+    assert!(all_names.iter().any(|n| n == "ping"));
+    assert!(all_names.iter().any(|n| n == "graphql_query"));
+    assert!(all_names.iter().any(|n| n == "rest_request"));
+
+    let modes: Vec<Vec<(&str, &str)>> = vec![
+        vec![],
+        vec![("GITHUB_MCP_ENABLE_PING", "1")],
+        vec![
+            ("GITHUB_MCP_ENABLE_RAW_GRAPHQL", "1"),
+            ("GITHUB_MCP_ENABLE_RAW_REST", "1"),
+        ],
+        vec![("GITHUB_MCP_READ_ONLY", "1")],
+        vec![("GITHUB_MCP_TOOLS_ALLOW", "list_issues, get_issue,merge_pr")],
+        vec![
+            ("GITHUB_MCP_TOOLS_DENY", "list_issues,ping"),
+            ("GITHUB_MCP_ENABLE_PING", "1"),
+        ],
+        vec![
+            ("GITHUB_MCP_READ_ONLY", "true"),
+            ("GITHUB_MCP_TOOLS_ALLOW", "list_issues,merge_pr"),
+        ],
+    ];
+    for env in modes {
+        let mut reqs = vec![list_req.clone()];
+        for (i, name) in all_names.iter().enumerate() {
+            // Empty arguments: enabled tools fail validation (or config), never with -32601.
+            reqs.push(serde_json::json!({
+                "jsonrpc":"2.0","method":"tools/call","id": i + 1,
+                "params":{"name": name, "arguments": {}}
+            }));
+        }
+        let resps = run_many(&reqs, &env)?;
+        let listed: Vec<&str> = resps[0]["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["name"].as_str().unwrap())
+            .collect();
+        for (i, name) in all_names.iter().enumerate() {
+            let r = &resps[i + 1];
+            let rejected = r["error"]["code"] == -32601;
+            assert_eq!(
+                listed.contains(&name.as_str()),
+                !rejected,
+                "list/call disagree for {} under {:?}",
+                name,
+                env
+            );
+        }
+        if env.iter().any(|(k, _)| *k == "GITHUB_MCP_TOOLS_ALLOW") {
+            assert!(listed.contains(&"list_issues"));
+            assert!(!listed.contains(&"list_pull_requests"));
+        }
+        if env.iter().any(|(k, _)| *k == "GITHUB_MCP_READ_ONLY") {
+            assert!(!listed.contains(&"merge_pr"));
+        }
+        if env.iter().any(|(k, _)| *k == "GITHUB_MCP_TOOLS_DENY") {
+            assert!(!listed.contains(&"list_issues"));
+            assert!(!listed.contains(&"ping"));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn sighup_reloads_gating_from_config_file_and_notifies() -> anyhow::Result<()> {
+    use std::io::{BufRead, BufReader};
+    use std::process::{Command as StdCommand, Stdio};
+
+    let path = std::env::temp_dir().join(format!("github-mcp-gating-{}", std::process::id()));
+    std::fs::write(&path, "GITHUB_MCP_READ_ONLY=0\n")?;
+    let mut child = StdCommand::new(assert_cmd::cargo::cargo_bin("github-mcp"))
+        .args(["--log-level", "warn"])
+        .env("GITHUB_MCP_CONFIG_FILE", &path)
+        .env_remove("GITHUB_MCP_READ_ONLY")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+    let mut stdin = child.stdin.take().unwrap();
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+    let mut line = String::new();
+
+    writeln!(
+        stdin,
+        "{}",
+        serde_json::json!({"jsonrpc":"2.0","method":"initialize","id":1})
+    )?;
+    stdout.read_line(&mut line)?;
+    let init: serde_json::Value = serde_json::from_str(&line)?;
+    assert_eq!(init["result"]["capabilities"]["tools"]["listChanged"], true);
+
+    std::fs::write(&path, "GITHUB_MCP_READ_ONLY=1\n")?;
+    let status = StdCommand::new("kill")
+        .args(["-HUP", &child.id().to_string()])
+        .status()?;
+    assert!(status.success());
+    line.clear();
+    stdout.read_line(&mut line)?;
+    let note: serde_json::Value = serde_json::from_str(&line)?;
+    assert_eq!(note["method"], "notifications/tools/list_changed");
+
+    writeln!(
+        stdin,
+        "{}",
+        serde_json::json!({"jsonrpc":"2.0","method":"tools/list","id":2})
+    )?;
+    line.clear();
+    stdout.read_line(&mut line)?;
+    assert!(!line.contains("\"merge_pr\""));
+    assert!(line.contains("\"list_issues\""));
+
+    drop(stdin);
+    child.wait()?;
+    let _ = std::fs::remove_file(&path);
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn sighup_reloads_config_file() -> anyhow::Result<()> {
+    use std::io::{BufRead, BufReader};
+    use std::process::{Command as StdCommand, Stdio};
+
+    let server = MockServer::start();
+    let run = serde_json::json!({
+        "id":1,"run_number":1,"event":"push","status":"completed","conclusion":"success",
+        "head_sha":"abc","created_at":"2025-01-01T00:00:00Z","updated_at":"2025-01-01T00:00:00Z"
+    });
+    let old = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/actions/runs/1")
+            .header("authorization", "Bearer old");
+        then.status(200).json_body(run.clone());
+    });
+    let new = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/actions/runs/1")
+            .header("authorization", "Bearer new");
+        then.status(200).json_body(run.clone());
+    });
+
+    // The file drives both config and gating, so the list_changed notification marks reload.
+    let path = std::env::temp_dir().join(format!("github-mcp-config-{}", std::process::id()));
+    std::fs::write(
+        &path,
+        format!("GITHUB_TOKEN=old\nGITHUB_API_URL={}\n", server.base_url()),
+    )?;
+    let mut child = StdCommand::new(assert_cmd::cargo::cargo_bin("github-mcp"))
+        .args(["--log-level", "warn"])
+        .env("GITHUB_MCP_CONFIG_FILE", &path)
+        .env_remove("GITHUB_TOKEN")
+        .env_remove("GH_TOKEN")
+        .env_remove("GITHUB_MCP_READ_ONLY")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+    let mut stdin = child.stdin.take().unwrap();
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+    let mut line = String::new();
+    let call = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"get_workflow_run_light","arguments":{"owner":"o","repo":"r","run_id":1}}
+    });
+
+    writeln!(stdin, "{}", call)?;
+    stdout.read_line(&mut line)?;
+    assert!(line.contains("\"structuredContent\""), "{}", line);
+    old.assert_hits(1);
+
+    std::fs::write(
+        &path,
+        format!(
+            "GITHUB_TOKEN=new\nGITHUB_API_URL={}\nGITHUB_MCP_READ_ONLY=1\n",
+            server.base_url()
+        ),
+    )?;
+    let status = StdCommand::new("kill")
+        .args(["-HUP", &child.id().to_string()])
+        .status()?;
+    assert!(status.success());
+    line.clear();
+    stdout.read_line(&mut line)?;
+    assert!(line.contains("notifications/tools/list_changed"));
+
+    writeln!(stdin, "{}", call)?;
+    line.clear();
+    stdout.read_line(&mut line)?;
+    assert!(line.contains("\"structuredContent\""), "{}", line);
+    new.assert_hits(1);
+    old.assert_hits(1);
+
+    drop(stdin);
+    child.wait()?;
+    let _ = std::fs::remove_file(&path);
+    Ok(())
+}
+
+#[test]
+fn oversized_results_are_cut_to_max_response_bytes() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let diff = "+added line\n".repeat(2_000);
+    let _diff = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/pulls/1");
+        then.status(200).body(diff);
+    });
+    let workflows: Vec<serde_json::Value> = (0..500)
+        .map(|i| serde_json::json!({"id": i, "name": format!("wf{}", i), "path": ".github/workflows/x.yml", "state": "active"}))
+        .collect();
+    let _wf = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/actions/workflows");
+        then.status(200)
+            .json_body(serde_json::json!({"workflows": workflows, "total_count": 500}));
+    });
+    let base = server.base_url();
+    let envs = [
+        ("GITHUB_TOKEN", "t"),
+        ("GITHUB_API_URL", base.as_str()),
+        ("GITHUB_MCP_MAX_RESPONSE_BYTES", "4000"),
+    ];
+    for (id, name, args) in [
+        (
+            1,
+            "get_pr_diff",
+            serde_json::json!({"owner":"o","repo":"r","number":1}),
+        ),
+        (
+            2,
+            "list_workflows_light",
+            serde_json::json!({"owner":"o","repo":"r","per_page":100}),
+        ),
+    ] {
+        let req = serde_json::json!({
+            "jsonrpc":"2.0","method":"tools/call","id":id,
+            "params":{"name":name,"arguments":args}
+        });
+        let out = run_with_env(&req, &envs)?;
+        // Envelope overhead (jsonrpc/id/content wrapper) comes on top of the capped result.
+        assert!(out.len() < 4_500, "{} produced {} bytes", name, out.len());
+        let v: serde_json::Value = serde_json::from_str(out.trim())?;
+        let sc = &v["result"]["structuredContent"];
+        assert_eq!(sc["truncated"], true, "{}", name);
+    }
+    Ok(())
+}
+
+#[test]
+fn list_capabilities_covers_every_tool_with_gating() -> anyhow::Result<()> {
+    let list_req = serde_json::json!({"jsonrpc":"2.0","method":"tools/list","id":0});
+    let caps_req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"list_capabilities","arguments":{}}
+    });
+    let resps = run_many(
+        &[list_req, caps_req],
+        &[
+            ("GITHUB_MCP_ENABLE_PING", "1"),
+            ("GITHUB_MCP_ENABLE_RAW_GRAPHQL", "1"),
+            ("GITHUB_MCP_ENABLE_RAW_REST", "1"),
+        ],
+    )?;
+    let caps = &resps[1]["result"]["structuredContent"];
+    assert_eq!(caps["read_only"], false);
+    let tools: Vec<&serde_json::Value> = caps["categories"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|c| c["tools"].as_array().unwrap())
+        .collect();
+    for t in resps[0]["result"]["tools"].as_array().unwrap() {
+        let name = t["name"].as_str().unwrap();
+        assert!(
+            tools.iter().any(|c| c["name"] == name),
+            "{} missing from list_capabilities",
+            name
+        );
+    }
+    let merge = tools.iter().find(|t| t["name"] == "merge_pr").unwrap();
+    assert_eq!(merge["mutates"], true);
+    assert_eq!(merge["enabled"], true);
+    assert!(merge["scopes"]
+        .as_array()
+        .unwrap()
+        .contains(&serde_json::json!("contents:write")));
+
+    let caps_req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"list_capabilities","arguments":{"category":"pulls"}}
+    });
+    let resps = run_many(&[caps_req], &[("GITHUB_MCP_READ_ONLY", "1")])?;
+    let caps = &resps[0]["result"]["structuredContent"];
+    assert_eq!(caps["read_only"], true);
+    let cats = caps["categories"].as_array().unwrap();
+    assert_eq!(cats.len(), 1);
+    let pulls = cats[0]["tools"].as_array().unwrap();
+    let merge = pulls.iter().find(|t| t["name"] == "merge_pr").unwrap();
+    assert_eq!(merge["enabled"], false);
+    assert_eq!(merge["disabled_reason"], "read-only mode");
+    let get_pr = pulls
+        .iter()
+        .find(|t| t["name"] == "get_pull_request")
+        .unwrap();
+    assert_eq!(get_pr["mutates"], false);
+    assert_eq!(get_pr["enabled"], true);
+    Ok(())
+}
+
+#[test]
+fn tools_list_carries_mcp_annotations() -> anyhow::Result<()> {
+    let list_req = serde_json::json!({"jsonrpc":"2.0","method":"tools/list","id":0});
+    let resps = run_many(
+        std::slice::from_ref(&list_req),
+        &[("GITHUB_MCP_ENABLE_PING", "1")],
+    )?;
+    let tools = resps[0]["result"]["tools"].as_array().unwrap();
+    let ann = |name: &str| {
+        tools
+            .iter()
+            .find(|t| t["name"] == name)
+            .map(|t| t["annotations"].clone())
+            .unwrap()
+    };
+    assert!(tools.iter().all(|t| t["annotations"].is_object()));
+    assert_eq!(
+        ann("list_issues"),
+        serde_json::json!({"readOnlyHint": true, "openWorldHint": true})
+    );
+    assert_eq!(ann("merge_pr")["readOnlyHint"], false);
+    assert_eq!(ann("merge_pr")["destructiveHint"], true);
+    assert_eq!(ann("delete_comment")["destructiveHint"], true);
+    assert_eq!(ann("star_gist")["destructiveHint"], false);
+    assert_eq!(ann("star_gist")["idempotentHint"], true);
+    assert_eq!(ann("create_review_comment")["idempotentHint"], false);
+    assert_eq!(ann("ping")["openWorldHint"], false);
+    Ok(())
+}
+
+#[test]
+fn every_input_property_has_title_and_description() -> anyhow::Result<()> {
+    let list_req = serde_json::json!({"jsonrpc":"2.0","method":"tools/list","id":0});
+    let resps = run_many(
+        std::slice::from_ref(&list_req),
+        &[
+            ("GITHUB_MCP_ENABLE_PING", "1"),
+            ("GITHUB_MCP_ENABLE_RAW_GRAPHQL", "1"),
+            ("GITHUB_MCP_ENABLE_RAW_REST", "1"),
+        ],
+    )?;
+    let mut missing = Vec::new();
+    for t in resps[0]["result"]["tools"].as_array().unwrap() {
+        let Some(props) = t["inputSchema"]["properties"].as_object() else {
+            continue;
+        };
+        for (name, prop) in props {
+            for key in ["title", "description"] {
+                if prop[key].as_str().is_none_or(|s| s.is_empty()) {
+                    missing.push(format!("{}.{} {}", t["name"], name, key));
+                }
+            }
+        }
+    }
+    assert!(missing.is_empty(), "undocumented properties: {:?}", missing);
+    // Shared wording plus per-tool overrides
+    let tools = resps[0]["result"]["tools"].as_array().unwrap();
+    let prop = |tool: &str, name: &str| {
+        tools.iter().find(|t| t["name"] == tool).unwrap()["inputSchema"]["properties"][name].clone()
+    };
+    assert!(prop("list_issues", "cursor")["description"]
+        .as_str()
+        .unwrap()
+        .contains("Opaque"));
+    assert_eq!(prop("get_issue", "number")["description"], "Issue number");
+    assert_eq!(
+        prop("get_pull_request", "number")["description"],
+        "Pull request number"
+    );
+    Ok(())
+}
+
+#[test]
+fn unknown_arguments_are_rejected_and_reserved_flags_are_not() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _m = server.mock(|when, then| {
+        when.method(POST).path("/graphql");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{"data":{"repository":{"issues":{"nodes":[],"pageInfo":{"hasNextPage":false,"endCursor":null}}}}}"#);
+    });
+    let base = server.base_url();
+    let gql = format!("{}/graphql", base);
+    let call = |id: i64, args: serde_json::Value| {
+        serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":id,
+            "params":{"name":"list_issues","arguments":args}})
+    };
+    let resps = run_many(
+        &[
+            call(1, serde_json::json!({"owner":"o","repo":"r","lable":"bug"})),
+            call(
+                2,
+                serde_json::json!({"owner":"o","repo":"r","_include_rate":true,"_include_pages":true}),
+            ),
+        ],
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_API_URL", base.as_str()),
+            ("GITHUB_GRAPHQL_URL", gql.as_str()),
+        ],
+    )?;
+    assert_eq!(resps[0]["error"]["code"], -32602);
+    assert!(resps[0]["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("unknown field `lable`"));
+    assert!(resps[1]["error"].is_null(), "{}", resps[1]);
+    Ok(())
+}
+
+#[test]
+fn every_advertised_property_is_accepted_at_call_time() -> anyhow::Result<()> {
+    let gating = [
+        ("GITHUB_MCP_ENABLE_PING", "1"),
+        ("GITHUB_MCP_ENABLE_RAW_GRAPHQL", "1"),
+        ("GITHUB_MCP_ENABLE_RAW_REST", "1"),
+    ];
+    let list_req = serde_json::json!({"jsonrpc":"2.0","method":"tools/list","id":0});
+    let listed = run_many(std::slice::from_ref(&list_req), &gating)?;
+    let sample = |prop: &serde_json::Value| -> serde_json::Value {
+        if let Some(first) = prop["enum"].as_array().and_then(|e| e.first()) {
+            return first.clone();
+        }
+        let ty = match &prop["type"] {
+            serde_json::Value::Array(a) => a[0].as_str().unwrap_or("string").to_string(),
+            v => v.as_str().unwrap_or("string").to_string(),
+        };
+        match ty.as_str() {
+            "integer" | "number" => serde_json::json!(1),
+            "boolean" => serde_json::json!(true),
+            "array" => serde_json::json!([]),
+            "object" => serde_json::json!({}),
+            _ => serde_json::json!("x"),
+        }
+    };
+    // One call per property, on top of the required ones, so a type quirk in one
+    // property can't mask an unknown-field rejection of another.
+    let mut reqs = Vec::new();
+    let mut labels = Vec::new();
+    for t in listed[0]["result"]["tools"].as_array().unwrap() {
+        let schema = &t["inputSchema"];
+        let Some(props) = schema["properties"].as_object() else {
+            continue;
+        };
+        let required: Vec<&str> = schema["required"]
+            .as_array()
+            .map(|r| r.iter().filter_map(|k| k.as_str()).collect())
+            .unwrap_or_default();
+        for name in props.keys() {
+            let mut args = serde_json::Map::new();
+            for k in required
+                .iter()
+                .copied()
+                .chain(std::iter::once(name.as_str()))
+            {
+                args.insert(k.to_string(), sample(&props[k]));
+            }
+            labels.push(format!("{}.{}", t["name"].as_str().unwrap(), name));
+            reqs.push(serde_json::json!({"jsonrpc":"2.0","method":"tools/call",
+                "id":reqs.len() + 1,
+                "params":{"name":t["name"],"arguments":args}}));
+        }
+    }
+    // Calls that get past argument parsing hit an empty mock and fail with 404s.
+    let server = MockServer::start();
+    let base = server.base_url();
+    let gql = format!("{}/graphql", base);
+    let mut envs = gating.to_vec();
+    envs.extend([
+        ("GITHUB_TOKEN", "t"),
+        ("GITHUB_API_URL", base.as_str()),
+        ("GITHUB_GRAPHQL_URL", gql.as_str()),
+    ]);
+    let resps = run_many(&reqs, &envs)?;
+    assert_eq!(resps.len(), reqs.len());
+    let rejected: Vec<&String> = resps
+        .iter()
+        .filter(|r| {
+            r["error"]["message"]
+                .as_str()
+                .is_some_and(|m| m.contains("unknown field"))
+        })
+        .filter_map(|r| r["id"].as_u64().map(|i| &labels[i as usize - 1]))
+        .collect();
+    assert!(
+        rejected.is_empty(),
+        "advertised but rejected: {:?}",
+        rejected
+    );
+    Ok(())
+}
+
+#[test]
+fn call_deadline_env_caps_a_retrying_call() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _down = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r");
+        then.status(503);
+    });
+    let base = server.base_url();
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"get_repo","arguments":{"owner":"o","repo":"r"}}
+    });
+    let start = std::time::Instant::now();
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_API_URL", base.as_str()),
+            ("GITHUB_MCP_CALL_DEADLINE_SECS", "1"),
+        ],
+    )?;
+    assert!(start.elapsed() < std::time::Duration::from_secs(4));
+    let v: serde_json::Value = serde_json::from_str(out.trim())?;
+    assert_eq!(v["result"]["isError"], true);
+    assert_eq!(
+        v["result"]["structuredContent"]["error"]["code"],
+        "deadline_exceeded"
+    );
+    Ok(())
+}
+
+#[test]
+fn timeout_secs_flag_bounds_a_long_wait() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _running = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/actions/runs/7");
+        then.status(200).json_body(serde_json::json!({
+            "id": 7, "run_number": 1, "event": "push", "status": "in_progress",
+            "conclusion": null, "head_sha": "abc",
+            "created_at": "2024-01-01T00:00:00Z", "updated_at": "2024-01-01T00:00:00Z"
+        }));
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"wait_for_workflow_run","arguments":{
+            "owner":"o","repo":"r","run_id":7,"timeout_secs":60,"_timeout_secs":1}}
+    });
+    let start = std::time::Instant::now();
+    let out = run_with_env(&req, &envs)?;
+    assert!(start.elapsed() < std::time::Duration::from_secs(5));
+    let v: serde_json::Value = serde_json::from_str(out.trim())?;
+    assert_eq!(v["result"]["isError"], true);
+    let err = &v["result"]["structuredContent"]["error"];
+    assert_eq!(err["code"], "timeout");
+    assert_eq!(err["retriable"], true);
+
+    // Anything but a positive integer is rejected before any request.
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":2,
+        "params":{"name":"get_repo","arguments":{"owner":"o","repo":"r","_timeout_secs":0}}
+    });
+    let out = run_with_env(&req, &envs)?;
+    let v: serde_json::Value = serde_json::from_str(out.trim())?;
+    assert_eq!(v["error"]["code"], -32602);
+
+    // A value that would overflow the clock is rejected, not a crash.
+    let huge = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":3,
+        "params":{"name":"get_repo","arguments":{"owner":"o","repo":"r","_timeout_secs":u64::MAX}}
+    });
+    let out = run_with_env(&huge, &envs)?;
+    let v: serde_json::Value = serde_json::from_str(out.trim())?;
+    assert_eq!(v["error"]["code"], -32602);
+
+    // The client can shorten the operator's budget but not extend it.
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":4,
+        "params":{"name":"wait_for_workflow_run","arguments":{
+            "owner":"o","repo":"r","run_id":7,"timeout_secs":60,"_timeout_secs":60}}
+    });
+    let start = std::time::Instant::now();
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_API_URL", base.as_str()),
+            ("GITHUB_MCP_CALL_DEADLINE_SECS", "1"),
+        ],
+    )?;
+    assert!(start.elapsed() < std::time::Duration::from_secs(5));
+    let v: serde_json::Value = serde_json::from_str(out.trim())?;
+    assert_eq!(
+        v["result"]["structuredContent"]["error"]["code"],
+        "deadline_exceeded"
+    );
+    Ok(())
+}
+
+#[test]
+fn list_items_are_an_array_on_success_and_null_on_error() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _empty = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/empty/tags");
+        then.status(200).json_body(serde_json::json!([]));
+    });
+    // No body at all: handlers see no value, yet the contract still promises an array.
+    let _no_content = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/blank/tags");
+        then.status(204);
+    });
+    let _missing = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/gone/tags");
+        then.status(404)
+            .json_body(serde_json::json!({"message": "Not Found"}));
+    });
+    let _no_repo = server.mock(|when, then| {
+        when.method(POST).path("/graphql");
+        then.status(200)
+            .json_body(serde_json::json!({"data": {"repository": null}}));
+    });
+    let base = server.base_url();
+    let gql = format!("{}/graphql", base);
+    let call = |id: i64, name: &str, repo: &str| {
+        serde_json::json!({
+            "jsonrpc":"2.0","method":"tools/call","id":id,
+            "params":{"name":name,"arguments":{"owner":"o","repo":repo}}
+        })
+    };
+    let resps = run_many(
+        &[
+            call(1, "list_tags", "empty"),
+            call(2, "list_tags", "blank"),
+            call(3, "list_tags", "gone"),
+            call(4, "list_issues", "r"),
+        ],
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_API_URL", base.as_str()),
+            ("GITHUB_GRAPHQL_URL", gql.as_str()),
+        ],
+    )?;
+    for r in &resps[..2] {
+        let sc = &r["result"]["structuredContent"];
+        assert_eq!(sc["items"], serde_json::json!([]), "{}", r);
+        assert!(sc.get("error").is_none());
+    }
+    for r in &resps[2..] {
+        let sc = &r["result"]["structuredContent"];
+        assert!(sc["items"].is_null(), "{}", r);
+        assert_eq!(sc["error"]["code"], "not_found");
+    }
+    Ok(())
+}
+
+#[test]
+fn a_burst_of_sequential_calls_all_succeed_in_one_process() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let tags = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/tags");
+        then.status(200).json_body(serde_json::json!([{
+            "name": "v1", "commit": {"sha": "abc", "url": "u"},
+            "zipball_url": "z", "tarball_url": "t"
+        }]));
+    });
+    let base = server.base_url();
+    let reqs: Vec<_> = (0..500)
+        .map(|i| {
+            let (name, args) = if i % 2 == 0 {
+                ("ping", serde_json::json!({}))
+            } else {
+                ("list_tags", serde_json::json!({"owner":"o","repo":"r"}))
+            };
+            serde_json::json!({
+                "jsonrpc":"2.0","method":"tools/call","id":i,
+                "params":{"name":name,"arguments":args}
+            })
+        })
+        .collect();
+    let resps = run_many(
+        &reqs,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_API_URL", base.as_str()),
+            ("GITHUB_MCP_ENABLE_PING", "1"),
+        ],
+    )?;
+    assert_eq!(resps.len(), 500);
+    for r in &resps {
+        assert!(r.get("error").is_none(), "{}", r);
+        assert!(r["result"].get("isError").is_none(), "{}", r);
+    }
+    tags.assert_hits(250);
+    Ok(())
+}
+
+#[test]
+fn batch_runs_sub_calls_in_order_with_per_call_gating() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _tags = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/tags");
+        then.status(200).json_body(serde_json::json!([{
+            "name": "v1", "commit": {"sha": "abc", "url": "u"},
+            "zipball_url": "z", "tarball_url": "t"
+        }]));
+    });
+    let _gone = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/gone/tags");
+        then.status(404)
+            .json_body(serde_json::json!({"message": "Not Found"}));
+    });
+    let base = server.base_url();
+    let batch = |id: i64, calls: serde_json::Value| {
+        serde_json::json!({
+            "jsonrpc":"2.0","method":"tools/call","id":id,
+            "params":{"name":"batch","arguments":{"calls":calls}}
+        })
+    };
+    let too_many: Vec<_> = (0..21)
+        .map(|_| serde_json::json!({"name":"list_tags","arguments":{"owner":"o","repo":"r"}}))
+        .collect();
+    let resps = run_many(
+        &[
+            batch(
+                1,
+                serde_json::json!([
+                    {"name":"list_tags","arguments":{"owner":"o","repo":"r"}},
+                    {"name":"list_tags","arguments":{"owner":"o","repo":"gone"}},
+                    {"name":"add_issue_comment","arguments":{"owner":"o","repo":"r","number":1,"body":"x"}},
+                    {"name":"no_such_tool"}
+                ]),
+            ),
+            batch(2, serde_json::Value::Array(too_many)),
+            batch(
+                3,
+                serde_json::json!([{"name":"batch","arguments":{"calls":[]}}]),
+            ),
+        ],
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_API_URL", base.as_str()),
+            ("GITHUB_MCP_READ_ONLY", "1"),
+        ],
+    )?;
+    let items = &resps[0]["result"]["structuredContent"]["items"];
+    assert_eq!(items.as_array().unwrap().len(), 4);
+    assert_eq!(items[0]["name"], "list_tags");
+    assert_eq!(items[0]["is_error"], false);
+    assert_eq!(items[0]["result"]["items"][0]["name"], "v1");
+    assert_eq!(items[1]["is_error"], true);
+    assert_eq!(items[1]["result"]["error"]["code"], "not_found");
+    // Read-only mode still applies inside a batch.
+    assert_eq!(items[2]["rpc_error"]["code"], -32601);
+    assert!(items[2]["rpc_error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("read-only mode"));
+    assert_eq!(items[3]["rpc_error"]["code"], -32601);
+    assert_eq!(resps[1]["error"]["code"], -32602);
+    assert_eq!(resps[2]["error"]["code"], -32602);
+    Ok(())
+}
+
+#[test]
+fn batch_sub_calls_share_the_batch_deadline() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _hung = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r");
+        then.status(200)
+            .delay(std::time::Duration::from_secs(30))
+            .json_body(serde_json::json!({}));
+    });
+    let base = server.base_url();
+    let get_repo = serde_json::json!({"name":"get_repo","arguments":{"owner":"o","repo":"r"}});
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"batch","arguments":{
+            "calls":[get_repo, get_repo, get_repo],
+            "_timeout_secs":2}}
+    });
+    let start = std::time::Instant::now();
+    let out = run_with_env(
+        &req,
+        &[("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())],
+    )?;
+    // One 2s budget for the whole batch, not 2s per sub-call.
+    assert!(start.elapsed() < std::time::Duration::from_secs(5));
+    let v: serde_json::Value = serde_json::from_str(out.trim())?;
+    let items = v["result"]["structuredContent"]["items"]
+        .as_array()
+        .unwrap();
+    assert_eq!(items.len(), 3);
+    for item in items {
+        assert_eq!(item["result"]["error"]["code"], "timeout");
+    }
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use assert_cmd::Command;
+use httpmock::{Method::GET, MockServer};
+use std::io::Write;
+
+fn run_with_env(req: &serde_json::Value, envs: &[(&str, &str)]) -> anyhow::Result<String> {
+    let mut cmd = Command::cargo_bin("github-mcp")?;
+    for (k, v) in envs {
+        cmd.env(k, v);
+    }
+    let input = serde_json::to_string(req)?;
+    let assert = cmd
+        .arg("--log-level")
+        .arg("warn")
+        .write_stdin({
+            let mut b = Vec::new();
+            writeln!(b, "{}", input).unwrap();
+            b
+        })
+        .assert();
+    let output = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    Ok(output)
+}
+
+fn pr_payload() -> serde_json::Value {
+    serde_json::json!({
+        "number": 1, "title": "PR", "state": "open", "draft": false,
+        "user": {"login": "alice"},
+        "head": {"sha": "abc"}, "base": {"label": "o:main", "ref": "main"},
+        "commits": 1, "changed_files": 1, "additions": 2, "deletions": 1,
+        "assignees": [{"login": "bob"}, {"login": "carol"}],
+        "labels": [{"name": "bug"}, {"name": "p1"}],
+        "milestone": {"number": 3, "title": "v1.0"}
+    })
+}
+
+#[test]
+fn pr_summary_reviewers_and_assignees() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _pr = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/pulls/1");
+        then.status(200).json_body(pr_payload());
+    });
+    let rr = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/pulls/1/requested_reviewers");
+        then.status(200).json_body(serde_json::json!({
+            "users": [{"login": "dave"}],
+            "teams": [{"slug": "core"}]
+        }));
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"pr_summary","arguments": {
+            "owner":"o","repo":"r","number":1,
+            "include_checks":false,"include_files":false,"include_reviews":false,
+            "include_reviewers":true,"include_assignees":true
+        }}
+    });
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_API_URL", server.base_url().as_str()),
+        ],
+    )?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let item = &v["result"]["structuredContent"]["item"];
+    assert_eq!(item["requested_reviewers"]["users"][0], "dave");
+    assert_eq!(item["requested_reviewers"]["teams"][0], "core");
+    assert_eq!(item["assignees"], serde_json::json!(["bob", "carol"]));
+    rr.assert();
+    Ok(())
+}
+
+#[test]
+fn pr_summary_omits_reviewers_by_default() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _pr = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/pulls/1");
+        then.status(200).json_body(pr_payload());
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"pr_summary","arguments": {
+            "owner":"o","repo":"r","number":1,
+            "include_checks":false,"include_files":false,"include_reviews":false
+        }}
+    });
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_API_URL", server.base_url().as_str()),
+        ],
+    )?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let item = &v["result"]["structuredContent"]["item"];
+    assert!(item.get("requested_reviewers").is_none());
+    assert!(item.get("assignees").is_none());
+    Ok(())
+}
+
+#[test]
+fn pr_summary_labels_and_milestone() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _pr = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/pulls/1");
+        then.status(200).json_body(pr_payload());
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"pr_summary","arguments": {
+            "owner":"o","repo":"r","number":1,
+            "include_checks":false,"include_files":false,"include_reviews":false
+        }}
+    });
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_API_URL", server.base_url().as_str()),
+        ],
+    )?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let item = &v["result"]["structuredContent"]["item"];
+    assert_eq!(item["labels"], serde_json::json!(["bug", "p1"]));
+    assert_eq!(item["milestone"], "v1.0");
+    Ok(())
+}
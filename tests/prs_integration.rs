@@ -25,10 +25,12 @@ fn run_with_env(req: &serde_json::Value, envs: &[(&str, &str)]) -> anyhow::Resul
 fn list_pull_requests_happy_path() -> anyhow::Result<()> {
     let server = MockServer::start();
     let body = serde_json::json!({
-      "data": {"repository": {"pullRequests": {"nodes": [{"id":"PR_1","number":1,"title":"PR One","state":"OPEN","createdAt":"2025-01-01T00:00:00Z","updatedAt":"2025-01-01T00:00:00Z","author":{"login":"alice"}}],"pageInfo":{"hasNextPage":false,"endCursor":null}}}}
+      "data": {"repository": {"pullRequests": {"nodes": [{"id":"PR_1","number":1,"title":"PR One","state":"OPEN","createdAt":"2025-01-01T00:00:00Z","updatedAt":"2025-01-01T00:00:00Z","url":"https://github.com/o/r/pull/1","author":{"login":"alice"}}],"pageInfo":{"hasNextPage":false,"endCursor":null}}}}
     });
     let _m = server.mock(|when, then| {
-        when.method(POST).path("/graphql");
+        when.method(POST)
+            .path("/graphql")
+            .body_contains("updatedAt url");
         then.status(200).json_body(body);
     });
     let req = serde_json::json!({
@@ -49,6 +51,7 @@ fn list_pull_requests_happy_path() -> anyhow::Result<()> {
     assert!(out.contains("\"structuredContent\""));
     assert!(out.contains("\"items\""));
     assert!(out.contains("\"author_login\":\"alice\""));
+    assert!(out.contains("\"html_url\":\"https://github.com/o/r/pull/1\""));
     Ok(())
 }
 
@@ -56,10 +59,12 @@ fn list_pull_requests_happy_path() -> anyhow::Result<()> {
 fn get_pull_request_happy_path() -> anyhow::Result<()> {
     let server = MockServer::start();
     let body = serde_json::json!({
-      "data": {"repository": {"pullRequest": {"id":"PR_1","number":1,"title":"PR One","body":"b","state":"OPEN","isDraft":false,"merged":false,"mergedAt":null,"createdAt":"2025-01-01T00:00:00Z","updatedAt":"2025-01-01T00:00:00Z","author":{"login":"alice"}}}}
+      "data": {"repository": {"pullRequest": {"id":"PR_1","number":1,"title":"PR One","body":"b","state":"OPEN","isDraft":false,"merged":false,"mergedAt":null,"createdAt":"2025-01-01T00:00:00Z","updatedAt":"2025-01-01T00:00:00Z","url":"https://github.com/o/r/pull/1","author":{"login":"alice"}}}}
     });
     let _m = server.mock(|when, then| {
-        when.method(POST).path("/graphql");
+        when.method(POST)
+            .path("/graphql")
+            .body_contains("updatedAt url");
         then.status(200).json_body(body);
     });
     let req = serde_json::json!({
@@ -80,5 +85,42 @@ fn get_pull_request_happy_path() -> anyhow::Result<()> {
     assert!(out.contains("\"structuredContent\""));
     assert!(out.contains("\"item\""));
     assert!(out.contains("\"author_login\":\"alice\""));
+    assert!(out.contains("\"html_url\":\"https://github.com/o/r/pull/1\""));
+    Ok(())
+}
+
+#[test]
+fn list_pull_requests_include_body_truncates() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let body = serde_json::json!({
+      "data": {"repository": {"pullRequests": {"nodes": [{"id":"PR_1","number":1,"title":"PR One","state":"OPEN","createdAt":"2025-01-01T00:00:00Z","updatedAt":"2025-01-01T00:00:00Z","author":null,"body":"héllo world"}],"pageInfo":{"hasNextPage":false,"endCursor":null}}}}
+    });
+    let m = server.mock(|when, then| {
+        when.method(POST)
+            .path("/graphql")
+            .body_contains("\"includeBody\":true");
+        then.status(200).json_body(body);
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"list_pull_requests","arguments": {"owner":"o","repo":"r","include_body":true,"body_max_chars":5}}
+    });
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            (
+                "GITHUB_GRAPHQL_URL",
+                &format!("{}/graphql", server.base_url()),
+            ),
+            ("GITHUB_API_URL", server.base_url().as_str()),
+        ],
+    )?;
+    m.assert();
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    assert_eq!(
+        v["result"]["structuredContent"]["items"][0]["body"],
+        "héllo"
+    );
     Ok(())
 }
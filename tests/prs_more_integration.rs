@@ -1,5 +1,5 @@
 use assert_cmd::Command;
-use httpmock::{Method::GET, Method::POST, MockServer};
+use httpmock::{Method::DELETE, Method::GET, Method::PATCH, Method::POST, MockServer};
 use std::io::Write;
 
 fn run_with_env(req: &serde_json::Value, envs: &[(&str, &str)]) -> anyhow::Result<String> {
@@ -186,7 +186,7 @@ fn list_pr_review_comments_plain_pagination_and_flags() -> anyhow::Result<()> {
             .header("link", format!("<{}/repos/o/r/pulls/1/comments?page=2>; rel=\"next\"", server.base_url()))
             .json_body(serde_json::json!([
                 {"id": 11, "body": "c1", "created_at":"2025-01-01T00:00:00Z","updated_at":"2025-01-01T00:00:00Z","user":{"login":"alice"},"path":"f.rs","line":10,"start_line":9,"side":"right","start_side":"left","original_line":8,"original_start_line":7,"diff_hunk":"@@","commit_id":"abc","original_commit_id":"def"},
-                {"id": 12, "body": "c2", "created_at":"2025-01-01T00:00:00Z","updated_at":"2025-01-01T00:00:00Z","user":null}
+                {"id": 12, "body": "c2", "created_at":"2025-01-01T00:00:00Z","updated_at":"2025-01-01T00:00:00Z","user":null,"in_reply_to_id":11,"pull_request_review_id":7}
             ]));
     });
     let req1 = serde_json::json!({
@@ -210,6 +210,10 @@ fn list_pr_review_comments_plain_pagination_and_flags() -> anyhow::Result<()> {
     assert_eq!(sc1["items"][0]["author_login"].as_str().unwrap(), "alice");
     assert_eq!(sc1["items"][0]["side"].as_str().unwrap(), "RIGHT");
     assert_eq!(sc1["items"][0]["start_side"].as_str().unwrap(), "LEFT");
+    assert_eq!(sc1["items"][0]["database_id"], 11);
+    assert!(sc1["items"][0].get("in_reply_to_id").is_none());
+    assert_eq!(sc1["items"][1]["in_reply_to_id"], 11);
+    assert_eq!(sc1["items"][1]["pull_request_review_id"], 7);
     assert!(sc1["meta"]["has_more"].as_bool().unwrap());
     let cursor = sc1["meta"]["next_cursor"].as_str().unwrap().to_string();
 
@@ -283,3 +287,680 @@ fn get_pr_diff_and_patch_rest_headers() -> anyhow::Result<()> {
     assert!(out2.contains("\"structuredContent\""));
     Ok(())
 }
+
+#[test]
+fn list_prs_for_commit_preview_accept() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let m = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/commits/abc123/pulls")
+            .header("accept", "application/vnd.github.groot-preview+json");
+        then.status(200).json_body(serde_json::json!([
+            {"number": 7, "title": "Fix", "state": "closed", "merged_at": "2025-01-02T00:00:00Z"},
+            {"number": 8, "title": "Draft", "state": "open", "merged_at": null}
+        ]));
+    });
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"list_prs_for_commit","arguments":{"owner":"o","repo":"r","sha":"abc123"}}});
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_API_URL", server.base_url().as_str()),
+        ],
+    )?;
+    m.assert();
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let items = &v["result"]["structuredContent"]["items"];
+    assert_eq!(items[0]["merged"], true);
+    assert_eq!(items[1]["merged"], false);
+    Ok(())
+}
+
+#[test]
+fn reply_to_review_comment_posts_reply() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let m = server.mock(|when, then| {
+        when.method(POST)
+            .path("/repos/o/r/pulls/1/comments/11/replies")
+            .json_body(serde_json::json!({"body":"done"}));
+        then.status(201).json_body(serde_json::json!({
+            "id": 21, "node_id": "PRRC_21", "created_at": "2025-01-02T03:04:05+00:00", "in_reply_to_id": 11
+        }));
+    });
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"reply_to_review_comment","arguments":{"owner":"o","repo":"r","number":1,"comment_id":11,"body":"done"}}});
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_API_URL", server.base_url().as_str()),
+        ],
+    )?;
+    m.assert();
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let item = &v["result"]["structuredContent"]["item"];
+    assert_eq!(item["id"], "PRRC_21");
+    assert_eq!(item["database_id"], 21);
+    assert_eq!(item["in_reply_to_id"], 11);
+    assert_eq!(item["created_at"], "2025-01-02T03:04:05Z");
+    Ok(())
+}
+
+#[test]
+fn create_review_comment_maps_side_and_range() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let m = server.mock(|when, then| {
+        when.method(POST)
+            .path("/repos/o/r/pulls/1/comments")
+            .json_body(serde_json::json!({
+                "body":"nit","commit_id":"abc","path":"src/a.rs","line":12,"side":"RIGHT","start_line":10,"start_side":"RIGHT"
+            }));
+        then.status(201).json_body(serde_json::json!({
+            "id": 31, "node_id": "PRRC_31", "created_at": "2025-01-01T00:00:00Z",
+            "path": "src/a.rs", "line": 12, "start_line": 10, "side": "RIGHT", "start_side": "RIGHT"
+        }));
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"create_review_comment","arguments":{"owner":"o","repo":"r","number":1,"body":"nit","commit_id":"abc","path":"src/a.rs","line":12,"start_line":10,"side":"right","start_side":"right"}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    m.assert();
+    let item = &v["result"]["structuredContent"]["item"];
+    assert_eq!(item["database_id"], 31);
+    assert_eq!(item["path"], "src/a.rs");
+    assert_eq!(item["start_line"], 10);
+    assert_eq!(item["side"], "RIGHT");
+
+    let bad = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":2,"params":{"name":"create_review_comment","arguments":{"owner":"o","repo":"r","number":1,"body":"nit","commit_id":"abc","path":"src/a.rs","line":12,"side":"middle"}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&bad, &envs)?)?;
+    assert_eq!(v["error"]["code"], -32602);
+    Ok(())
+}
+
+#[test]
+fn update_and_delete_comment_pick_endpoint_by_type() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let m_patch = server.mock(|when, then| {
+        when.method(PATCH)
+            .path("/repos/o/r/issues/comments/5")
+            .json_body(serde_json::json!({"body":"edited"}));
+        then.status(200).json_body(serde_json::json!({
+            "id": 5, "node_id": "IC_5", "body": "edited", "updated_at": "2025-01-01T00:00:00Z"
+        }));
+    });
+    let m_delete = server.mock(|when, then| {
+        when.method(DELETE).path("/repos/o/r/pulls/comments/6");
+        then.status(204);
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"update_comment","arguments":{"owner":"o","repo":"r","comment_id":5,"body":"edited"}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    m_patch.assert();
+    assert_eq!(v["result"]["structuredContent"]["item"]["body"], "edited");
+    assert_eq!(v["result"]["structuredContent"]["item"]["id"], "IC_5");
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":2,"params":{"name":"delete_comment","arguments":{"owner":"o","repo":"r","comment_id":6,"comment_type":"review"}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    m_delete.assert();
+    assert_eq!(v["result"]["structuredContent"]["ok"], true);
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":3,"params":{"name":"delete_comment","arguments":{"owner":"o","repo":"r","comment_id":6,"comment_type":"commit"}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    assert_eq!(v["error"]["code"], -32602);
+    Ok(())
+}
+
+#[test]
+fn find_pull_request_qualifies_head_and_returns_null_when_absent() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let found = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/pulls")
+            .query_param("state", "open")
+            .query_param("head", "o:feature/x")
+            .query_param("base", "main");
+        then.status(200).json_body(serde_json::json!([
+            {"node_id":"PR_9","number":9,"title":"Feature","state":"open",
+             "created_at":"2025-01-01T00:00:00Z","updated_at":"2025-01-02T00:00:00Z","user":{"login":"u"}}
+        ]));
+    });
+    let none = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/pulls")
+            .query_param("head", "fork:other");
+        then.status(200).json_body(serde_json::json!([]));
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"find_pull_request","arguments":{"owner":"o","repo":"r","head":"feature/x","base":"main"}}});
+    let out = run_with_env(&req, &envs)?;
+    found.assert();
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(sc["number"], 9);
+    assert_eq!(sc["item"]["id"], "PR_9");
+    assert_eq!(sc["item"]["state"], "OPEN");
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":2,"params":{"name":"find_pull_request","arguments":{"owner":"o","repo":"r","head":"fork:other"}}});
+    let out = run_with_env(&req, &envs)?;
+    none.assert();
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let sc = &v["result"]["structuredContent"];
+    assert!(sc["number"].is_null());
+    assert!(sc["item"].is_null());
+    Ok(())
+}
+
+#[test]
+fn resolve_pr_review_thread_by_comment_id() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let lookup = server.mock(|when, then| {
+        when.method(POST)
+            .path("/graphql")
+            .body_contains("ResolveThreadId");
+        then.status(200).json_body(serde_json::json!({
+          "data": {"repository": {"pullRequest": {"reviewThreads": {
+            "nodes": [
+              {"id":"PRRT_a","comments":{"nodes":[{"databaseId":10}]}},
+              {"id":"PRRT_b","comments":{"nodes":[{"databaseId":11},{"databaseId":12}]}}
+            ],
+            "pageInfo": {"hasNextPage": false, "endCursor": null}
+          }}}}
+        }));
+    });
+    let mutation = server.mock(|when, then| {
+        when.method(POST)
+            .path("/graphql")
+            .body_contains("resolveReviewThread")
+            .body_contains("PRRT_b");
+        then.status(200).json_body(serde_json::json!({
+          "data": {"resolveReviewThread": {"thread": {"id":"PRRT_b","isResolved":true}}}
+        }));
+    });
+    let base = server.base_url();
+    let graphql = format!("{}/graphql", base);
+    let envs = [
+        ("GITHUB_TOKEN", "t"),
+        ("GITHUB_GRAPHQL_URL", graphql.as_str()),
+        ("GITHUB_API_URL", base.as_str()),
+    ];
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"resolve_pr_review_thread","arguments":{"owner":"o","repo":"r","number":5,"comment_id":12}}});
+    let out = run_with_env(&req, &envs)?;
+    lookup.assert();
+    mutation.assert();
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(sc["thread_id"], "PRRT_b");
+    assert_eq!(sc["is_resolved"], true);
+
+    // Neither thread_id nor a full comment reference is a params error
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":2,"params":{"name":"resolve_pr_review_thread","arguments":{"owner":"o","repo":"r","number":5}}});
+    let out = run_with_env(&req, &envs)?;
+    assert!(out.contains("-32602"));
+    Ok(())
+}
+
+#[test]
+fn list_pr_reviews_include_body_truncates() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let m = server.mock(|when, then| {
+        when.method(POST)
+            .path("/graphql")
+            .body_contains("\"includeBody\":true");
+        then.status(200).json_body(serde_json::json!({
+            "data": {
+                "repository": {
+                    "pullRequest": {
+                        "reviews": {
+                            "nodes": [
+                                {"id":"R1","databaseId":77,"state":"CHANGES_REQUESTED","submittedAt":"2024-01-01T00:00:00Z","author":{"login":"bob"},"body":"Please rename this helper"}
+                            ],
+                            "pageInfo": { "hasNextPage": false, "endCursor": null }
+                        }
+                    }
+                }
+            }
+        }));
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"list_pr_reviews","arguments": {"owner":"o","repo":"r","number":1,"include_body":true,"body_max_chars":6}}
+    });
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            (
+                "GITHUB_GRAPHQL_URL",
+                &format!("{}/graphql", server.base_url()),
+            ),
+        ],
+    )?;
+    m.assert();
+    assert!(out.contains("\"body\":\"Please\""));
+    assert!(out.contains("\"state\":\"CHANGES_REQUESTED\""));
+    assert!(out.contains("\"database_id\":77"));
+    Ok(())
+}
+
+#[test]
+fn get_pr_review_returns_review_and_paginated_comments() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let review = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/pulls/1/reviews/77");
+        then.status(200).json_body(serde_json::json!({
+            "id": 77, "node_id": "PRR_1", "state": "CHANGES_REQUESTED", "body": "A couple of nits",
+            "user": {"login": "bob"}, "submitted_at": "2024-01-01T00:00:00Z", "commit_id": "abc",
+            "html_url": "https://github.com/o/r/pull/1#pullrequestreview-77"
+        }));
+    });
+    let comments = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/pulls/1/reviews/77/comments")
+            .query_param("per_page", "1")
+            .query_param("page", "1");
+        then.status(200)
+            .header(
+                "Link",
+                "<https://api.github.com/repos/o/r/pulls/1/reviews/77/comments?per_page=1&page=2>; rel=\"next\"",
+            )
+            .json_body(serde_json::json!([
+                {"id": 5, "node_id": "PRRC_5", "body": "rename", "user": {"login": "bob"},
+                 "created_at": "2024-01-01T00:00:00Z", "updated_at": "2024-01-01T00:00:00Z",
+                 "path": "src/lib.rs", "line": 12, "pull_request_review_id": 77}
+            ]));
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"get_pr_review","arguments": {"owner":"o","repo":"r","number":1,"review_id":77,"limit":1}}
+    });
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_API_URL", server.base_url().as_str()),
+        ],
+    )?;
+    review.assert();
+    comments.assert();
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(sc["item"]["database_id"], 77);
+    assert_eq!(sc["item"]["state"], "CHANGES_REQUESTED");
+    assert_eq!(sc["item"]["author_login"], "bob");
+    assert_eq!(sc["items"][0]["path"], "src/lib.rs");
+    assert_eq!(sc["items"][0]["line"], 12);
+    assert_eq!(sc["items"][0]["author_login"], "bob");
+    assert_eq!(sc["meta"]["has_more"], true);
+    assert!(sc["meta"]["next_cursor"].is_string());
+    Ok(())
+}
+
+#[test]
+fn pr_comment_lists_filter_by_since() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _gql = server.mock(|when, then| {
+        when.method(POST).path("/graphql");
+        then.status(200).json_body(serde_json::json!({
+          "data": {"repository": {"pullRequest": {"comments": {"nodes": [
+            {"id":"IC_old","body":"old","createdAt":"2025-01-01T00:00:00Z","updatedAt":"2025-01-01T00:00:00Z","author":null},
+            {"id":"IC_new","body":"new","createdAt":"2025-02-01T00:00:00Z","updatedAt":"2025-02-01T00:00:00Z","author":null}
+          ],"pageInfo": {"hasNextPage": false, "endCursor": null}}}}}
+        }));
+    });
+    let rest = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/pulls/1/comments")
+            .query_param("since", "2025-01-15T00:00:00Z");
+        then.status(200).json_body(serde_json::json!([]));
+    });
+    let base = server.base_url();
+    let graphql = format!("{}/graphql", base);
+    let envs = [
+        ("GITHUB_TOKEN", "t"),
+        ("GITHUB_GRAPHQL_URL", graphql.as_str()),
+        ("GITHUB_API_URL", base.as_str()),
+    ];
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"list_pr_comments_plain","arguments": {"owner":"o","repo":"r","number":1,"since":"2025-01-15T00:00:00Z"}}
+    });
+    let out = run_with_env(&req, &envs)?;
+    assert!(out.contains("IC_new"));
+    assert!(!out.contains("IC_old"));
+
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":2,
+        "params":{"name":"list_pr_review_comments","arguments": {"owner":"o","repo":"r","number":1,"since":"2025-01-15T00:00:00Z"}}
+    });
+    let out = run_with_env(&req, &envs)?;
+    rest.assert();
+    assert!(out.contains("\"items\":[]"));
+
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":3,
+        "params":{"name":"list_pr_comments_plain","arguments": {"owner":"o","repo":"r","number":1,"since":"last week"}}
+    });
+    let out = run_with_env(&req, &envs)?;
+    assert!(out.contains("-32602"));
+    Ok(())
+}
+
+#[test]
+fn get_pr_diff_single_file() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let diff = "diff --git a/src/a.rs b/src/a.rs\nindex 1..2 100644\n--- a/src/a.rs\n+++ b/src/a.rs\n@@ -1 +1 @@\n-old_a\n+new_a\ndiff --git a/src/b.rs b/src/b.rs\nindex 3..4 100644\n--- a/src/b.rs\n+++ b/src/b.rs\n@@ -1 +1 @@\n-old_b\n+new_b\n";
+    let _m = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/pulls/1");
+        then.status(200).body(diff);
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"get_pr_diff","arguments":{"owner":"o","repo":"r","number":1,"path":"src/b.rs"}}});
+    let out = run_with_env(&req, &envs)?;
+    assert!(out.contains("new_b"));
+    assert!(!out.contains("new_a"));
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":2,"params":{"name":"get_pr_diff","arguments":{"owner":"o","repo":"r","number":1,"path":"src/c.rs"}}});
+    let out = run_with_env(&req, &envs)?;
+    assert!(out.contains("\"code\":\"not_found\""));
+    Ok(())
+}
+
+#[test]
+fn close_pull_request_deletes_same_repo_branch_only() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let pr = |number: i64, head_repo: &str| {
+        serde_json::json!({
+            "id": number, "number": number, "title": "t", "body": null, "state": "closed", "draft": false,
+            "base": {"ref": "main", "repo": {"full_name": "o/r"}},
+            "head": {"ref": "feature/x", "repo": {"full_name": head_repo}}
+        })
+    };
+    let close1 = server.mock(|when, then| {
+        when.method(PATCH)
+            .path("/repos/o/r/pulls/1")
+            .json_body(serde_json::json!({"state": "closed"}));
+        then.status(200).json_body(pr(1, "o/r"));
+    });
+    let close2 = server.mock(|when, then| {
+        when.method(PATCH).path("/repos/o/r/pulls/2");
+        then.status(200).json_body(pr(2, "someone/r"));
+    });
+    let delete = server.mock(|when, then| {
+        when.method(DELETE)
+            .path("/repos/o/r/git/refs/heads/feature/x");
+        then.status(204);
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"close_pull_request","arguments":{"owner":"o","repo":"r","number":1,"delete_branch":true}}});
+    let out = run_with_env(&req, &envs)?;
+    close1.assert();
+    delete.assert();
+    assert!(out.contains("\"branch_deleted\":true"));
+    assert!(out.contains("\"state\":\"closed\""));
+
+    // Head in a fork: the PR is closed but the branch is left alone.
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":2,"params":{"name":"close_pull_request","arguments":{"owner":"o","repo":"r","number":2,"delete_branch":true}}});
+    let out = run_with_env(&req, &envs)?;
+    close2.assert();
+    delete.assert_hits(1);
+    assert!(out.contains("\"branch_deleted\":false"));
+    Ok(())
+}
+
+#[test]
+fn wait_for_checks_settles_or_times_out() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let rollup = |state: &str, nodes: serde_json::Value| serde_json::json!({"oid": "abc", "statusCheckRollup": {"state": state, "contexts": {"nodes": nodes}}});
+    let pr = server.mock(|when, then| {
+        when.method(POST)
+            .path("/graphql")
+            .body_contains("GetPrStatusSummary");
+        then.status(200).json_body(serde_json::json!({"data": {"repository": {"pullRequest": {"commits": {"nodes": [{"commit": rollup("FAILURE", serde_json::json!([
+            {"__typename": "CheckRun", "name": "build", "status": "COMPLETED", "conclusion": "SUCCESS"},
+            {"__typename": "CheckRun", "name": "lint", "status": "COMPLETED", "conclusion": "FAILURE"}
+        ]))}]}}}}}));
+    });
+    let _ref = server.mock(|when, then| {
+        when.method(POST)
+            .path("/graphql")
+            .body_contains("GetRefStatusSummary");
+        then.status(200).json_body(serde_json::json!({"data": {"repository": {"object": rollup("PENDING", serde_json::json!([
+            {"__typename": "CheckRun", "name": "build", "status": "IN_PROGRESS", "conclusion": null},
+            {"__typename": "StatusContext", "context": "ci/legacy", "state": "SUCCESS"}
+        ]))}}}));
+    });
+    let base = server.base_url();
+    let gql = format!("{}/graphql", base);
+    let envs = [
+        ("GITHUB_TOKEN", "t"),
+        ("GITHUB_API_URL", base.as_str()),
+        ("GITHUB_GRAPHQL_URL", gql.as_str()),
+    ];
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"wait_for_checks","arguments":{"owner":"o","repo":"r","number":5}}});
+    let out = run_with_env(&req, &envs)?;
+    pr.assert_hits(1);
+    let v: serde_json::Value = serde_json::from_str(out.trim())?;
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(sc["item"]["overall_state"], "FAILURE");
+    assert_eq!(sc["item"]["failing_contexts"], serde_json::json!(["lint"]));
+    assert_eq!(sc["timed_out"], false);
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":2,"params":{"name":"wait_for_checks","arguments":{"owner":"o","repo":"r","ref":"main","timeout_secs":1}}});
+    let out = run_with_env(&req, &envs)?;
+    let v: serde_json::Value = serde_json::from_str(out.trim())?;
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(sc["timed_out"], true);
+    assert_eq!(sc["item"]["overall_state"], "PENDING");
+    assert_eq!(sc["item"]["counts"]["pending"], 1);
+    assert_eq!(sc["item"]["counts"]["success"], 1);
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":3,"params":{"name":"wait_for_checks","arguments":{"owner":"o","repo":"r","number":5,"ref":"main"}}});
+    let out = run_with_env(&req, &envs)?;
+    assert!(out.contains("-32602"));
+    Ok(())
+}
+
+#[test]
+fn list_branches_for_commit_uses_preview_then_falls_back_to_scan() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let preview = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/commits/abc123/branches-where-head")
+            .header("accept", "application/vnd.github.groot-preview+json");
+        then.status(200).json_body(serde_json::json!([
+            {"name": "main", "commit": {"sha": "abc123"}, "protected": true}
+        ]));
+    });
+    let _gone = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/commits/def/branches-where-head");
+        then.status(415)
+            .json_body(serde_json::json!({"message": "Unsupported Media Type"}));
+    });
+    let scan = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/branches")
+            .query_param("page", "1");
+        then.status(200).json_body(serde_json::json!([
+            {"name": "main", "commit": {"sha": "def456"}, "protected": true},
+            {"name": "feature", "commit": {"sha": "999999"}, "protected": false},
+            {"name": "release", "commit": {"sha": "def456"}, "protected": false}
+        ]));
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"list_branches_for_commit","arguments":{"owner":"o","repo":"r","sha":"abc123"}}});
+    let out = run_with_env(&req, &envs)?;
+    preview.assert();
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(sc["source"], "branches_where_head");
+    assert_eq!(sc["items"][0]["name"], "main");
+    assert_eq!(sc["items"][0]["protected"], true);
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":2,"params":{"name":"list_branches_for_commit","arguments":{"owner":"o","repo":"r","sha":"def"}}});
+    let out = run_with_env(&req, &envs)?;
+    scan.assert();
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(sc["source"], "branch_scan");
+    let names: Vec<&str> = sc["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|b| b["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["main", "release"]);
+    assert!(sc.get("incomplete").is_none());
+    assert!(sc.get("error").is_none());
+    Ok(())
+}
+
+#[test]
+fn list_branches_for_commit_flags_a_capped_scan() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let base = server.base_url();
+    let _gone = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/commits/def/branches-where-head");
+        then.status(404)
+            .json_body(serde_json::json!({"message": "Not Found"}));
+    });
+    let link = format!(
+        "<{}/repos/o/r/branches?per_page=100&page=99>; rel=\"next\"",
+        base
+    );
+    let scan = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/branches");
+        then.status(200)
+            .header("Link", link.as_str())
+            .json_body(serde_json::json!([
+                {"name": "main", "commit": {"sha": "def456"}, "protected": true}
+            ]));
+    });
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"list_branches_for_commit","arguments":{"owner":"o","repo":"r","sha":"def"}}});
+    let out = run_with_env(&req, &envs)?;
+    scan.assert_hits(10);
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(sc["source"], "branch_scan");
+    assert_eq!(sc["incomplete"], true);
+    assert_ne!(sc["meta"]["has_more"], true);
+    Ok(())
+}
+
+#[test]
+fn create_pull_request_posts_and_surfaces_validation_errors() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let created = server.mock(|when, then| {
+        when.method(POST)
+            .path("/repos/o/r/pulls")
+            .json_body(serde_json::json!({"title": "Add x", "head": "feature/x", "base": "main", "draft": true}));
+        then.status(201).json_body(serde_json::json!({
+            "id": 99, "number": 7, "state": "open", "draft": true,
+            "html_url": "https://github.com/o/r/pull/7", "title": "Add x"
+        }));
+    });
+    let empty = server.mock(|when, then| {
+        when.method(POST)
+            .path("/repos/o/r/pulls")
+            .json_body_partial(r#"{"head": "main"}"#);
+        then.status(422).json_body(serde_json::json!({
+            "message": "Validation Failed",
+            "errors": [{"resource": "PullRequest", "code": "custom", "message": "No commits between main and main"}]
+        }));
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"create_pull_request","arguments":{"owner":"o","repo":"r","title":"Add x","head":"feature/x","base":"main","draft":true}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    created.assert();
+    let item = &v["result"]["structuredContent"]["item"];
+    assert_eq!(item["number"], 7);
+    assert_eq!(item["is_draft"], true);
+    assert_eq!(item["html_url"], "https://github.com/o/r/pull/7");
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":2,"params":{"name":"create_pull_request","arguments":{"owner":"o","repo":"r","title":"Nothing","head":"main","base":"main"}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    empty.assert();
+    assert_eq!(v["result"]["isError"], true);
+    let err = &v["result"]["structuredContent"]["error"];
+    assert_eq!(err["code"], "bad_request");
+    assert_eq!(
+        err["message"],
+        "Validation Failed: No commits between main and main"
+    );
+    Ok(())
+}
+
+#[test]
+fn list_pending_reviews_searches_oldest_first_and_fetches_reviewers() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let search = server.mock(|when, then| {
+        when.method(GET)
+            .path("/search/issues")
+            .query_param("q", "is:open is:pr org:acme review:required draft:false")
+            .query_param("sort", "created")
+            .query_param("order", "asc");
+        then.status(200).json_body(serde_json::json!({
+            "total_count": 2, "incomplete_results": false,
+            "items": [
+                {"id": 1, "number": 10, "title": "Old one", "state": "open",
+                 "repository_url": "https://api.github.com/repos/acme/api", "user": {"login": "ann"},
+                 "created_at": "2024-01-01T00:00:00Z", "updated_at": "2024-01-02T00:00:00Z", "pull_request": {}},
+                {"id": 2, "number": 3, "title": "Gone", "state": "open",
+                 "repository_url": "https://api.github.com/repos/acme/web", "user": {"login": "ben"},
+                 "created_at": "2024-02-01T00:00:00Z", "updated_at": "2024-02-01T00:00:00Z", "pull_request": {}}
+            ]
+        }));
+    });
+    let reviewers = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/acme/api/pulls/10/requested_reviewers");
+        then.status(200).json_body(serde_json::json!({
+            "users": [{"login": "dave"}], "teams": [{"slug": "core"}]
+        }));
+    });
+    let missing = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/acme/web/pulls/3/requested_reviewers");
+        then.status(404)
+            .json_body(serde_json::json!({"message": "Not Found"}));
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"list_pending_reviews","arguments":{"org":"acme"}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    search.assert();
+    reviewers.assert();
+    missing.assert();
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(
+        sc["query"],
+        "is:open is:pr org:acme review:required draft:false"
+    );
+    let items = sc["items"].as_array().unwrap();
+    assert_eq!(items[0]["number"], 10);
+    assert_eq!(items[0]["repo_full_name"], "acme/api");
+    assert!(items[0]["age_days"].as_i64().unwrap() > 0);
+    assert_eq!(items[0]["requested_reviewers"]["users"][0], "dave");
+    assert_eq!(items[0]["requested_reviewers"]["teams"][0], "core");
+    assert!(items[1].get("requested_reviewers").is_none());
+    assert_eq!(items[1]["reviewers_error"]["code"], "not_found");
+    assert!(sc.get("error").is_none());
+
+    // Scope must be owner+repo or org, not both or neither.
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":2,
+        "params":{"name":"list_pending_reviews","arguments":{"owner":"acme","org":"acme"}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    assert_eq!(v["error"]["code"], -32602);
+    Ok(())
+}
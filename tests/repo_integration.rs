@@ -0,0 +1,1655 @@
+use assert_cmd::Command;
+use httpmock::{Method::DELETE, Method::GET, Method::PATCH, Method::POST, Method::PUT, MockServer};
+use std::io::Write;
+
+fn run_with_env(req: &serde_json::Value, envs: &[(&str, &str)]) -> anyhow::Result<String> {
+    let mut cmd = Command::cargo_bin("github-mcp")?;
+    for (k, v) in envs {
+        cmd.env(k, v);
+    }
+    let input = serde_json::to_string(req)?;
+    let assert = cmd
+        .arg("--log-level")
+        .arg("warn")
+        .write_stdin({
+            let mut b = Vec::new();
+            writeln!(b, "{}", input).unwrap();
+            b
+        })
+        .assert();
+    let output = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    Ok(output)
+}
+
+#[test]
+fn repo_overview_partial_sections() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _repo = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r");
+        then.status(200).json_body(serde_json::json!({
+            "full_name": "o/r", "description": "demo", "private": false, "archived": false,
+            "default_branch": "main", "stargazers_count": 5, "forks_count": 1,
+            "html_url": "https://github.com/o/r", "clone_url": "https://github.com/o/r.git",
+            "ssh_url": "git@github.com:o/r.git", "git_url": "git://github.com/o/r.git"
+        }));
+    });
+    // No published release: 404 is not a section error
+    let _rel = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/releases/latest");
+        then.status(404).body("{\"message\":\"Not Found\"}");
+    });
+    let _langs = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/languages");
+        then.status(200)
+            .json_body(serde_json::json!({"Rust": 1000, "Shell": 20}));
+    });
+    let _issues = server.mock(|when, then| {
+        when.method(GET)
+            .path("/search/issues")
+            .query_param("q", "repo:o/r is:issue is:open");
+        then.status(200)
+            .json_body(serde_json::json!({"total_count": 7}));
+    });
+    let _prs = server.mock(|when, then| {
+        when.method(GET)
+            .path("/search/issues")
+            .query_param("q", "repo:o/r is:pr is:open");
+        then.status(403).body("{\"message\":\"Forbidden\"}");
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"repo_overview","arguments": {"owner":"o","repo":"r"}}
+    });
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_API_URL", server.base_url().as_str()),
+        ],
+    )?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let sc = &v["result"]["structuredContent"];
+    assert!(sc.get("error").is_none());
+    let item = &sc["item"];
+    assert_eq!(item["default_branch"], "main");
+    assert_eq!(item["open_issues_count"], 7);
+    assert!(item["open_pull_requests_count"].is_null());
+    assert!(item["latest_release"].is_null());
+    assert_eq!(item["languages"]["Rust"], 1000);
+    assert_eq!(
+        item["section_errors"]["open_pull_requests_count"]["code"],
+        "forbidden"
+    );
+    assert!(item["section_errors"].get("latest_release").is_none());
+    Ok(())
+}
+
+#[test]
+fn resolve_ref_branch_is_default() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _sha = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/commits/main")
+            .header("accept", "application/vnd.github.sha");
+        then.status(200)
+            .body("0123456789abcdef0123456789abcdef01234567");
+    });
+    let _head = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/git/ref/heads/main");
+        then.status(200)
+            .json_body(serde_json::json!({"ref": "refs/heads/main"}));
+    });
+    let _tag = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/git/ref/tags/main");
+        then.status(404).body("{\"message\":\"Not Found\"}");
+    });
+    let _repo = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r");
+        then.status(200)
+            .json_body(serde_json::json!({"default_branch": "main"}));
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"resolve_ref","arguments": {"owner":"o","repo":"r","ref":"main"}}
+    });
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_API_URL", server.base_url().as_str()),
+        ],
+    )?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let item = &v["result"]["structuredContent"]["item"];
+    assert_eq!(item["sha"], "0123456789abcdef0123456789abcdef01234567");
+    assert_eq!(item["ref_type"], "branch");
+    assert_eq!(item["is_default"], true);
+    Ok(())
+}
+
+#[test]
+fn list_branches_short_sha() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _m = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/branches");
+        then.status(200).json_body(serde_json::json!([
+            {"name":"main","commit":{"sha":"0123456789abcdef0123456789abcdef01234567"},"protected":true}
+        ]));
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"list_branches","arguments":{"owner":"o","repo":"r","sha_length":"short"}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    assert_eq!(
+        v["result"]["structuredContent"]["items"][0]["commit_sha"],
+        "0123456"
+    );
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":2,"params":{"name":"list_branches","arguments":{"owner":"o","repo":"r"}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    assert_eq!(
+        v["result"]["structuredContent"]["items"][0]["commit_sha"],
+        "0123456789abcdef0123456789abcdef01234567"
+    );
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":3,"params":{"name":"list_branches","arguments":{"owner":"o","repo":"r","sha_length":"tiny"}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    assert_eq!(v["error"]["code"], -32602);
+    Ok(())
+}
+
+#[test]
+fn list_releases_excludes_drafts_and_prereleases() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _m = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/releases");
+        then.status(200)
+            .header(
+                "link",
+                format!(
+                    "<{}/repos/o/r/releases?page=2>; rel=\"next\"",
+                    server.base_url()
+                ),
+            )
+            .json_body(serde_json::json!([
+                {"id":3,"tag_name":"v3.0.0-rc1","name":null,"draft":false,"prerelease":true,"created_at":null,"published_at":null,"author":null,"assets":[]},
+                {"id":2,"tag_name":"v2.1.0","name":null,"draft":true,"prerelease":false,"created_at":null,"published_at":null,"author":null,"assets":[]},
+                {"id":1,"tag_name":"v2.0.0","name":null,"draft":false,"prerelease":false,"created_at":null,"published_at":null,"author":null,"assets":[]}
+            ]));
+    });
+    let base = server.base_url();
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"list_releases","arguments":{"owner":"o","repo":"r","limit":3,"exclude_drafts":true,"exclude_prereleases":true}}});
+    let out = run_with_env(
+        &req,
+        &[("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())],
+    )?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let sc = &v["result"]["structuredContent"];
+    let items = sc["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["tag_name"], "v2.0.0");
+    assert_eq!(sc["meta"]["has_more"], true);
+    Ok(())
+}
+
+#[test]
+fn download_release_asset_follows_redirect_and_caps_size() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _meta = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/releases/assets/9")
+            .header("accept", "application/vnd.github+json");
+        then.status(200).json_body(serde_json::json!({
+            "id": 9, "name": "notes.txt", "content_type": "text/plain", "size": 5
+        }));
+    });
+    let redirect_url = format!("{}/storage/notes.txt", server.base_url());
+    let _bin = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/releases/assets/9")
+            .header("accept", "application/octet-stream");
+        then.status(302).header("location", redirect_url.as_str());
+    });
+    let _storage = server.mock(|when, then| {
+        when.method(GET).path("/storage/notes.txt");
+        then.status(200).body("hello");
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"download_release_asset","arguments":{"owner":"o","repo":"r","asset_id":9}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    let item = &v["result"]["structuredContent"]["item"];
+    assert_eq!(item["content_base64"], "aGVsbG8=");
+    assert_eq!(item["content_type"], "text/plain");
+    assert_eq!(item["size"], 5);
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":2,"params":{"name":"download_release_asset","arguments":{"owner":"o","repo":"r","asset_id":9,"max_bytes":4}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    assert_eq!(
+        v["result"]["structuredContent"]["error"]["code"],
+        "bad_request"
+    );
+
+    // Metadata that understates the size: the capped transfer still refuses the body
+    let _stale = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/releases/assets/11")
+            .header("accept", "application/vnd.github+json");
+        then.status(200).json_body(serde_json::json!({
+            "id": 11, "name": "notes.txt", "content_type": "text/plain", "size": 3
+        }));
+    });
+    let _stale_bin = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/releases/assets/11")
+            .header("accept", "application/octet-stream");
+        then.status(302).header("location", redirect_url.as_str());
+    });
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":3,"params":{"name":"download_release_asset","arguments":{"owner":"o","repo":"r","asset_id":11,"max_bytes":4}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(sc["error"]["code"], "bad_request");
+    assert_eq!(sc["error"]["message"], "asset exceeds max_bytes 4");
+    assert!(sc["item"].is_null());
+
+    // A body of exactly max_bytes fits
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":4,"params":{"name":"download_release_asset","arguments":{"owner":"o","repo":"r","asset_id":11,"max_bytes":5}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    assert_eq!(
+        v["result"]["structuredContent"]["item"]["content_base64"],
+        "aGVsbG8="
+    );
+    Ok(())
+}
+
+#[test]
+fn download_release_asset_fails_when_base64_would_exceed_response_cap() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let big = "a".repeat(12_000);
+    let _big_meta = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/releases/assets/9")
+            .header("accept", "application/vnd.github+json");
+        then.status(200).json_body(serde_json::json!({
+            "id": 9, "name": "big.bin", "content_type": "application/octet-stream", "size": 12000
+        }));
+    });
+    let big_bin = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/releases/assets/9")
+            .header("accept", "application/octet-stream");
+        then.status(200).body(big.as_str());
+    });
+    // Metadata that understates the size is caught once the bytes are in
+    let _lying_meta = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/releases/assets/10")
+            .header("accept", "application/vnd.github+json");
+        then.status(200).json_body(serde_json::json!({
+            "id": 10, "name": "small.bin", "content_type": "application/octet-stream", "size": 100
+        }));
+    });
+    let _lying_bin = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/releases/assets/10")
+            .header("accept", "application/octet-stream");
+        then.status(200).body(big.as_str());
+    });
+    let base = server.base_url();
+    // 16 KiB leaves room for 9216 raw bytes once base64-encoded
+    let envs = [
+        ("GITHUB_TOKEN", "t"),
+        ("GITHUB_API_URL", base.as_str()),
+        ("GITHUB_MCP_MAX_RESPONSE_BYTES", "16384"),
+    ];
+    for asset_id in [9, 10] {
+        let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"download_release_asset","arguments":{"owner":"o","repo":"r","asset_id":asset_id,"max_bytes":20000}}});
+        let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+        let sc = &v["result"]["structuredContent"];
+        assert_eq!(sc["error"]["code"], "bad_request");
+        assert!(sc["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("GITHUB_MCP_MAX_RESPONSE_BYTES"));
+        assert!(sc["item"].is_null());
+        assert!(sc.get("truncated").is_none());
+    }
+    // The oversized asset is refused from its metadata, without downloading it
+    big_bin.assert_hits(0);
+    Ok(())
+}
+
+#[test]
+fn upload_release_asset_posts_raw_bytes_to_upload_url() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let upload_url = format!(
+        "{}/uploads/repos/o/r/releases/7/assets{{?name,label}}",
+        server.base_url()
+    );
+    let _rel = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/releases/7");
+        then.status(200)
+            .json_body(serde_json::json!({"id": 7, "upload_url": upload_url}));
+    });
+    let up = server.mock(|when, then| {
+        when.method(POST)
+            .path("/uploads/repos/o/r/releases/7/assets")
+            .query_param("name", "app v1.tar.gz")
+            .query_param("label", "App")
+            .header("content-type", "application/gzip")
+            .body("hello");
+        then.status(201).json_body(serde_json::json!({
+            "id": 55, "name": "app.v1.tar.gz", "size": 5,
+            "browser_download_url": "https://github.com/o/r/releases/download/v1/app.v1.tar.gz"
+        }));
+    });
+    let base = server.base_url();
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"upload_release_asset","arguments":{"owner":"o","repo":"r","release_id":7,"name":"app v1.tar.gz","label":"App","content_type":"application/gzip","content":"aGVsbG8="}}});
+    let out = run_with_env(
+        &req,
+        &[("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())],
+    )?;
+    up.assert();
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let item = &v["result"]["structuredContent"]["item"];
+    assert_eq!(item["id"], 55);
+    assert!(item["browser_download_url"]
+        .as_str()
+        .unwrap()
+        .ends_with("app.v1.tar.gz"));
+    Ok(())
+}
+
+#[test]
+fn find_files_glob_and_substring() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _m = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/git/trees/main")
+            .query_param("recursive", "1");
+        then.status(200).json_body(serde_json::json!({
+            "sha": "t", "truncated": false,
+            "tree": [
+                {"path":"src","type":"tree","sha":"d1"},
+                {"path":"src/main.rs","type":"blob","sha":"b1","size":10},
+                {"path":"src/domain.rs.bak","type":"blob","sha":"b0","size":5},
+                {"path":"src/http/mod.rs","type":"blob","sha":"b2","size":20},
+                {"path":"README.md","type":"blob","sha":"b3","size":30},
+                {"path":"docs/methods.md","type":"blob","sha":"b4","size":40}
+            ]
+        }));
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"find_files","arguments":{"owner":"o","repo":"r","ref":"main","pattern":"src/**/*.rs"}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    let paths: Vec<&str> = v["result"]["structuredContent"]["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|i| i["path"].as_str().unwrap())
+        .collect();
+    assert_eq!(paths, vec!["src/main.rs", "src/http/mod.rs"]);
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":2,"params":{"name":"find_files","arguments":{"owner":"o","repo":"r","ref":"main","pattern":"*.md","max_results":1}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(sc["items"].as_array().unwrap().len(), 1);
+    assert_eq!(sc["capped"], true);
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":3,"params":{"name":"find_files","arguments":{"owner":"o","repo":"r","ref":"main","query":"METHODS"}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    assert_eq!(v["result"]["structuredContent"]["items"][0]["sha"], "b4");
+    Ok(())
+}
+
+#[test]
+fn repo_subscription_set_and_delete() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let put = server.mock(|when, then| {
+        when.method(PUT)
+            .path("/repos/o/r/subscription")
+            .json_body(serde_json::json!({"subscribed": true}));
+        then.status(200).json_body(serde_json::json!({
+            "subscribed": true, "ignored": false, "reason": null, "created_at": "2025-01-01T00:00:00Z"
+        }));
+    });
+    let del = server.mock(|when, then| {
+        when.method(DELETE).path("/repos/o/r/subscription");
+        then.status(204);
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"set_repo_subscription","arguments":{"owner":"o","repo":"r","subscribed":true}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    put.assert();
+    assert_eq!(v["result"]["structuredContent"]["item"]["subscribed"], true);
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":2,"params":{"name":"delete_repo_subscription","arguments":{"owner":"o","repo":"r"}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    del.assert();
+    assert_eq!(
+        v["result"]["structuredContent"]["item"]["subscribed"],
+        false
+    );
+    Ok(())
+}
+
+#[test]
+fn update_repository_patches_given_fields_and_guards_archive() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let m = server.mock(|when, then| {
+        when.method(PATCH)
+            .path("/repos/o/r")
+            .json_body(serde_json::json!({"description":"new","has_wiki":false}));
+        then.status(200).json_body(serde_json::json!({
+            "full_name":"o/r","description":"new","homepage":"","private":false,
+            "visibility":"public","archived":false,"default_branch":"main",
+            "has_issues":true,"has_projects":true,"has_wiki":false,
+            "html_url":"https://github.com/o/r","updated_at":"2025-01-01T00:00:00Z"
+        }));
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"update_repository","arguments":{"owner":"o","repo":"r","description":"new","has_wiki":false}}});
+    let out = run_with_env(&req, &envs)?;
+    m.assert();
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let item = &v["result"]["structuredContent"]["item"];
+    assert_eq!(item["full_name"], "o/r");
+    assert_eq!(item["has_wiki"], false);
+    assert!(item.get("homepage").is_none());
+
+    // Archiving without confirmation is rejected before any request is made.
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":2,"params":{"name":"update_repository","arguments":{"owner":"o","repo":"r","archived":true}}});
+    let out = run_with_env(&req, &envs)?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    assert_eq!(v["error"]["code"], -32602);
+    assert!(v["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("confirm_archive"));
+    m.assert_hits(1);
+    Ok(())
+}
+
+#[test]
+fn sync_labels_creates_updates_and_prunes() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _list = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/labels");
+        then.status(200).json_body(serde_json::json!([
+            {"name":"bug","color":"ff0000","description":"old"},
+            {"name":"docs","color":"00ff00","description":null},
+            {"name":"stale","color":"cccccc","description":null}
+        ]));
+    });
+    let create = server.mock(|when, then| {
+        when.method(POST)
+            .path("/repos/o/r/labels")
+            .json_body(serde_json::json!({"name":"feature","color":"0000ff"}));
+        then.status(201)
+            .json_body(serde_json::json!({"name":"feature"}));
+    });
+    let update = server.mock(|when, then| {
+        when.method(PATCH).path("/repos/o/r/labels/bug").json_body(
+            serde_json::json!({"new_name":"bug","color":"ee0000","description":"Something broke"}),
+        );
+        then.status(200)
+            .json_body(serde_json::json!({"name":"bug"}));
+    });
+    let delete = server.mock(|when, then| {
+        when.method(DELETE).path("/repos/o/r/labels/stale");
+        then.status(204);
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    let labels = serde_json::json!([
+        {"name":"bug","color":"#EE0000","description":"Something broke"},
+        {"name":"docs","color":"00FF00"},
+        {"name":"feature","color":"0000ff"}
+    ]);
+
+    // Dry run reports the plan without writing.
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"sync_labels","arguments":{"owner":"o","repo":"r","labels":labels,"prune":true,"dry_run":true}}});
+    let out = run_with_env(&req, &envs)?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let item = &v["result"]["structuredContent"]["item"];
+    assert_eq!(item["dry_run"], true);
+    assert_eq!(item["deleted"], serde_json::json!(["stale"]));
+    create.assert_hits(0);
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":2,"params":{"name":"sync_labels","arguments":{"owner":"o","repo":"r","labels":labels,"prune":true}}});
+    let out = run_with_env(&req, &envs)?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let item = &v["result"]["structuredContent"]["item"];
+    assert_eq!(item["created"], serde_json::json!(["feature"]));
+    assert_eq!(item["updated"], serde_json::json!(["bug"]));
+    assert_eq!(item["deleted"], serde_json::json!(["stale"]));
+    assert_eq!(item["unchanged"], 1);
+    assert!(item.get("failed").is_none());
+    create.assert();
+    update.assert();
+    delete.assert();
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":3,"params":{"name":"sync_labels","arguments":{"owner":"o","repo":"r","labels":[{"name":"x","color":"red"}]}}});
+    let out = run_with_env(&req, &envs)?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    assert_eq!(v["error"]["code"], -32602);
+    Ok(())
+}
+
+#[test]
+fn sync_labels_refuses_a_truncated_label_list() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let base = server.base_url();
+    let link = format!(
+        "<{}/repos/o/r/labels?per_page=100&page=99>; rel=\"next\"",
+        base
+    );
+    let list = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/labels");
+        then.status(200)
+            .header("Link", link.as_str())
+            .json_body(serde_json::json!([
+                {"name":"bug","color":"ff0000","description":null}
+            ]));
+    });
+    let writes = server.mock(|when, then| {
+        when.method(POST).path("/repos/o/r/labels");
+        then.status(201)
+            .json_body(serde_json::json!({"name":"feature"}));
+    });
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"sync_labels","arguments":{"owner":"o","repo":"r","labels":[{"name":"feature","color":"0000ff"}],"prune":true}}});
+    let out = run_with_env(&req, &envs)?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let err = &v["result"]["structuredContent"]["error"];
+    assert!(err["message"].as_str().unwrap().contains("partial list"));
+    assert!(v["result"]["structuredContent"]["item"].is_null());
+    list.assert_hits(10);
+    writes.assert_hits(0);
+    Ok(())
+}
+
+#[test]
+fn rest_request_is_opt_in_and_confined_to_api_base() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let topics = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/topics")
+            .header("accept", "application/vnd.github.mercy-preview+json");
+        then.status(200)
+            .json_body(serde_json::json!({"names": ["rust", "mcp"]}));
+    });
+    let star = server.mock(|when, then| {
+        when.method(PUT).path("/user/starred/o/r");
+        then.status(204);
+    });
+    let base = server.base_url();
+    let on = [
+        ("GITHUB_TOKEN", "t"),
+        ("GITHUB_API_URL", base.as_str()),
+        ("GITHUB_MCP_ENABLE_RAW_REST", "1"),
+    ];
+    let call = |args: serde_json::Value| {
+        serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,
+            "params":{"name":"rest_request","arguments":args}})
+    };
+
+    let get = call(
+        serde_json::json!({"method":"GET","path":"/repos/o/r/topics",
+        "accept":"application/vnd.github.mercy-preview+json"}),
+    );
+    let off = run_with_env(&get, &on[..2])?;
+    let v: serde_json::Value = serde_json::from_str(&off)?;
+    assert_eq!(v["error"]["code"], -32601);
+
+    let out = run_with_env(&get, &on)?;
+    topics.assert();
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(sc["status"], 200);
+    assert_eq!(sc["body"]["names"][1], "mcp");
+
+    let out = run_with_env(
+        &call(serde_json::json!({"method":"PUT","path":"/user/starred/o/r"})),
+        &on,
+    )?;
+    star.assert();
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(sc["status"], 204);
+    assert!(sc["body"].is_null());
+    assert!(sc.get("error").is_none());
+
+    for path in [
+        "https://evil.example/x",
+        "//evil.example/x",
+        "/repos/../../admin",
+        "/repos/o/r/.%2e/.%2e/.%2e/escape",
+        "/repos/o/r/%2E./x",
+        "/repos/o/r/%2e/x",
+        "repos/o/r",
+    ] {
+        let out = run_with_env(&call(serde_json::json!({"method":"GET","path":path})), &on)?;
+        let v: serde_json::Value = serde_json::from_str(&out)?;
+        assert_eq!(v["error"]["code"], -32602, "{}", path);
+    }
+
+    // A 2xx body that isn't JSON is reported, not passed off as an empty success.
+    let _html = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/readme-html");
+        then.status(200).body("<html>proxy login</html>");
+    });
+    let out = run_with_env(
+        &call(serde_json::json!({"method":"GET","path":"/repos/o/r/readme-html"})),
+        &on,
+    )?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(sc["status"], 200);
+    assert!(sc["error"]["message"].is_string());
+
+    // Read-only mode keeps the escape hatch to GET
+    let mut ro = on.to_vec();
+    ro.push(("GITHUB_MCP_READ_ONLY", "1"));
+    let out = run_with_env(
+        &call(serde_json::json!({"method":"DELETE","path":"/user/starred/o/r"})),
+        &ro,
+    )?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    assert_eq!(v["error"]["code"], -32602);
+    Ok(())
+}
+
+#[test]
+fn get_release_without_id_or_tag_is_invalid_params() -> anyhow::Result<()> {
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"get_release","arguments":{"owner":"o","repo":"r"}}});
+    let out = run_with_env(&req, &[("GITHUB_TOKEN", "t")])?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    assert_eq!(v["error"]["code"], -32602);
+    assert!(v.get("result").is_none());
+    Ok(())
+}
+
+#[test]
+fn repo_activity_digest_counts_window() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let count = |q: &'static str, n: i64| {
+        server.mock(move |when, then| {
+            when.method(GET).path("/search/issues").query_param("q", q);
+            then.status(200).json_body(
+                serde_json::json!({"total_count": n, "incomplete_results": false, "items": []}),
+            );
+        })
+    };
+    let _issues = count("repo:o/r is:issue created:2025-03-01..2025-03-07", 4);
+    let _prs = count("repo:o/r is:pr created:2025-03-01..2025-03-07", 3);
+    let _merged = server.mock(|when, then| {
+        when.method(GET)
+            .path("/search/issues")
+            .query_param("q", "repo:o/r is:pr merged:2025-03-01..2025-03-07");
+        then.status(403).body("{\"message\":\"Forbidden\"}");
+    });
+    let _rels = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/releases");
+        then.status(200).json_body(serde_json::json!([
+            {"tag_name":"v3","draft":true,"published_at":null},
+            {"tag_name":"v2","draft":false,"published_at":"2025-03-07T18:00:00Z"},
+            {"tag_name":"v1","draft":false,"published_at":"2025-02-20T00:00:00Z"}
+        ]));
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"repo_activity_digest","arguments": {"owner":"o","repo":"r","since":"2025-03-01","until":"2025-03-07"}}
+    });
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_API_URL", server.base_url().as_str()),
+        ],
+    )?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let item = &v["result"]["structuredContent"]["item"];
+    assert_eq!(item["new_issues"], 4);
+    assert_eq!(item["new_pull_requests"], 3);
+    assert!(item["merged_pull_requests"].is_null());
+    assert_eq!(
+        item["section_errors"]["merged_pull_requests"]["code"],
+        "forbidden"
+    );
+    assert_eq!(item["releases"], 1);
+    assert_eq!(item["release_tags"], serde_json::json!(["v2"]));
+    assert!(item.get("releases_incomplete").is_none());
+
+    // A busy repo: releases page past the first 100 until one predates the window
+    let full_page: Vec<serde_json::Value> = (0..100)
+        .map(|i| serde_json::json!({"tag_name":format!("b{}", i),"draft":false,"published_at":"2025-05-02T00:00:00Z"}))
+        .collect();
+    let _busy1 = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/busy/releases")
+            .query_param("page", "1");
+        then.status(200).json_body(serde_json::json!(full_page));
+    });
+    let busy2 = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/busy/releases")
+            .query_param("page", "2");
+        then.status(200).json_body(serde_json::json!([
+            {"tag_name":"old","draft":false,"published_at":"2025-04-01T00:00:00Z"}
+        ]));
+    });
+    let busy = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":3,
+        "params":{"name":"repo_activity_digest","arguments": {"owner":"o","repo":"busy","since":"2025-05-01"}}
+    });
+    let out = run_with_env(
+        &busy,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_API_URL", server.base_url().as_str()),
+        ],
+    )?;
+    busy2.assert();
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let item = &v["result"]["structuredContent"]["item"];
+    assert_eq!(item["releases"], 100);
+    assert!(item.get("releases_incomplete").is_none());
+
+    let bad = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":2,
+        "params":{"name":"repo_activity_digest","arguments": {"owner":"o","repo":"r","since":"2025-03-07","until":"2025-03-01"}}
+    });
+    let out = run_with_env(
+        &bad,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_API_URL", server.base_url().as_str()),
+        ],
+    )?;
+    assert!(out.contains("-32602"));
+    Ok(())
+}
+
+#[test]
+fn list_starred_sort_starred_uses_star_media_type() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let starred = server.mock(|when, then| {
+        when.method(GET)
+            .path("/user/starred")
+            .query_param("sort", "created")
+            .header("accept", "application/vnd.github.star+json");
+        then.status(200).json_body(serde_json::json!([
+            {"starred_at":"2025-01-02T00:00:00Z","repo":{"full_name":"o/r","private":false,"description":null,"language":null,"stargazers_count":1,"html_url":"https://github.com/o/r","owner":{"login":"o"}}}
+        ]));
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"list_starred_repositories","arguments": {"sort":"starred"}}
+    });
+    let out = run_with_env(&req, &envs)?;
+    starred.assert();
+    assert!(out.contains("\"starred_at\":\"2025-01-02T00:00:00Z\""));
+
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":2,
+        "params":{"name":"list_starred_repositories","arguments": {"sort":"starred","include_starred_at":false}}
+    });
+    let out = run_with_env(&req, &envs)?;
+    assert!(out.contains("-32602"));
+    Ok(())
+}
+
+#[test]
+fn list_watched_repositories_paginates() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let next = format!(
+        "<{}/user/subscriptions?per_page=1&page=2>; rel=\"next\"",
+        server.base_url()
+    );
+    let m = server.mock(|when, then| {
+        when.method(GET)
+            .path("/user/subscriptions")
+            .query_param("per_page", "1")
+            .query_param("page", "1");
+        then.status(200).header("link", next.as_str()).json_body(serde_json::json!([
+            {"full_name":"o/r","private":true,"description":null,"language":"Rust","stargazers_count":3,"html_url":"https://github.com/o/r","pushed_at":"2025-04-01T10:00:00Z"}
+        ]));
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"list_watched_repositories","arguments": {"limit":1}}
+    });
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_API_URL", server.base_url().as_str()),
+        ],
+    )?;
+    m.assert();
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(sc["items"][0]["full_name"], "o/r");
+    assert_eq!(sc["items"][0]["pushed_at"], "2025-04-01T10:00:00Z");
+    assert_eq!(sc["meta"]["has_more"], true);
+    assert!(sc["meta"]["next_cursor"].is_string());
+    Ok(())
+}
+
+#[test]
+fn list_available_merge_methods_from_repo_settings() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _repo = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r");
+        then.status(200).json_body(serde_json::json!({
+            "full_name": "o/r", "allow_merge_commit": false, "allow_squash_merge": true,
+            "allow_rebase_merge": true, "delete_branch_on_merge": true
+        }));
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"list_available_merge_methods","arguments": {"owner":"o","repo":"r"}}
+    });
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_API_URL", server.base_url().as_str()),
+        ],
+    )?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let item = &v["result"]["structuredContent"]["item"];
+    assert_eq!(item["methods"], serde_json::json!(["squash", "rebase"]));
+    assert_eq!(item["allow_merge_commit"], false);
+    assert_eq!(item["delete_branch_on_merge"], true);
+    Ok(())
+}
+
+#[test]
+fn get_repo_exposes_clone_urls() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _repo = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r");
+        then.status(200).json_body(serde_json::json!({
+            "full_name": "o/r", "description": null, "private": false, "fork": false,
+            "archived": false, "default_branch": "main", "html_url": "https://github.com/o/r",
+            "clone_url": "https://github.com/o/r.git", "ssh_url": "git@github.com:o/r.git",
+            "git_url": "git://github.com/o/r.git", "stargazers_count": 0, "forks_count": 0
+        }));
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"get_repo","arguments": {"owner":"o","repo":"r"}}
+    });
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_API_URL", server.base_url().as_str()),
+        ],
+    )?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let item = &v["result"]["structuredContent"]["item"];
+    assert_eq!(item["clone_url"], "https://github.com/o/r.git");
+    assert_eq!(item["ssh_url"], "git@github.com:o/r.git");
+    assert_eq!(item["git_url"], "git://github.com/o/r.git");
+    assert_eq!(item["default_branch"], "main");
+    assert!(item.get("description").is_none());
+    Ok(())
+}
+
+#[test]
+fn gist_update_delete_and_star() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let patch = server.mock(|when, then| {
+        when.method(PATCH)
+            .path("/gists/g1")
+            .json_body(serde_json::json!({
+                "description": "notes",
+                "files": {"a.md": {"content": "hi"}, "old.txt": null}
+            }));
+        then.status(200).json_body(serde_json::json!({
+            "id": "g1", "description": "notes", "public": false,
+            "html_url": "https://gist.github.com/g1",
+            "files": {"a.md": {"filename": "a.md"}},
+            "updated_at": "2024-02-01T00:00:00Z"
+        }));
+    });
+    let star = server.mock(|when, then| {
+        when.method(PUT).path("/gists/g1/star");
+        then.status(204);
+    });
+    let unstar = server.mock(|when, then| {
+        when.method(DELETE).path("/gists/g1/star");
+        then.status(204);
+    });
+    let delete = server.mock(|when, then| {
+        when.method(DELETE).path("/gists/g1");
+        then.status(204);
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    let call = |name: &str, args: serde_json::Value| serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":name,"arguments":args}});
+
+    let out = run_with_env(
+        &call(
+            "update_gist",
+            serde_json::json!({"gist_id":"g1","description":"notes","files":{"a.md":{"content":"hi"},"old.txt":null}}),
+        ),
+        &envs,
+    )?;
+    patch.assert();
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    assert_eq!(
+        v["result"]["structuredContent"]["item"]["files"],
+        serde_json::json!(["a.md"])
+    );
+
+    for (name, mock) in [
+        ("star_gist", &star),
+        ("unstar_gist", &unstar),
+        ("delete_gist", &delete),
+    ] {
+        let out = run_with_env(&call(name, serde_json::json!({"gist_id":"g1"})), &envs)?;
+        mock.assert();
+        assert!(out.contains("\"ok\":true"), "{}: {}", name, out);
+    }
+
+    let out = run_with_env(
+        &call("update_gist", serde_json::json!({"gist_id":"g1"})),
+        &envs,
+    )?;
+    assert!(out.contains("-32602"));
+
+    let ro = [
+        ("GITHUB_TOKEN", "t"),
+        ("GITHUB_API_URL", base.as_str()),
+        ("GITHUB_MCP_READ_ONLY", "1"),
+    ];
+    let out = run_with_env(
+        &call("delete_gist", serde_json::json!({"gist_id":"g1"})),
+        &ro,
+    )?;
+    assert!(out.contains("read-only mode"));
+    delete.assert_hits(1);
+    Ok(())
+}
+
+#[test]
+fn debug_tracing_covers_write_helpers_without_bodies() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _patch = server.mock(|when, then| {
+        when.method(PATCH).path("/gists/g2");
+        then.status(200).json_body(serde_json::json!({
+            "id": "g2", "description": "secret-note", "public": false,
+            "html_url": "https://gist.github.com/g2", "files": {},
+            "updated_at": "2024-02-01T00:00:00Z"
+        }));
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"update_gist","arguments":{"gist_id":"g2","description":"secret-note"}}
+    });
+    let assert = Command::cargo_bin("github-mcp")?
+        .env("GITHUB_TOKEN", "t")
+        .env("GITHUB_API_URL", server.base_url())
+        .env("GITHUB_MCP_DEBUG", "1")
+        .arg("--log-level")
+        .arg("warn")
+        .write_stdin(format!("{}\n", req))
+        .assert();
+    let stderr = String::from_utf8(assert.get_output().stderr.clone())?;
+    let expected = format!(
+        "[debug] PATCH {}/gists/g2 status=200 attempt=0 body=<redacted>",
+        server.base_url()
+    );
+    assert!(stderr.contains(&expected), "{}", stderr);
+    assert!(stderr
+        .lines()
+        .filter(|l| l.starts_with("[debug]"))
+        .all(|l| !l.contains("secret-note")));
+    Ok(())
+}
+
+#[test]
+fn slow_requests_are_logged_only_when_a_threshold_is_set() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _keys = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/keys");
+        then.status(200)
+            .delay(std::time::Duration::from_millis(300))
+            .json_body(serde_json::json!([]));
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"list_deploy_keys","arguments":{"owner":"o","repo":"r"}}
+    });
+    let stderr_with = |slow_ms: Option<&str>| -> anyhow::Result<String> {
+        let mut cmd = Command::cargo_bin("github-mcp")?;
+        cmd.env("GITHUB_TOKEN", "t")
+            .env("GITHUB_API_URL", server.base_url())
+            .env_remove("GITHUB_MCP_SLOW_MS");
+        if let Some(ms) = slow_ms {
+            cmd.env("GITHUB_MCP_SLOW_MS", ms);
+        }
+        let assert = cmd
+            .arg("--log-level")
+            .arg("warn")
+            .write_stdin(format!("{}\n", req))
+            .assert();
+        Ok(String::from_utf8(assert.get_output().stderr.clone())?)
+    };
+    let stderr = stderr_with(Some("100"))?;
+    let expected = format!("slow request: GET {}/repos/o/r/keys", server.base_url());
+    assert!(stderr.contains(&expected), "{}", stderr);
+    assert!(stderr.contains("GITHUB_MCP_SLOW_MS=100"));
+    // Off by default
+    assert!(!stderr_with(None)?.contains("slow request"));
+    Ok(())
+}
+
+#[test]
+fn list_deploy_keys_paginates_with_public_keys() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let base = server.base_url();
+    let link = format!("<{}/repos/o/r/keys?per_page=1&page=2>; rel=\"next\"", base);
+    let _keys = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/keys")
+            .query_param("per_page", "1")
+            .query_param("page", "1");
+        then.status(200)
+            .header("Link", link.as_str())
+            .json_body(serde_json::json!([{
+                "id": 7, "title": "deploy", "key": "ssh-ed25519 AAAA", "read_only": false,
+                "verified": true, "url": "https://api.github.com/repos/o/r/keys/7",
+                "created_at": "2024-03-01T00:00:00Z"
+            }]));
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"list_deploy_keys","arguments": {"owner":"o","repo":"r","limit":1}}
+    });
+    let out = run_with_env(
+        &req,
+        &[("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())],
+    )?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(
+        sc["items"][0],
+        serde_json::json!({
+            "id": 7, "title": "deploy", "key": "ssh-ed25519 AAAA", "read_only": false,
+            "created_at": "2024-03-01T00:00:00Z"
+        })
+    );
+    assert_eq!(sc["meta"]["has_more"], true);
+    assert!(sc["meta"]["next_cursor"].is_string());
+    Ok(())
+}
+
+#[test]
+fn get_environment_flattens_protection_rules() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _env = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/environments/prod%20eu");
+        then.status(200).json_body(serde_json::json!({
+            "id": 1, "name": "prod eu",
+            "created_at": "2024-01-01T00:00:00Z", "updated_at": "2024-02-01T00:00:00Z",
+            "protection_rules": [
+                {"id": 3, "type": "wait_timer", "wait_timer": 30},
+                {"id": 4, "type": "required_reviewers", "prevent_self_review": true, "reviewers": [
+                    {"type": "User", "reviewer": {"login": "octocat", "id": 1}},
+                    {"type": "Team", "reviewer": {"slug": "release", "name": "Release", "id": 2}}
+                ]},
+                {"id": 5, "type": "branch_policy"}
+            ],
+            "deployment_branch_policy": {"protected_branches": false, "custom_branch_policies": true}
+        }));
+    });
+    let base = server.base_url();
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"get_environment","arguments": {"owner":"o","repo":"r","environment_name":"prod eu"}}
+    });
+    let out = run_with_env(
+        &req,
+        &[("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())],
+    )?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    assert_eq!(
+        v["result"]["structuredContent"]["item"],
+        serde_json::json!({
+            "name": "prod eu",
+            "wait_timer": 30,
+            "reviewers": [
+                {"type": "User", "login": "octocat"},
+                {"type": "Team", "login": "release"}
+            ],
+            "prevent_self_review": true,
+            "deployment_branch_policy": {"protected_branches": false, "custom_branch_policies": true},
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-02-01T00:00:00Z"
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn get_license_decodes_text_and_maps_404_to_null() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _lic = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/license");
+        then.status(200).json_body(serde_json::json!({
+            "name": "LICENSE", "path": "LICENSE", "encoding": "base64",
+            "content": "TUlUIExpY2Vuc2UKCkNvcHly\naWdodCAoYykgT2N0bw==\n",
+            "license": {"key": "mit", "name": "MIT License", "spdx_id": "MIT"}
+        }));
+    });
+    let _none = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/bare/license");
+        then.status(404)
+            .json_body(serde_json::json!({"message": "Not Found"}));
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    let call = |repo: &str| {
+        serde_json::json!({
+            "jsonrpc":"2.0","method":"tools/call","id":1,
+            "params":{"name":"get_license","arguments": {"owner":"o","repo":repo}}
+        })
+    };
+    let out = run_with_env(&call("r"), &envs)?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let license = &v["result"]["structuredContent"]["license"];
+    assert_eq!(license["spdx_id"], "MIT");
+    assert_eq!(license["name"], "MIT License");
+    assert_eq!(license["text"], "MIT License\n\nCopyright (c) Octo");
+
+    let out = run_with_env(&call("bare"), &envs)?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let sc = &v["result"]["structuredContent"];
+    assert!(sc["license"].is_null());
+    assert!(sc.get("error").is_none());
+    assert_ne!(v["result"]["isError"], true);
+    Ok(())
+}
+
+#[test]
+fn list_org_audit_log_follows_after_tokens_and_explains_403() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let base = server.base_url();
+    let link = format!(
+        "<{}/organizations/1/audit-log?per_page=1&phrase=action%3Arepo.create&after=MS4y%3D>; rel=\"next\"",
+        base
+    );
+    let first = server.mock(|when, then| {
+        when.method(GET)
+            .path("/orgs/acme/audit-log")
+            .query_param("per_page", "1")
+            .query_param("phrase", "action:repo.create")
+            .matches(|req| {
+                !req.query_params
+                    .as_ref()
+                    .is_some_and(|q| q.iter().any(|(k, _)| k == "after"))
+            });
+        then.status(200)
+            .header("Link", link.as_str())
+            .json_body(serde_json::json!([{
+                "action": "repo.create", "actor": "octo", "created_at": 1_700_000_000_000_i64,
+                "@timestamp": 1_700_000_000_000_i64, "org": "acme", "repo": "acme/new",
+                "operation_type": "create", "_document_id": "d1"
+            }]));
+    });
+    let second = server.mock(|when, then| {
+        when.method(GET)
+            .path("/orgs/acme/audit-log")
+            .query_param("phrase", "action:repo.create")
+            .query_param("after", "MS4y=");
+        then.status(200).json_body(serde_json::json!([{
+            "action": "repo.create", "actor": "octo", "@timestamp": 1_600_000_000_000_i64
+        }]));
+    });
+    let _denied = server.mock(|when, then| {
+        when.method(GET).path("/orgs/other/audit-log");
+        then.status(403)
+            .json_body(serde_json::json!({"message": "Must have admin rights"}));
+    });
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    let call = |args: serde_json::Value| {
+        serde_json::json!({
+            "jsonrpc":"2.0","method":"tools/call","id":1,
+            "params":{"name":"list_org_audit_log","arguments": args}
+        })
+    };
+    let out = run_with_env(
+        &call(serde_json::json!({"org":"acme","phrase":"action:repo.create","limit":1})),
+        &envs,
+    )?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(sc["items"][0]["action"], "repo.create");
+    assert_eq!(sc["items"][0]["created_at"], "2023-11-14T22:13:20Z");
+    assert_eq!(sc["items"][0]["document_id"], "d1");
+    let cursor = sc["meta"]["next_cursor"].as_str().unwrap().to_string();
+    first.assert();
+
+    let out = run_with_env(
+        &call(serde_json::json!({"org":"acme","phrase":"action:repo.create","cursor":cursor})),
+        &envs,
+    )?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(sc["items"][0]["created_at"], "2020-09-13T12:26:40Z");
+    assert!(sc["meta"].get("has_more").is_none());
+    second.assert();
+
+    // A cursor issued for one org isn't replayed against another, nor with a different phrase
+    for args in [
+        serde_json::json!({"org":"other","phrase":"action:repo.create","cursor":cursor}),
+        serde_json::json!({"org":"acme","phrase":"action:repo.destroy","cursor":cursor}),
+        serde_json::json!({"org":"acme","cursor":cursor}),
+    ] {
+        let out = run_with_env(&call(args), &envs)?;
+        assert!(out.contains("-32602"));
+    }
+    second.assert_hits(1);
+
+    // Page-number cursors from other tools don't apply here
+    let page_cursor = github_mcp::http::encode_rest_cursor(github_mcp::http::RestCursor {
+        page: 2,
+        per_page: 1,
+        path: None,
+        after: None,
+    });
+    let out = run_with_env(
+        &call(serde_json::json!({"org":"acme","cursor":page_cursor})),
+        &envs,
+    )?;
+    assert!(out.contains("-32602"));
+
+    let out = run_with_env(&call(serde_json::json!({"org":"other"})), &envs)?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let err = &v["result"]["structuredContent"]["error"];
+    assert_eq!(err["code"], "forbidden");
+    assert!(err["message"].as_str().unwrap().contains("read:audit_log"));
+    Ok(())
+}
+
+#[test]
+fn bad_dates_are_rejected_before_any_request() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let any = server.mock(|when, then| {
+        when.any_request();
+        then.status(200).json_body(serde_json::json!([]));
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    for (tool, args) in [
+        (
+            "list_commits",
+            serde_json::json!({"owner":"o","repo":"r","since":"yesterday"}),
+        ),
+        (
+            "list_commits",
+            serde_json::json!({"owner":"o","repo":"r","until":"2025-13-01"}),
+        ),
+        (
+            "list_issues",
+            serde_json::json!({"owner":"o","repo":"r","since":"2025-01-01"}),
+        ),
+    ] {
+        let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":tool,"arguments":args}});
+        let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+        assert_eq!(v["error"]["code"], -32602, "{} {}", tool, args);
+        assert!(v["error"]["message"].as_str().unwrap().contains("RFC3339"));
+    }
+    any.assert_hits(0);
+    Ok(())
+}
+
+#[test]
+fn include_verification_surfaces_commit_signature_status() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let commit = |sha: &str, verified: bool, reason: &str| {
+        serde_json::json!({
+            "sha": sha,
+            "commit": {
+                "message": "m",
+                "author": {"name": "a", "email": "a@x", "date": "2025-01-01T00:00:00Z"},
+                "verification": {"verified": verified, "reason": reason, "signature": null, "payload": null}
+            },
+            "author": null, "committer": null, "parents": []
+        })
+    };
+    server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/commits/abc");
+        then.status(200).json_body(commit("abc", true, "valid"));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/commits");
+        then.status(200)
+            .json_body(serde_json::json!([commit("def", false, "unsigned")]));
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"get_commit","arguments":{"owner":"o","repo":"r","ref":"abc","include_verification":true}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    let item = &v["result"]["structuredContent"]["item"];
+    assert_eq!(item["verification"]["verified"], true);
+    assert_eq!(item["verification"]["reason"], "valid");
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":2,"params":{"name":"list_commits","arguments":{"owner":"o","repo":"r","include_verification":true}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    let item = &v["result"]["structuredContent"]["items"][0];
+    assert_eq!(item["verification"]["verified"], false);
+    assert_eq!(item["verification"]["reason"], "unsigned");
+
+    // Off by default.
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":3,"params":{"name":"get_commit","arguments":{"owner":"o","repo":"r","ref":"abc"}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    assert!(v["result"]["structuredContent"]["item"]
+        .get("verification")
+        .is_none());
+    Ok(())
+}
+
+#[test]
+fn list_branches_protection_summary_and_protected_first() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/branches");
+        then.status(200).json_body(serde_json::json!([
+            {"name":"dev","commit":{"sha":"a"},"protected":false},
+            {"name":"main","commit":{"sha":"b"},"protected":true},
+            {"name":"release/1.0","commit":{"sha":"c"},"protected":true}
+        ]));
+    });
+    let main = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/branches/main/protection");
+        then.status(200).json_body(serde_json::json!({
+            "required_status_checks": {"strict": true, "contexts": ["ci"], "checks": [{"context": "ci", "app_id": 1}, {"context": "lint", "app_id": null}]},
+            "required_pull_request_reviews": {"required_approving_review_count": 2},
+            "enforce_admins": {"enabled": true}
+        }));
+    });
+    let release = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/branches/release%2F1.0/protection");
+        then.status(403)
+            .json_body(serde_json::json!({"message": "Resource not accessible by integration"}));
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"list_branches","arguments":{"owner":"o","repo":"r","sort":"protected_first","include_protection_summary":true}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    main.assert();
+    release.assert();
+    let items = &v["result"]["structuredContent"]["items"];
+    let names: Vec<_> = items
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|b| b["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["main", "release/1.0", "dev"]);
+    let p = &items[0]["protection"];
+    assert_eq!(p["required_approving_review_count"], 2);
+    assert_eq!(p["required_checks"], serde_json::json!(["ci", "lint"]));
+    assert_eq!(p["enforce_admins"], true);
+    assert_eq!(items[1]["protection_error"]["code"], "forbidden");
+    assert!(items[2].get("protection").is_none());
+    assert!(v["result"]["structuredContent"].get("error").is_none());
+    Ok(())
+}
+
+#[test]
+fn get_file_contents_decodes_files_and_lists_directories() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let file = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/contents/src/my%20file.rs")
+            .query_param("ref", "dev");
+        then.status(200).json_body(serde_json::json!({
+            "type": "file", "name": "my file.rs", "path": "src/my file.rs", "sha": "s1", "size": 12,
+            "encoding": "base64", "content": "Zm4gbWFpbigp\nIHt9Cg==\n"
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/contents/img.png");
+        then.status(200).json_body(serde_json::json!({
+            "type": "file", "name": "img.png", "path": "img.png", "sha": "s2", "size": 4,
+            "encoding": "base64", "content": "iVBORw=="
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/contents/src");
+        then.status(200).json_body(serde_json::json!([
+            {"type": "file", "name": "main.rs", "path": "src/main.rs", "sha": "s3", "size": 10},
+            {"type": "dir", "name": "http", "path": "src/http", "sha": "s4", "size": 0}
+        ]));
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    let call = |args: serde_json::Value| -> anyhow::Result<serde_json::Value> {
+        let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"get_file_contents","arguments":args}});
+        Ok(serde_json::from_str(&run_with_env(&req, &envs)?)?)
+    };
+
+    let v = call(serde_json::json!({"owner":"o","repo":"r","path":"src/my file.rs","ref":"dev"}))?;
+    file.assert();
+    let item = &v["result"]["structuredContent"]["item"];
+    assert_eq!(item["content"], "fn main() {}\n");
+    assert_eq!(item["encoding"], "utf-8");
+    assert_eq!(item["sha"], "s1");
+
+    // Binary: base64 within max_bytes, truncated above it.
+    let v = call(serde_json::json!({"owner":"o","repo":"r","path":"img.png"}))?;
+    let item = &v["result"]["structuredContent"]["item"];
+    assert_eq!(item["encoding"], "base64");
+    assert_eq!(item["content"], "iVBORw==");
+    let v = call(serde_json::json!({"owner":"o","repo":"r","path":"img.png","max_bytes":2}))?;
+    let item = &v["result"]["structuredContent"]["item"];
+    assert_eq!(item["truncated"], true);
+    assert!(item.get("content").is_none());
+
+    let v = call(serde_json::json!({"owner":"o","repo":"r","path":"src/"}))?;
+    let sc = &v["result"]["structuredContent"];
+    assert!(sc.get("item").is_none());
+    assert_eq!(sc["items"][1]["type"], "dir");
+    assert_eq!(sc["items"][0]["path"], "src/main.rs");
+
+    // Dot segments would be resolved by the URL parser and escape /contents.
+    let escape = server.mock(|when, then| {
+        when.method(GET).path("/user");
+        then.status(200)
+            .json_body(serde_json::json!({"login": "me"}));
+    });
+    for path in ["../../../../user", "src/./main.rs", "src/%2E%2e/x", "a//b"] {
+        let v = call(serde_json::json!({"owner":"o","repo":"r","path":path}))?;
+        assert_eq!(v["error"]["code"], -32602, "{path}");
+    }
+    let v = call(serde_json::json!({"owner":"..","repo":"..","path":"user"}))?;
+    assert_eq!(v["error"]["code"], -32602);
+    escape.assert_hits(0);
+    Ok(())
+}
+
+#[test]
+fn create_or_update_file_encodes_content_and_reports_conflicts() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let created = server.mock(|when, then| {
+        when.method(PUT)
+            .path("/repos/o/r/contents/docs/new%20file.md")
+            .json_body(
+                serde_json::json!({"message": "add doc", "content": "aGVsbG8K", "branch": "dev"}),
+            );
+        then.status(201).json_body(serde_json::json!({
+            "content": {"name": "new file.md", "path": "docs/new file.md", "sha": "blob1"},
+            "commit": {"sha": "commit1", "message": "add doc"}
+        }));
+    });
+    let stale = server.mock(|when, then| {
+        when.method(PUT)
+            .path("/repos/o/r/contents/README.md")
+            .json_body_partial(r#"{"sha": "old"}"#);
+        then.status(409)
+            .json_body(serde_json::json!({"message": "README.md does not match old"}));
+    });
+    let missing_sha = server.mock(|when, then| {
+        when.method(PUT).path("/repos/o/r/contents/LICENSE");
+        then.status(422).json_body(serde_json::json!({
+            "message": "Invalid request.\n\n\"sha\" wasn't supplied."
+        }));
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    let call = |args: serde_json::Value| -> anyhow::Result<serde_json::Value> {
+        let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"create_or_update_file","arguments":args}});
+        Ok(serde_json::from_str(&run_with_env(&req, &envs)?)?)
+    };
+
+    let v = call(
+        serde_json::json!({"owner":"o","repo":"r","path":"docs/new file.md","message":"add doc","content":"hello\n","branch":"dev"}),
+    )?;
+    created.assert();
+    let item = &v["result"]["structuredContent"]["item"];
+    assert_eq!(item["commit_sha"], "commit1");
+    assert_eq!(item["file_sha"], "blob1");
+    assert_eq!(item["created"], true);
+
+    let v = call(
+        serde_json::json!({"owner":"o","repo":"r","path":"README.md","message":"m","content":"x","sha":"old"}),
+    )?;
+    stale.assert();
+    assert_eq!(
+        v["result"]["structuredContent"]["error"]["code"],
+        "conflict"
+    );
+
+    let v = call(
+        serde_json::json!({"owner":"o","repo":"r","path":"LICENSE","message":"m","content":"x"}),
+    )?;
+    missing_sha.assert();
+    let err = &v["result"]["structuredContent"]["error"];
+    assert_eq!(err["code"], "bad_request");
+    assert!(err["message"]
+        .as_str()
+        .unwrap()
+        .contains("get_file_contents"));
+
+    let v = call(
+        serde_json::json!({"owner":"o","repo":"r","path":"a.txt","message":" ","content":"x"}),
+    )?;
+    assert_eq!(v["error"]["code"], -32602);
+
+    // Dot segments must not turn this into a PUT against an arbitrary endpoint.
+    let follow = server.mock(|when, then| {
+        when.method(PUT).path("/user/following/octocat");
+        then.status(204);
+    });
+    for path in [
+        "../../../../user/following/octocat",
+        "docs/%2e%2E/../../../../user/following/octocat",
+        "docs/./x.md",
+    ] {
+        let v = call(
+            serde_json::json!({"owner":"o","repo":"r","path":path,"message":"m","content":"x"}),
+        )?;
+        assert_eq!(v["error"]["code"], -32602, "{path}");
+    }
+    follow.assert_hits(0);
+    Ok(())
+}
+
+#[test]
+fn search_repositories_composes_structured_qualifiers() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let m = server.mock(|when, then| {
+        when.method(GET)
+            .path("/search/repositories")
+            .query_param("q", "cli language:rust stars:>100 topic:\"command line\" org:acme");
+        then.status(200).json_body(serde_json::json!({
+            "total_count": 1, "incomplete_results": false,
+            "items": [{"full_name": "acme/tool", "private": false, "description": null, "language": "Rust",
+                "stargazers_count": 150, "forks_count": 3, "open_issues_count": 1, "html_url": "https://github.com/acme/tool"}]
+        }));
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"search_repositories","arguments":{"q":"cli","language":"rust","stars":">100","topic":"command line","org":"acme"}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    m.assert();
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(
+        sc["query"],
+        "cli language:rust stars:>100 topic:\"command line\" org:acme"
+    );
+    assert_eq!(sc["items"][0]["full_name"], "acme/tool");
+
+    // Neither q nor a qualifier.
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":2,"params":{"name":"search_repositories","arguments":{"sort":"stars"}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    assert_eq!(v["error"]["code"], -32602);
+    Ok(())
+}
+
+#[test]
+fn search_pull_requests_composes_qualifiers_and_adds_is_pr() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let m = server.mock(|when, then| {
+        when.method(GET)
+            .path("/search/issues")
+            .query_param("q", "state:open label:\"needs review\" author:octo repo:acme/tool is:pr");
+        then.status(200).json_body(serde_json::json!({
+            "total_count": 1, "incomplete_results": false,
+            "items": [{"id": 7, "number": 3, "title": "Fix", "state": "open",
+                "repository_url": "https://api.github.com/repos/acme/tool", "user": {"login": "octo"},
+                "created_at": "2025-01-01T00:00:00Z", "updated_at": "2025-01-02T00:00:00Z", "pull_request": {}}]
+        }));
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"search_pull_requests","arguments":{"state":"open","label":"needs review","author":"octo","repo":"acme/tool"}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    m.assert();
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(
+        sc["query"],
+        "state:open label:\"needs review\" author:octo repo:acme/tool is:pr"
+    );
+    assert_eq!(sc["items"][0]["is_pull_request"], true);
+
+    // An explicit type qualifier in q is left alone
+    let typed = server.mock(|when, then| {
+        when.method(GET)
+            .path("/search/issues")
+            .query_param("q", "type:issue repo:acme/tool");
+        then.status(200).json_body(
+            serde_json::json!({"total_count": 0, "incomplete_results": false, "items": []}),
+        );
+    });
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":3,"params":{"name":"search_pull_requests","arguments":{"q":"type:issue","repo":"acme/tool"}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    typed.assert();
+    assert_eq!(
+        v["result"]["structuredContent"]["query"],
+        "type:issue repo:acme/tool"
+    );
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":2,"params":{"name":"search_issues","arguments":{"q":"  "}}});
+    let v: serde_json::Value = serde_json::from_str(&run_with_env(&req, &envs)?)?;
+    assert_eq!(v["error"]["code"], -32602);
+    Ok(())
+}
@@ -79,6 +79,41 @@ fn rest_pagination_link_headers() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn graphql_query_cost_is_reported_with_rate() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let m = server.mock(|when, then| {
+        when.method(httpmock::Method::POST)
+            .path("/graphql")
+            .body_contains("rateLimit { cost nodeCount remaining used resetAt }");
+        then.status(200).json_body(serde_json::json!({
+          "data": {"repository": {"issues": {"nodes": [], "pageInfo": {"hasNextPage": false, "endCursor": null}}},
+           "rateLimit": {"cost": 2, "nodeCount": 90, "remaining": 4998, "used": 2, "resetAt": "1970-01-01T00:00:00Z"}}
+        }));
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"list_issues","arguments":{"owner":"o","repo":"r","_include_rate":true}}
+    });
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            (
+                "GITHUB_GRAPHQL_URL",
+                &format!("{}/graphql", server.base_url()),
+            ),
+        ],
+    )?;
+    m.assert();
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let rate = &v["result"]["structuredContent"]["meta"]["rate"];
+    assert_eq!(rate["cost"], 2);
+    assert_eq!(rate["node_count"], 90);
+    assert_eq!(rate["remaining"], 4998);
+    Ok(())
+}
+
 #[test]
 fn graphql_rate_limit_meta() -> anyhow::Result<()> {
     let server = MockServer::start();
@@ -197,3 +232,151 @@ fn rest_non_paginated_meta_default_and_optin() -> anyhow::Result<()> {
     assert!(out2.contains("\"rate\""));
     Ok(())
 }
+
+#[test]
+fn list_commit_statuses_paginates() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _m = server.mock(|when, then| {
+        when.method(GET)
+            .path("/repos/o/r/commits/feature%2Fx/statuses")
+            .query_param("page", "1");
+        then.status(200)
+            .header(
+                "link",
+                format!(
+                    "<{}/repos/o/r/commits/feature%2Fx/statuses?page=2>; rel=\"next\"",
+                    server.base_url()
+                ),
+            )
+            .json_body(serde_json::json!([
+                {"context":"ci/build","state":"success","description":"ok","target_url":"https://ci/1","created_at":"2025-01-01T00:00:00Z"}
+            ]));
+    });
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"list_commit_statuses","arguments":{"owner":"o","repo":"r","ref":"feature/x","limit":1}}});
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_API_URL", server.base_url().as_str()),
+        ],
+    )?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let sc = &v["result"]["structuredContent"];
+    assert_eq!(sc["items"][0]["context"], "ci/build");
+    assert_eq!(sc["meta"]["has_more"], true);
+    assert!(sc["meta"]["next_cursor"].is_string());
+    Ok(())
+}
+
+#[test]
+fn rest_page_fields_are_opt_in() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _m = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/tags");
+        then.status(200)
+            .header(
+                "link",
+                format!(
+                    "<{0}/repos/o/r/tags?per_page=2&page=2>; rel=\"next\", <{0}/repos/o/r/tags?per_page=2&page=5>; rel=\"last\"",
+                    server.base_url()
+                ),
+            )
+            .json_body(serde_json::json!([
+                {"name":"v1","commit":{"sha":"a","url":"u"},"zipball_url":"z","tarball_url":"t"},
+                {"name":"v2","commit":{"sha":"b","url":"u"},"zipball_url":"z","tarball_url":"t"}
+            ]));
+    });
+    let base = server.base_url();
+    let envs = [("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())];
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"list_tags","arguments":{"owner":"o","repo":"r","limit":2}}});
+    let out = run_with_env(&req, &envs)?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let meta = &v["result"]["structuredContent"]["meta"];
+    assert_eq!(meta["has_more"], true);
+    assert!(meta.get("page").is_none());
+    assert!(meta.get("total_pages").is_none());
+
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":2,"params":{"name":"list_tags","arguments":{"owner":"o","repo":"r","limit":2,"_include_pages":true}}});
+    let out = run_with_env(&req, &envs)?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let meta = &v["result"]["structuredContent"]["meta"];
+    assert_eq!(meta["page"], 1);
+    assert_eq!(meta["per_page"], 2);
+    assert_eq!(meta["total_pages"], 5);
+    Ok(())
+}
+
+#[test]
+fn low_rate_budget_adds_warning_without_include_rate() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let _m = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/actions/runs");
+        then.status(200)
+            .header("x-ratelimit-remaining", "40")
+            .header("x-ratelimit-used", "4960")
+            .header("x-ratelimit-reset", "0")
+            .json_body(serde_json::json!({"workflow_runs":[]}));
+    });
+    let req = serde_json::json!({
+        "jsonrpc":"2.0","method":"tools/call","id":1,
+        "params":{"name":"list_workflow_runs_light","arguments":{"owner":"o","repo":"r"}}
+    });
+    let base = server.base_url();
+    let out = run_with_env(
+        &req,
+        &[("GITHUB_TOKEN", "t"), ("GITHUB_API_URL", base.as_str())],
+    )?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let meta = &v["result"]["structuredContent"]["meta"];
+    let warning = meta["warning"].as_str().unwrap();
+    assert!(warning.contains("40 of 5000 remaining"), "{}", warning);
+    assert!(warning.contains("resets at 1970-01-01T00:00:00Z"));
+    assert!(meta.get("rate").is_none());
+
+    // A threshold of 0 disables the warning
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_API_URL", base.as_str()),
+            ("GITHUB_MCP_RATE_WARN_PERCENT", "0"),
+        ],
+    )?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    assert!(v["result"]["structuredContent"].get("meta").is_none());
+    Ok(())
+}
+
+#[test]
+fn get_rate_limit_reports_each_resource_budget() -> anyhow::Result<()> {
+    let server = MockServer::start();
+    let m = server.mock(|when, then| {
+        when.method(GET).path("/rate_limit");
+        then.status(200).json_body(serde_json::json!({
+            "resources": {
+                "core": {"limit": 5000, "remaining": 4990, "used": 10, "reset": 1735689600},
+                "graphql": {"limit": 5000, "remaining": 5000, "used": 0, "reset": 1735689600},
+                "search": {"limit": 30, "remaining": 29, "used": 1, "reset": 1735689600},
+                "code_search": {"limit": 10, "remaining": 10, "used": 0, "reset": 1735689600}
+            },
+            "rate": {"limit": 5000, "remaining": 4990, "used": 10, "reset": 1735689600}
+        }));
+    });
+    let req = serde_json::json!({"jsonrpc":"2.0","method":"tools/call","id":1,"params":{"name":"get_rate_limit","arguments":{}}});
+    let out = run_with_env(
+        &req,
+        &[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_API_URL", &server.base_url()),
+        ],
+    )?;
+    m.assert();
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let item = &v["result"]["structuredContent"]["item"];
+    assert_eq!(item["core"]["remaining"], 4990);
+    assert_eq!(item["core"]["reset_at"], "2025-01-01T00:00:00Z");
+    assert_eq!(item["search"]["limit"], 30);
+    assert_eq!(item["code_search"]["used"], 0);
+    assert_eq!(item["graphql"]["remaining"], 5000);
+    Ok(())
+}
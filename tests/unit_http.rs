@@ -1,9 +1,19 @@
 // remove unused import to satisfy clippy
+use github_mcp::config::Config;
 use github_mcp::http::{
-    decode_rest_cursor, encode_path_segment, encode_rest_cursor, extract_rate_from_rest,
-    map_status_to_error, RestCursor,
+    build_client, decode_rest_cursor, encode_path_segment, encode_rest_cursor,
+    extract_rate_from_rest, map_rest_error, map_status_to_error, node_ids, normalize_ts,
+    rest_delete_opts, rest_get_bytes_follow_redirect, rest_get_json, rest_get_json_opts,
+    rest_patch_json, rest_post_empty, rest_put_bytes, rest_put_empty, rest_put_json,
+    CallDeadlineGuard, RequestOptions, RestCursor,
+};
+use github_mcp::types::ErrorCode;
+use httpmock::{
+    Method::{DELETE, GET, PATCH, POST, PUT},
+    MockServer,
 };
 use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
 
 #[test]
 fn rest_cursor_codec_roundtrip() {
@@ -11,10 +21,51 @@ fn rest_cursor_codec_roundtrip() {
         page: 3,
         per_page: 50,
         path: None,
+        after: None,
     };
     let enc = encode_rest_cursor(c.clone());
     let dec = decode_rest_cursor(&enc).unwrap();
     assert_eq!(c, dec);
+
+    let a = RestCursor {
+        page: 2,
+        per_page: 30,
+        path: None,
+        after: Some("MS4y=".into()),
+    };
+    assert_eq!(
+        decode_rest_cursor(&encode_rest_cursor(a.clone())).unwrap(),
+        a
+    );
+}
+
+#[test]
+fn rest_cursor_decodes_cursors_without_after() {
+    // Cursors handed out before `after` existed: base64url of {"page":2,"per_page":30}
+    let dec = decode_rest_cursor("eyJwYWdlIjoyLCJwZXJfcGFnZSI6MzB9").unwrap();
+    assert_eq!((dec.page, dec.per_page), (2, 30));
+    assert_eq!(dec.after, None);
+    assert!(!encode_rest_cursor(dec).is_empty());
+}
+
+#[test]
+fn error_codes_serialize_to_wire_strings() {
+    for code in [
+        ErrorCode::BadRequest,
+        ErrorCode::RateLimited,
+        ErrorCode::SsoRequired,
+        ErrorCode::MergeNotAllowed,
+        ErrorCode::SearchLimitReached,
+        ErrorCode::Timeout,
+    ] {
+        let v = serde_json::to_value(code).unwrap();
+        assert_eq!(v, code.as_str());
+        assert_eq!(serde_json::from_value::<ErrorCode>(v).unwrap(), code);
+    }
+    assert_eq!(
+        serde_json::to_value(ErrorCode::UpstreamError).unwrap(),
+        "upstream_error"
+    );
 }
 
 #[test]
@@ -47,3 +98,574 @@ fn url_path_segment_encoding() {
     // Unreserved characters remain as-is
     assert_eq!(encode_path_segment("abc-._~123"), "abc-._~123");
 }
+
+#[test]
+fn timestamps_normalize_to_utc() {
+    assert_eq!(
+        normalize_ts("2025-01-01T02:00:00+02:00".into()),
+        "2025-01-01T00:00:00Z"
+    );
+    assert_eq!(
+        normalize_ts("2025-01-01T00:00:00.123Z".into()),
+        "2025-01-01T00:00:00Z"
+    );
+    // Unparseable values pass through untouched
+    assert_eq!(normalize_ts("yesterday".into()), "yesterday");
+}
+
+#[test]
+fn raw_bytes_put_sends_content_type_and_parses_json() {
+    let server = MockServer::start();
+    let m = server.mock(|when, then| {
+        when.method(PUT)
+            .path("/blob")
+            .header("content-type", "application/octet-stream")
+            .body("\x01\x02");
+        then.status(200).json_body(serde_json::json!({"ok": true}));
+    });
+    let cfg = Config {
+        token: "t".into(),
+        token_file: None,
+        api_url: server.base_url(),
+        graphql_url: format!("{}/graphql", server.base_url()),
+        api_version: "2022-11-28".into(),
+        user_agent: "test".into(),
+        timeout_secs: 5,
+        graphql_timeout_secs: 5,
+        rate_warn_percent: 10,
+        max_response_bytes: 0,
+        call_deadline_secs: None,
+        slow_ms: 0,
+    };
+    let client = build_client(&cfg).unwrap();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let resp = rt.block_on(rest_put_bytes::<serde_json::Value>(
+        &client,
+        &cfg,
+        "/blob",
+        "application/octet-stream",
+        vec![1, 2],
+    ));
+    m.assert();
+    assert!(resp.error.is_none());
+    assert_eq!(resp.value.unwrap()["ok"], true);
+    // An upload that failed upstream may already have left a placeholder asset; it is
+    // reported once rather than retried into a duplicate-name 422.
+    let failed = server.mock(|when, then| {
+        when.method(PUT).path("/blob-5xx");
+        then.status(502);
+    });
+    let resp = rt.block_on(rest_put_bytes::<serde_json::Value>(
+        &client,
+        &cfg,
+        "/blob-5xx",
+        "application/octet-stream",
+        vec![1, 2],
+    ));
+    assert!(resp.error.is_some());
+    failed.assert_hits(1);
+}
+
+#[test]
+fn sso_header_maps_to_sso_required() {
+    let mut h = HeaderMap::new();
+    h.insert(
+        "x-github-sso",
+        "required; url=https://github.com/orgs/acme/sso?authorization_request=abc"
+            .parse()
+            .unwrap(),
+    );
+    let e = map_rest_error(reqwest::StatusCode::FORBIDDEN, &h, "denied".into());
+    assert_eq!(e.code, "sso_required");
+    assert!(!e.retriable);
+    assert!(e
+        .message
+        .contains("https://github.com/orgs/acme/sso?authorization_request=abc"));
+    // Plain 403 without the header stays forbidden
+    let e = map_rest_error(
+        reqwest::StatusCode::FORBIDDEN,
+        &HeaderMap::new(),
+        "x".into(),
+    );
+    assert_eq!(e.code, "forbidden");
+}
+
+#[test]
+fn not_found_message_hints_at_missing_access() {
+    let e = map_rest_error(
+        reqwest::StatusCode::NOT_FOUND,
+        &HeaderMap::new(),
+        "Not Found".into(),
+    );
+    assert_eq!(e.code, "not_found");
+    assert!(e.message.starts_with("Not Found"));
+    assert!(e
+        .message
+        .contains("private resources the token cannot access"));
+    // A classic token without `repo` gets its scopes named
+    let mut h = HeaderMap::new();
+    h.insert("x-oauth-scopes", "public_repo, read:org".parse().unwrap());
+    let e = map_rest_error(reqwest::StatusCode::NOT_FOUND, &h, "Not Found".into());
+    assert_eq!(e.code, "not_found");
+    assert!(e
+        .message
+        .contains("[public_repo, read:org] do not include `repo`"));
+    // With `repo` the generic hint is used
+    h.insert("x-oauth-scopes", "repo, workflow".parse().unwrap());
+    let e = map_rest_error(reqwest::StatusCode::NOT_FOUND, &h, "Not Found".into());
+    assert!(!e.message.contains("do not include"));
+}
+
+#[test]
+fn token_file_is_reread_when_mtime_changes() {
+    let server = MockServer::start();
+    let first = server.mock(|when, then| {
+        when.method(GET)
+            .path("/t")
+            .header("authorization", "Bearer one");
+        then.status(200).json_body(serde_json::json!({}));
+    });
+    let second = server.mock(|when, then| {
+        when.method(GET)
+            .path("/t")
+            .header("authorization", "Bearer two");
+        then.status(200).json_body(serde_json::json!({}));
+    });
+    let path = std::env::temp_dir().join(format!("github-mcp-token-{}", std::process::id()));
+    std::fs::write(&path, "one\n").unwrap();
+    let cfg = Config {
+        token: "env".into(),
+        token_file: Some(path.to_string_lossy().into_owned()),
+        api_url: server.base_url(),
+        graphql_url: format!("{}/graphql", server.base_url()),
+        api_version: "2022-11-28".into(),
+        user_agent: "test".into(),
+        timeout_secs: 5,
+        graphql_timeout_secs: 5,
+        rate_warn_percent: 10,
+        max_response_bytes: 0,
+        call_deadline_secs: None,
+        slow_ms: 0,
+    };
+    let client = build_client(&cfg).unwrap();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let get = || rt.block_on(rest_get_json::<serde_json::Value>(&client, &cfg, "/t"));
+    assert!(get().error.is_none());
+    first.assert();
+
+    std::fs::write(&path, "two\n").unwrap();
+    // Force a distinct mtime in case the filesystem clock is coarse.
+    let later = std::time::SystemTime::now() + std::time::Duration::from_secs(5);
+    std::fs::File::options()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_modified(later)
+        .unwrap();
+    assert!(get().error.is_none());
+    second.assert();
+    first.assert_hits(1);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn request_options_override_accept_and_api_version() {
+    let server = MockServer::start();
+    let defaults = server.mock(|when, then| {
+        when.method(GET)
+            .path("/d")
+            .header("accept", "application/vnd.github+json")
+            .header("x-github-api-version", "2022-11-28");
+        then.status(200).json_body(serde_json::json!({}));
+    });
+    let overridden = server.mock(|when, then| {
+        when.method(GET)
+            .path("/o")
+            .header("accept", "application/vnd.github.mercy-preview+json")
+            .header("x-github-api-version", "2026-03-10");
+        then.status(200).json_body(serde_json::json!({}));
+    });
+    let cfg = Config {
+        token: "t".into(),
+        token_file: None,
+        api_url: server.base_url(),
+        graphql_url: format!("{}/graphql", server.base_url()),
+        api_version: "2022-11-28".into(),
+        user_agent: "test".into(),
+        timeout_secs: 5,
+        graphql_timeout_secs: 5,
+        rate_warn_percent: 10,
+        max_response_bytes: 0,
+        call_deadline_secs: None,
+        slow_ms: 0,
+    };
+    let client = build_client(&cfg).unwrap();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let resp = rt.block_on(rest_get_json::<serde_json::Value>(&client, &cfg, "/d"));
+    assert!(resp.error.is_none());
+    let opts = RequestOptions {
+        accept: Some("application/vnd.github.mercy-preview+json"),
+        api_version: Some("2026-03-10"),
+        ..Default::default()
+    };
+    let resp = rt.block_on(rest_get_json_opts::<serde_json::Value>(
+        &client, &cfg, "/o", opts,
+    ));
+    assert!(resp.error.is_none());
+    defaults.assert();
+    overridden.assert();
+}
+
+#[test]
+fn empty_post_and_put_accept_bodyless_success() {
+    let server = MockServer::start();
+    let post = server.mock(|when, then| {
+        when.method(POST).path("/runs/1/cancel");
+        then.status(202);
+    });
+    let put = server.mock(|when, then| {
+        when.method(PUT).path("/user/starred/o/r");
+        then.status(204);
+    });
+    let cfg = Config {
+        token: "t".into(),
+        token_file: None,
+        api_url: server.base_url(),
+        graphql_url: format!("{}/graphql", server.base_url()),
+        api_version: "2022-11-28".into(),
+        user_agent: "test".into(),
+        timeout_secs: 5,
+        graphql_timeout_secs: 5,
+        rate_warn_percent: 10,
+        max_response_bytes: 0,
+        call_deadline_secs: None,
+        slow_ms: 0,
+    };
+    let client = build_client(&cfg).unwrap();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let resp = rt.block_on(rest_post_empty(&client, &cfg, "/runs/1/cancel"));
+    assert!(resp.error.is_none());
+    assert_eq!(resp.status.as_u16(), 202);
+    let resp = rt.block_on(rest_put_empty(&client, &cfg, "/user/starred/o/r"));
+    assert!(resp.error.is_none());
+    assert_eq!(resp.status.as_u16(), 204);
+    post.assert();
+    put.assert();
+}
+
+#[test]
+fn accepted_with_empty_body_is_success_and_accept_statuses_override() {
+    let server = MockServer::start();
+    let queued = server.mock(|when, then| {
+        when.method(PUT).path("/pulls/1/update-branch");
+        then.status(202);
+    });
+    let gone = server.mock(|when, then| {
+        when.method(DELETE).path("/refs/heads/x");
+        then.status(404)
+            .json_body(serde_json::json!({"message":"Not Found"}));
+    });
+    let cfg = Config {
+        token: "t".into(),
+        token_file: None,
+        api_url: server.base_url(),
+        graphql_url: format!("{}/graphql", server.base_url()),
+        api_version: "2022-11-28".into(),
+        user_agent: "test".into(),
+        timeout_secs: 5,
+        graphql_timeout_secs: 5,
+        rate_warn_percent: 10,
+        max_response_bytes: 0,
+        call_deadline_secs: None,
+        slow_ms: 0,
+    };
+    let client = build_client(&cfg).unwrap();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let resp = rt.block_on(rest_put_json::<_, serde_json::Value>(
+        &client,
+        &cfg,
+        "/pulls/1/update-branch",
+        &serde_json::json!({}),
+    ));
+    assert!(resp.error.is_none());
+    assert!(resp.value.is_none());
+    assert_eq!(resp.status, StatusCode::ACCEPTED);
+    // By default a 404 is an error; an idempotent delete can opt in to treating it as done.
+    let resp = rt.block_on(rest_delete_opts(
+        &client,
+        &cfg,
+        "/refs/heads/x",
+        RequestOptions::default(),
+    ));
+    assert_eq!(resp.error.map(|e| e.code), Some(ErrorCode::NotFound));
+    let opts = RequestOptions {
+        accept_statuses: Some(&[StatusCode::NO_CONTENT, StatusCode::NOT_FOUND]),
+        ..Default::default()
+    };
+    let resp = rt.block_on(rest_delete_opts(&client, &cfg, "/refs/heads/x", opts));
+    assert!(resp.error.is_none());
+    queued.assert();
+    gone.assert_hits(2);
+}
+
+#[test]
+fn no_content_success_yields_no_value_without_error() {
+    let server = MockServer::start();
+    let enable = server.mock(|when, then| {
+        when.method(PUT).path("/actions/workflows/7/enable");
+        then.status(204);
+    });
+    let patch = server.mock(|when, then| {
+        when.method(PATCH).path("/notifications/threads/1");
+        then.status(205).body("  ");
+    });
+    let cfg = Config {
+        token: "t".into(),
+        token_file: None,
+        api_url: server.base_url(),
+        graphql_url: format!("{}/graphql", server.base_url()),
+        api_version: "2022-11-28".into(),
+        user_agent: "test".into(),
+        timeout_secs: 5,
+        graphql_timeout_secs: 5,
+        rate_warn_percent: 10,
+        max_response_bytes: 0,
+        call_deadline_secs: None,
+        slow_ms: 0,
+    };
+    let client = build_client(&cfg).unwrap();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let resp = rt.block_on(rest_put_json::<_, serde_json::Value>(
+        &client,
+        &cfg,
+        "/actions/workflows/7/enable",
+        &serde_json::json!({}),
+    ));
+    assert!(resp.error.is_none());
+    assert!(resp.value.is_none());
+    assert_eq!(resp.status, StatusCode::NO_CONTENT);
+    let resp = rt.block_on(rest_patch_json::<_, serde_json::Value>(
+        &client,
+        &cfg,
+        "/notifications/threads/1",
+        &serde_json::json!({}),
+    ));
+    assert!(resp.error.is_none());
+    assert!(resp.value.is_none());
+    // A 200 that should carry a payload but arrives empty is still an error, so callers
+    // never see a success without a value.
+    let empty = server.mock(|when, then| {
+        when.method(GET).path("/repos/o/r/actions/workflows/7");
+        then.status(200);
+    });
+    let resp = rt.block_on(rest_get_json::<serde_json::Value>(
+        &client,
+        &cfg,
+        "/repos/o/r/actions/workflows/7",
+    ));
+    assert!(resp.value.is_none());
+    assert!(resp.error.is_some());
+    enable.assert();
+    patch.assert();
+    empty.assert();
+}
+
+#[test]
+fn call_deadline_bounds_retries_across_requests() {
+    let server = MockServer::start();
+    let flaky = server.mock(|when, then| {
+        when.method(GET).path("/flaky");
+        then.status(502);
+    });
+    let cfg = Config {
+        token: "t".into(),
+        token_file: None,
+        api_url: server.base_url(),
+        graphql_url: format!("{}/graphql", server.base_url()),
+        api_version: "2022-11-28".into(),
+        user_agent: "test".into(),
+        timeout_secs: 5,
+        graphql_timeout_secs: 5,
+        rate_warn_percent: 10,
+        max_response_bytes: 0,
+        call_deadline_secs: None,
+        slow_ms: 0,
+    };
+    let client = build_client(&cfg).unwrap();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let start = std::time::Instant::now();
+    let _guard = CallDeadlineGuard::set(Some(start + std::time::Duration::from_millis(700)));
+    let first = rt.block_on(rest_get_json::<serde_json::Value>(&client, &cfg, "/flaky"));
+    let second = rt.block_on(rest_get_json::<serde_json::Value>(&client, &cfg, "/flaky"));
+    // Five retries with backoff would take several seconds per request
+    assert!(start.elapsed() < std::time::Duration::from_secs(2));
+    for resp in [first, second] {
+        let err = resp.error.unwrap();
+        assert_eq!(err.code, ErrorCode::DeadlineExceeded);
+        assert!(err.retriable);
+    }
+    let hits = flaky.hits();
+    assert!((1..6).contains(&hits), "hits={}", hits);
+}
+
+#[test]
+fn review_thread_lookup_pages_until_the_comment_is_found() {
+    let server = MockServer::start();
+    let second = server.mock(|when, then| {
+        when.method(POST)
+            .path("/graphql")
+            .body_contains("ResolveThreadId")
+            .body_contains("\"after\":\"c1\"");
+        then.status(200).json_body(serde_json::json!({
+          "data": {"repository": {"pullRequest": {"reviewThreads": {
+            "nodes": [{"id":"PRRT_b","comments":{"nodes":[{"databaseId":12}]}}],
+            "pageInfo": {"hasNextPage": false, "endCursor": null}
+          }}}}
+        }));
+    });
+    let first = server.mock(|when, then| {
+        when.method(POST)
+            .path("/graphql")
+            .body_contains("ResolveThreadId")
+            .body_contains("\"after\":null");
+        then.status(200).json_body(serde_json::json!({
+          "data": {"repository": {"pullRequest": {"reviewThreads": {
+            "nodes": [{"id":"PRRT_a","comments":{"nodes":[{"databaseId":10}]}}],
+            "pageInfo": {"hasNextPage": true, "endCursor": "c1"}
+          }}}}
+        }));
+    });
+    let cfg = Config {
+        token: "t".into(),
+        token_file: None,
+        api_url: server.base_url(),
+        graphql_url: format!("{}/graphql", server.base_url()),
+        api_version: "2022-11-28".into(),
+        user_agent: "test".into(),
+        timeout_secs: 5,
+        graphql_timeout_secs: 5,
+        rate_warn_percent: 10,
+        max_response_bytes: 0,
+        call_deadline_secs: None,
+        slow_ms: 0,
+    };
+    let client = build_client(&cfg).unwrap();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let found = rt.block_on(node_ids::resolve_review_thread_id(
+        &client, &cfg, "o", "r", 5, 12,
+    ));
+    assert_eq!(found.unwrap(), "PRRT_b");
+    first.assert_hits(1);
+    second.assert_hits(1);
+    // A comment on no thread is not_found once the last page is scanned
+    let missing = rt.block_on(node_ids::resolve_review_thread_id(
+        &client, &cfg, "o", "r", 5, 99,
+    ));
+    assert_eq!(missing.unwrap_err().code, ErrorCode::NotFound);
+    first.assert_hits(2);
+    second.assert_hits(2);
+}
+
+// Answers successive connections with `statuses` in order (httpmock can't sequence replies).
+fn serve_statuses(statuses: &'static [u16]) -> String {
+    serve_responses(statuses.iter().map(|&s| (s, "")).collect())
+}
+
+// Like serve_statuses, with extra raw header lines ("name: value\r\n") per reply.
+fn serve_responses(replies: Vec<(u16, &'static str)>) -> String {
+    use std::io::{Read, Write};
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        for (stream, (status, extra)) in listener.incoming().zip(replies) {
+            let mut stream = stream.unwrap();
+            let mut buf = [0u8; 4096];
+            let _ = stream.read(&mut buf);
+            let body = r#"{"message":"x"}"#;
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {} X\r\ncontent-type: application/json\r\ncontent-length: {}\r\n{}connection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                extra,
+                body
+            );
+        }
+    });
+    format!("http://{}", addr)
+}
+
+#[test]
+fn delete_retried_after_5xx_treats_404_as_done() {
+    let base = serve_statuses(&[502, 404]);
+    let cfg = Config {
+        token: "t".into(),
+        token_file: None,
+        api_url: base.clone(),
+        graphql_url: format!("{}/graphql", base),
+        api_version: "2022-11-28".into(),
+        user_agent: "test".into(),
+        timeout_secs: 5,
+        graphql_timeout_secs: 5,
+        rate_warn_percent: 10,
+        max_response_bytes: 0,
+        call_deadline_secs: None,
+        slow_ms: 0,
+    };
+    let client = build_client(&cfg).unwrap();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let resp = rt.block_on(rest_delete_opts(
+        &client,
+        &cfg,
+        "/repos/o/r/git/refs/heads/x",
+        RequestOptions::default(),
+    ));
+    assert!(resp.error.is_none());
+    assert_eq!(resp.status, StatusCode::NOT_FOUND);
+
+    // Without a failed attempt before it, a 404 is still not_found
+    let base = serve_statuses(&[404]);
+    let cfg = Config {
+        api_url: base,
+        ..cfg
+    };
+    let resp = rt.block_on(rest_delete_opts(
+        &client,
+        &cfg,
+        "/repos/o/r/git/refs/heads/x",
+        RequestOptions::default(),
+    ));
+    assert_eq!(resp.error.map(|e| e.code), Some(ErrorCode::NotFound));
+}
+
+#[test]
+fn redirect_download_honours_retry_after() {
+    let base = serve_responses(vec![(429, "retry-after: 1\r\n"), (200, "")]);
+    let cfg = Config {
+        token: "t".into(),
+        token_file: None,
+        api_url: base.clone(),
+        graphql_url: format!("{}/graphql", base),
+        api_version: "2022-11-28".into(),
+        user_agent: "test".into(),
+        timeout_secs: 5,
+        graphql_timeout_secs: 5,
+        rate_warn_percent: 10,
+        max_response_bytes: 0,
+        call_deadline_secs: None,
+        slow_ms: 0,
+    };
+    let client = build_client(&cfg).unwrap();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let started = std::time::Instant::now();
+    let resp = rt.block_on(rest_get_bytes_follow_redirect(
+        &client,
+        &cfg,
+        "/repos/o/r/actions/jobs/1/logs",
+        "application/vnd.github+json",
+    ));
+    assert!(resp.error.is_none());
+    assert_eq!(resp.value.as_deref(), Some(&br#"{"message":"x"}"#[..]));
+    // The default backoff for a first retry is well under a second
+    assert!(started.elapsed() >= std::time::Duration::from_secs(1));
+}